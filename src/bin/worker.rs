@@ -0,0 +1,26 @@
+//! Web Worker that runs batches of programs away from the page,
+//! so the page stays responsive and a batch can be cancelled at any time.
+
+use simplicity_webide::worker;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
+
+fn main() {
+    console_error_panic_hook::set_once();
+
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    let reply_scope = scope.clone();
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        // An empty response tells the page that the request was not understood
+        let response = event
+            .data()
+            .as_string()
+            .and_then(|request| worker::handle_request(&request))
+            .unwrap_or_default();
+        let _ = reply_scope.post_message(&JsValue::from_str(&response));
+    });
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    // The worker handles messages until the page terminates it
+    on_message.forget();
+}
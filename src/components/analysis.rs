@@ -1,9 +1,17 @@
-use leptos::{component, view, IntoView, Signal, SignalGet};
-use std::str::FromStr;
+use hex_conservative::DisplayHex;
+use leptos::{
+    component, create_memo, create_rw_signal, event_target_value, use_context, view, IntoView,
+    Signal, SignalGet, SignalSet, SignalWith, SignalWithUntracked,
+};
 use std::sync::Arc;
 
-use crate::util;
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::Program;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::pipeline::ProgramError;
 use crate::util::Expression;
+use crate::util::{encoding, report};
+use crate::{download, util};
 
 #[component]
 pub fn Analysis(program: Signal<Option<Arc<Expression>>>) -> impl IntoView {
@@ -21,47 +29,279 @@ pub fn Analysis(program: Signal<Option<Arc<Expression>>>) -> impl IntoView {
     }
 }
 
+/// Merkle root of a Simplicity program.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MerkleRoot {
+    Cmr,
+    Imr,
+    Amr,
+}
+
+impl MerkleRoot {
+    const ALL: [Self; 3] = [Self::Cmr, Self::Imr, Self::Amr];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Cmr => "CMR",
+            Self::Imr => "IMR",
+            Self::Amr => "AMR",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cmr => "CMR:",
+            Self::Imr => "IMR:",
+            Self::Amr => "AMR:",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Cmr => "Commitment Merkle root: commits to the program without its witness data. The CMR determines the address of the program.",
+            Self::Imr => "Identity Merkle root: commits to the program together with its witness data and pruning. Two spends of the same address can have different IMRs.",
+            Self::Amr => "Annotated Merkle root: commits to the program with its witness data and the types of all nodes.",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|root| root.name() == name)
+    }
+}
+
 const MILLISECONDS_PER_WU: f64 = 0.5 / 1000.0;
 
 #[component]
 fn AnalysisInner(expression: Arc<Expression>) -> impl IntoView {
     let bounds = expression.bounds();
-    // FIXME: Add conversion method to simplicity::Cost
-    let milli_weight = u32::from_str(&bounds.cost.to_string()).unwrap();
-    let weight = milli_weight.saturating_add(999) / 1000;
+    let weight = util::weight(&expression);
     let virtual_size = weight.div_ceil(4);
     let size = weight; // Simplicity programs are Taproot witness data
     let max_milliseconds = format!("{:.3}", f64::from(weight) * MILLISECONDS_PER_WU);
     let max_bytes = bounds.extra_cells.div_ceil(8);
     let compression = util::get_compression_factor(&expression);
+    let metrics = util::get_complexity_metrics(&expression);
+    let mut program_bytes = Vec::new();
+    let program_bits = expression
+        .encode_without_witness(&mut program_bytes)
+        .expect("Vec::write is infallible");
+    let bit_string = util::bit_string(&program_bytes);
+    let (_, witness_bytes) = expression.to_vec_with_witness();
+    let cmr = expression.cmr().to_string();
+    // The IHR of the redeemed program is its IMR
+    let imr = expression.ihr().to_string();
+    let amr = expression.amr().to_string();
+    let shown_root = create_rw_signal(MerkleRoot::Cmr);
+    let root_options = MerkleRoot::ALL
+        .into_iter()
+        .map(|root| view! { <option value=root.name()>{root.name()}</option> })
+        .collect::<Vec<_>>();
+    let root_item = move || {
+        let root = shown_root.get();
+        let data = match root {
+            MerkleRoot::Cmr => cmr.clone(),
+            MerkleRoot::Imr => imr.clone(),
+            MerkleRoot::Amr => amr.clone(),
+        };
+        view! { <AnalysisItem label=root.label() data=data /> }
+    };
+    let root_description = move || shown_root.get().description();
+    let arrow = expression.arrow();
+    let ty = util::type_arrow(arrow);
+    let constant_warning = util::constant_outcome(&expression).map(|success| match success {
+        true => "The program always succeeds: it takes no witness data and does not read the transaction, so anyone can spend the coins.",
+        false => "The program always fails: it takes no witness data and does not read the transaction, so the coins can never be spent.",
+    });
 
     view! {
         <div class="analysis">
+            {constant_warning.map(|warning| view! { <div class="warning-box">{warning}</div> })}
+            <div class="analysis-root">
+                <label>
+                    "Merkle root "
+                    <select
+                        prop:value=move || shown_root.get().name()
+                        on:change=move |event| {
+                            if let Some(root) = MerkleRoot::from_name(&event_target_value(&event)) {
+                                shown_root.set(root);
+                            }
+                        }
+                    >
+                        {root_options}
+                    </select>
+                </label>
+                <p class="analysis-root-description">{root_description}</p>
+                <CopyBugReport />
+                <ExportReport expression=expression.clone() />
+            </div>
             <div class="analysis-body">
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Size:"</div>
-                    <div class="analysis-item-data">{size}"B"</div>
-                </div>
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Virtual size:"</div>
-                    <div class="analysis-item-data">{virtual_size}"vB"</div>
-                </div>
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Maximum memory:"</div>
-                    <div class="analysis-item-data">{max_bytes}"B"</div>
-                </div>
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Weight:"</div>
-                    <div class="analysis-item-data">{weight}"WU"</div>
-                </div>
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Maximum runtime:"</div>
-                    <div class="analysis-item-data">{max_milliseconds}"ms"</div>
-                </div>
-                <div class="analysis-item">
-                    <div class="analysis-item-label">"Program compression:"</div>
-                    <div class="analysis-item-data">{compression}"x"</div>
+                {root_item}
+                <AnalysisItem label="Type:" data=ty />
+                <AnalysisItem label="Size:" data=format!("{size}B") />
+                <AnalysisItem label="Virtual size:" data=format!("{virtual_size}vB") />
+                <AnalysisItem label="Maximum memory:" data=format!("{max_bytes}B") />
+                <AnalysisItem label="Weight:" data=format!("{weight}WU") />
+                <AnalysisItem label="Maximum runtime:" data=format!("{max_milliseconds}ms") />
+                <AnalysisItem label="Program compression:" data=format!("{compression}x") />
+                <AnalysisItem label="Unique nodes:" data=metrics.unique_nodes.to_string() />
+                <AnalysisItem label="Expanded nodes:" data=metrics.total_nodes.to_string() />
+                <AnalysisItem label="Shared nodes:" data=metrics.shared_nodes.to_string() />
+                <AnalysisItem label="Maximum depth:" data=metrics.max_depth.to_string() />
+            </div>
+            <div class="analysis-encoding">
+                <div class="analysis-item-label">"Bit encoding ("{program_bits}" bits):"</div>
+                <pre class="analysis-encoding-bits">{bit_string}</pre>
+            </div>
+            <WitnessEncoding bytes=witness_bytes />
+        </div>
+    }
+}
+
+/// Type and size of the program, which follow each compilation.
+///
+/// Unlike the full analysis, these facts only need a program that compiles,
+/// except for the witness size, which needs a satisfied program.
+#[component]
+pub fn ProgramMetrics() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let compiled = create_memo(move |_| {
+        program
+            .lazy_compiled
+            .with(|compiled| compiled.arrow.clone().zip(compiled.commit_nodes))
+    });
+    let witness_size = create_memo(move |_| {
+        program
+            .satisfied()
+            .ok()
+            .map(|satisfied| encoding::encode_satisfied(&satisfied).witness.len())
+    });
+
+    move || match compiled.get() {
+        Some((arrow, nodes)) => {
+            let witness_size = match witness_size.get() {
+                Some(bytes) => format!("{bytes}B"),
+                None => "The witness data does not satisfy the program".to_string(),
+            };
+            view! {
+                <div class="analysis-body analysis-metrics">
+                    <AnalysisItem label="Type:" data=arrow />
+                    <AnalysisItem label="Commitment nodes:" data=nodes.to_string() />
+                    <AnalysisItem label="Witness size:" data=witness_size />
                 </div>
+            }
+            .into_view()
+        }
+        None => view! {
+            <div class="neutral-box">"Type and size appear once the program compiles."</div>
+        }
+        .into_view(),
+    }
+}
+
+/// Serialized witness data of the program, as it appears on the wire next to the program.
+#[component]
+fn WitnessEncoding(bytes: Vec<u8>) -> impl IntoView {
+    if bytes.is_empty() {
+        return view! {
+            <div class="analysis-encoding">
+                <div class="analysis-item-label">"Witness encoding (0 bytes):"</div>
+                <p class="analysis-encoding-bits">"The program has no witness data."</p>
+            </div>
+        };
+    }
+    let hex = bytes.as_hex().to_string();
+    let content = {
+        let hex = hex.clone();
+        Signal::derive(move || hex.clone())
+    };
+
+    view! {
+        <div class="analysis-encoding">
+            <div class="analysis-encoding-header">
+                <div class="analysis-item-label">"Witness encoding ("{bytes.len()}" bytes):"</div>
+                <CopyToClipboard content=content class="copy-button analysis-copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <pre class="analysis-encoding-bits">{hex}</pre>
+            <pre class="analysis-encoding-bits">{util::bit_string(&bytes)}</pre>
+        </div>
+    }
+}
+
+/// Format the program `text` with its CMR, to be pasted into a bug report.
+fn bug_report(text: &str, cmr: &Result<simplicityhl::simplicity::Cmr, ProgramError>) -> String {
+    let cmr = match cmr {
+        Ok(cmr) => cmr.to_string(),
+        Err(..) => "the program does not compile".to_string(),
+    };
+    format!("CMR: {cmr}\n\n```simplicityhl\n{}\n```\n", text.trim_end())
+}
+
+/// Button that copies the source of the program together with its CMR.
+#[component]
+fn CopyBugReport() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let content =
+        Signal::derive(move || program.text.with(|text| bug_report(text, &program.cmr())));
+
+    view! {
+        <CopyToClipboard content=content class="button analysis-bug-report">
+            <i class="far fa-copy"></i>
+            " Copy source & CMR"
+        </CopyToClipboard>
+    }
+}
+
+/// Buttons that copy or download a Markdown report that summarizes the program.
+#[component]
+fn ExportReport(expression: Arc<Expression>) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let content = {
+        let expression = expression.clone();
+        Signal::derive(move || program.text.with(|text| report::report(text, &expression)))
+    };
+    let download_report = move |_| {
+        let text = program
+            .text
+            .with_untracked(|text| report::report(text, &expression));
+        if download::download_file("program-report.md", "text/markdown", text.as_bytes()).is_err() {
+            toasts.push(
+                ToastKind::Error,
+                "The browser could not download the report",
+            );
+        }
+    };
+
+    view! {
+        <CopyToClipboard content=content class="button analysis-bug-report">
+            <i class="far fa-copy"></i>
+            " Copy report"
+        </CopyToClipboard>
+        <button class="button analysis-bug-report" type="button" on:click=download_report>
+            <i class="fas fa-file-download"></i>
+            " Download report"
+        </button>
+    }
+}
+
+/// Labeled fact about the program, with a button to copy it.
+#[component]
+fn AnalysisItem(label: &'static str, data: String) -> impl IntoView {
+    let content = {
+        let data = data.clone();
+        Signal::derive(move || data.clone())
+    };
+
+    view! {
+        <div class="analysis-item">
+            <div class="analysis-item-label">{label}</div>
+            <div class="analysis-item-data">
+                <span class="analysis-item-value">{data}</span>
+                <CopyToClipboard content=content class="copy-button analysis-copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
             </div>
         </div>
     }
@@ -1,11 +1,28 @@
-use leptos::{component, provide_context, view, IntoView, RwSignal};
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_effect, ev, provide_context, spawn_local, store_value, view,
+    window_event_listener, IntoView, RwSignal, SignalGetUntracked, SignalSet, SignalWith,
+    SignalWithUntracked,
+};
 
-use super::program_window::{select_example, Program, ProgramWindow, Runtime};
+use super::program_window::{
+    select_example, Program, ProgramTabs, ProgramWindow, ReadOnly, Runtime, SavedPrograms,
+    SharedState, ShowDesugared,
+};
+use crate::components::copy_to_clipboard::{ManualCopy, ManualCopyBox};
 use crate::components::footer::Footer;
 use crate::components::navigation::Navigation;
-use crate::components::run_window::{HashCount, KeyCount, RunWindow, SignedData, TxEnv};
-use crate::components::state::LocalStorage;
+use crate::components::run_window::{
+    HashCount, KeyCount, RunWindow, SavedTxEnvs, SignedData, TxEnv, WitnessSeed, WitnessSets,
+};
+use crate::components::shortcuts::ShortcutsOverlay;
+use crate::components::state::{autosave, load_settings, LocalStorage};
+use crate::components::toast::{ToastList, Toasts};
+use crate::components::tour::Tour;
+use crate::components::whats_new::WhatsNew;
 use crate::examples;
+use crate::jet;
+use crate::pipeline::ProgramError;
 use crate::transaction::TxParams;
 use crate::url_sharing;
 use crate::util::{HashedData, SigningKeys};
@@ -13,7 +30,8 @@ use crate::util::{HashedData, SigningKeys};
 #[derive(Copy, Clone, Debug, Default)]
 pub struct ActiveRunTab(pub RwSignal<&'static str>);
 
-/// Controls which view is shown in `ProgramWindow`: "Run" (code editor) or "Analyze" (DAG view)
+/// Controls which view is shown in `ProgramWindow`:
+/// "Run" (code editor), "Analyze" (DAG view) or "Compare" (two programs side by side)
 #[derive(Copy, Clone, Debug)]
 pub struct ActiveProgramView(pub RwSignal<&'static str>);
 
@@ -23,36 +41,227 @@ impl Default for ActiveProgramView {
     }
 }
 
+/// Whether the IDE renders the minimal layout for embedding in other pages.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EmbedMode(pub bool);
+
+/// Whether the page that links to the IDE allows the help button.
+#[derive(Copy, Clone, Debug)]
+pub struct HelpAllowed(pub bool);
+
+/// Time after the last edit until the share link in the address bar is updated, in milliseconds.
+const SHARE_LINK_DELAY_MS: u32 = 500;
+
+/// Keep the program of the share link in the address bar up to date with the edits,
+/// so that reloading the page keeps them.
+///
+/// Consecutive edits are debounced, like automatic saving.
+fn follow_edits_in_share_link(program: Program) {
+    let generation = store_value(0_usize);
+    create_effect(move |previous: Option<()>| {
+        program.text.with(|_| ());
+        // The loaded program is already in the link
+        if previous.is_none() {
+            return;
+        }
+        generation.update_value(|generation| *generation += 1);
+        let current = generation.get_value();
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(SHARE_LINK_DELAY_MS).await;
+            if generation.get_value() == current {
+                url_sharing::update_shared_program(&program.text_to_store());
+            }
+        });
+    });
+}
+
+/// Compile the program shortly after each edit, away from the render that follows the edit.
+fn compile_after_edits(program: Program) {
+    create_effect(move |_| {
+        program.text.with(|_| ());
+        program.compile_debounced();
+    });
+}
+
+/// Check whether the key with Alt types a character into a text field,
+/// such as "å" with Option+A on macOS, so a shortcut must not take the key.
+fn alt_types_character(event: &ev::KeyboardEvent) -> bool {
+    let in_text_field = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA")
+                || element.is_content_editable()
+        });
+    // Without a typed character, the key is the plain letter
+    let letter = char::from_u32(event.key_code()).map(|c| c.to_string());
+    in_text_field && letter.is_some_and(|letter| !event.key().eq_ignore_ascii_case(&letter))
+}
+
+const A_KEY: u32 = 65;
+const R_KEY: u32 = 82;
+const W_KEY: u32 = 87;
+
 #[component]
 pub fn App() -> impl IntoView {
-    let program = match url_sharing::read_shared_program() {
-        Some(Ok(shared_text)) => Program::new(shared_text),
-        Some(Err(())) => Program::new("// The shared link could not be decoded.\n".to_string()),
-        None => Program::load_from_storage().unwrap_or_default(),
+    let toasts = Toasts::default();
+    provide_context(toasts);
+    provide_context(ManualCopy::default());
+    let (shared_text, repro_params) = match url_sharing::read_repro() {
+        Some(Ok((text, params))) => (Some(Ok(text)), Some(params)),
+        Some(Err(())) => (Some(Err(())), None),
+        None => (url_sharing::read_shared_program(), None),
+    };
+    let shared_state = match &shared_text {
+        Some(Ok(text)) => SharedState(Some((text.clone(), repro_params.clone()))),
+        _ => SharedState(None),
+    };
+    provide_context(shared_state);
+    let settings = load_settings();
+    provide_context(settings);
+    let saved_programs = SavedPrograms::load_from_storage().unwrap_or_default();
+    let embedded = url_sharing::is_embedded();
+    let stored_program = Program::load_from_storage();
+    let stored_text = stored_program
+        .as_ref()
+        .map(|stored| stored.text.get_untracked());
+    // Links that cannot be decoded open the IDE as if there was no link
+    let (program, opens_link) = match shared_text {
+        Some(Ok(shared_text)) => (Program::new(shared_text), true),
+        Some(Err(())) | None => (stored_program.unwrap_or_default(), false),
     };
+    let program = program.with_settings(settings);
     provide_context(program);
-    let tx_params = TxParams::load_from_storage().unwrap_or_default();
+    let program_tabs = ProgramTabs::load_from_storage().unwrap_or_default();
+    match opens_link {
+        // The shared program gets a tab, so it replaces none of the programs of the user
+        true => program_tabs.open_shared(program, stored_text),
+        // The program in storage is the latest text of the active tab
+        false => program_tabs.sync(program),
+    }
+    provide_context(program_tabs);
+    autosave(program, program_tabs, settings, toasts);
+    follow_edits_in_share_link(program);
+    compile_after_edits(program);
+    let tx_params = repro_params
+        .or_else(TxParams::load_from_storage)
+        .unwrap_or_default();
     let tx_env = TxEnv::new(program, tx_params);
     provide_context(tx_env);
+    provide_context(SavedTxEnvs::load_from_storage().unwrap_or_default());
+    provide_context(saved_programs);
+    provide_context(WitnessSets::load_from_storage().unwrap_or_default());
+    provide_context(WitnessSeed::load_from_storage().unwrap_or_default());
     provide_context(SigningKeys::load_from_storage().unwrap_or_default());
     provide_context(SignedData::new(tx_env.lazy_env));
     provide_context(HashedData::load_from_storage().unwrap_or_default());
     provide_context(KeyCount::load_from_storage().unwrap_or_default());
     provide_context(HashCount::load_from_storage().unwrap_or_default());
-    provide_context(Runtime::new(program, tx_env.lazy_env));
-    provide_context(ActiveRunTab::default());
-    provide_context(ActiveProgramView::default());
+    let runtime = Runtime::new(program, tx_env.lazy_env);
+    provide_context(runtime);
+    let active_run_tab = ActiveRunTab::default();
+    let active_view = ActiveProgramView::default();
+    match url_sharing::read_shared_tab() {
+        Some("Analyze") => active_view.0.set("Analyze"),
+        Some(tab) => active_run_tab.0.set(tab),
+        None => {}
+    }
+    provide_context(active_run_tab);
+    provide_context(active_view);
+    provide_context(ShowDesugared::default());
+    provide_context(ReadOnly::new(url_sharing::is_read_only()));
+    provide_context(EmbedMode(embedded));
+    provide_context(HelpAllowed(!url_sharing::is_help_hidden()));
+
+    // Rerun with Ctrl+R (Cmd+R) instead of reloading the page.
+    // Ctrl+Shift+R still reloads the page.
+    let _listener = window_event_listener(ev::keydown, move |event| {
+        let ctrl_or_cmd = event.ctrl_key() || event.meta_key();
+        if ctrl_or_cmd && !event.shift_key() && !event.alt_key() && event.key_code() == R_KEY {
+            event.prevent_default();
+            runtime.run();
+        } else if event.alt_key() && !ctrl_or_cmd && event.key_code() == W_KEY {
+            event.prevent_default();
+            settings.toggle_module_tabs();
+        } else if event.alt_key()
+            && !ctrl_or_cmd
+            && event.key_code() == A_KEY
+            && !alt_types_character(&event)
+        {
+            // Ctrl+Shift+A is taken by the browsers (add-ons, tab search)
+            event.prevent_default();
+            settings.toggle_dock_analysis();
+        }
+    });
 
     if program.is_empty() {
-        select_example(examples::get("✍️️ P2PK").expect("P2PK example should exist"));
+        select_example(examples::get(examples::STARTER).expect("starter example should exist"));
+    }
+
+    let jets_broken = !jet::c_jets_work();
+    if jets_broken {
+        leptos::logging::error!("Sanity checks of the C jets failed");
+    }
+    let jet_banner = move || {
+        jets_broken.then(|| {
+            view! {
+                <div class="jet-banner">
+                    <i class="fas fa-exclamation-triangle"></i>
+                    " Jets are broken in this browser: the sanity checks of the C jets failed. \
+                    Programs that call jets will fail to run. Try a different browser."
+                </div>
+            }
+        })
+    };
+
+    if embedded {
+        // Let documentation pages push programs into the embedded IDE
+        let _listener = window_event_listener(ev::message, move |event| {
+            let Some(message) = url_sharing::EmbedMessage::from_event(&event) else {
+                return;
+            };
+            let params = tx_env
+                .params
+                .with_untracked(|params| message.tx_params(params));
+            match params {
+                Ok(params) => tx_env.params.set(params),
+                Err(error) => {
+                    runtime
+                        .error_output
+                        .set(Some(ProgramError::Environment(format!(
+                            "The embedding page sent an invalid transaction environment: {error}"
+                        ))));
+                    return;
+                }
+            }
+            program.text.set(message.program_text());
+            runtime.error_output.set(None);
+        });
+        return view! {
+            {jet_banner}
+            <section class="main-content embedded">
+                <ProgramWindow />
+                <RunWindow />
+            </section>
+            <ManualCopyBox />
+            <ToastList />
+        }
+        .into_view();
     }
 
     view! {
         <Navigation />
+        {jet_banner}
         <section class="main-content">
             <ProgramWindow />
             <RunWindow />
         </section>
         <Footer />
+        <Tour />
+        <ShortcutsOverlay />
+        <WhatsNew />
+        <ManualCopyBox />
+        <ToastList />
     }
+    .into_view()
 }
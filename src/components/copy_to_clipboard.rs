@@ -1,37 +1,158 @@
-use leptos::wasm_bindgen::JsValue;
-use leptos::{component, create_rw_signal, ev, view, with, Children, IntoView, Signal, SignalSet};
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use leptos::{
+    component, create_node_ref, create_rw_signal, html, spawn_local, store_value, use_context,
+    view, with, Children, IntoView, RwSignal, Show, Signal, SignalGet, SignalGetUntracked,
+    SignalSet,
+};
 
-fn try_write_clipboard(text: &str) {
+use crate::components::modal::Modal;
+use crate::components::toast::{ToastKind, Toasts};
+
+/// Text that the browser refused to copy, which is shown for the user to copy by hand.
+#[derive(Copy, Clone, Debug)]
+pub struct ManualCopy(pub RwSignal<Option<String>>);
+
+impl ManualCopy {
+    pub fn new() -> Self {
+        Self(create_rw_signal(None))
+    }
+
+    /// Copy `text` to the clipboard and confirm it with a toast that says `message`.
+    ///
+    /// If the browser refuses to copy, then the text is shown for the user to copy by hand.
+    pub fn copy(self, text: String, toasts: Toasts, message: &'static str) {
+        spawn_local(async move {
+            match try_write_clipboard(&text).await {
+                true => toasts.push(ToastKind::Success, message),
+                false => self.0.set(Some(text)),
+            }
+        });
+    }
+}
+
+impl Default for ManualCopy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write `text` to the clipboard and return whether the browser accepted it.
+///
+/// The Clipboard API is unavailable in insecure contexts and in older browsers,
+/// so the text is then copied from a hidden text area instead.
+pub async fn try_write_clipboard(text: &str) -> bool {
     let Some(window) = web_sys::window() else {
-        return;
+        return false;
     };
     let clipboard = window.navigator().clipboard();
     let js: &JsValue = clipboard.as_ref();
-    if js.is_undefined() || js.is_null() {
-        return;
+    if window.is_secure_context() && !js.is_undefined() && !js.is_null() {
+        let written = wasm_bindgen_futures::JsFuture::from(clipboard.write_text(text)).await;
+        if written.is_ok() {
+            return true;
+        }
+    }
+    copy_with_command(text)
+}
+
+/// Copy `text` by selecting it in a hidden text area and running the `copy` command.
+///
+/// The command is deprecated, but it is the only way to copy where the Clipboard API is missing.
+fn copy_with_command(text: &str) -> bool {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return false;
+    };
+    let Some(body) = document.body() else {
+        return false;
+    };
+    let Some(area) = document
+        .create_element("textarea")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+    else {
+        return false;
+    };
+    let focused = document
+        .active_element()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    area.set_value(text);
+    let _ = area.set_attribute("readonly", "");
+    let _ = area.set_attribute("style", "position: fixed; top: 0; left: 0; opacity: 0;");
+    if body.append_child(&area).is_err() {
+        return false;
     }
-    let _ = clipboard.write_text(text);
+    area.select();
+    let copied = document
+        .dyn_ref::<web_sys::HtmlDocument>()
+        .and_then(|document| document.exec_command("copy").ok())
+        .unwrap_or(false);
+    area.remove();
+    // Selecting the text area took the focus, for example from the program editor
+    if let Some(focused) = focused {
+        let _ = focused.focus();
+    }
+    copied
 }
 
+/// Check of the content before it is copied, which returns `false` to cancel copying.
+pub type BeforeCopy = Box<dyn Fn(&str) -> bool>;
+
+/// Button that copies its content to the clipboard.
+///
+/// Content that is expensive to compute, such as an encoded share link, can be `deferred`:
+/// the button then shows a spinner and computes the content after the spinner was rendered,
+/// so the page does not appear frozen.
+///
+/// `before_copy` receives the content and may cancel copying by returning `false`,
+/// for example to offer an alternative for content that is unsuitable.
 #[component]
 pub fn CopyToClipboard(
     #[prop(into)] content: Signal<String>,
     #[prop(into)] class: String,
     #[prop(default = false)] tooltip_below: bool,
+    #[prop(default = false)] deferred: bool,
     #[prop(optional)] on_copy: Option<Box<dyn Fn()>>,
+    #[prop(optional)] before_copy: Option<BeforeCopy>,
     children: Children,
 ) -> impl IntoView {
-    let tooltip_text = create_rw_signal("Copy");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let manual_copy = use_context::<ManualCopy>().expect("manual copy should exist in context");
+    let busy = create_rw_signal(false);
+    let on_copy = store_value(on_copy);
+    let before_copy = store_value(before_copy);
 
-    let button_click = move |_event: ev::MouseEvent| {
-        with!(|content| try_write_clipboard(content));
-        if let Some(cb) = &on_copy {
-            cb();
+    let copy = move || {
+        let allowed = with!(|content| {
+            before_copy.with_value(|before_copy| {
+                before_copy
+                    .as_ref()
+                    .map_or(true, |before_copy| before_copy(content))
+            })
+        });
+        if !allowed {
+            return;
         }
-        tooltip_text.set("Copied!");
+        manual_copy.copy(content.get_untracked(), toasts, "Copied to clipboard");
+        on_copy.with_value(|on_copy| {
+            if let Some(cb) = on_copy {
+                cb();
+            }
+        });
     };
-    let button_mouseout = move |_event: ev::MouseEvent| {
-        tooltip_text.set("Copy");
+    let button_click = move |_| {
+        if !deferred {
+            return copy();
+        }
+        if busy.get_untracked() {
+            return;
+        }
+        busy.set(true);
+        spawn_local(async move {
+            // Yield to the browser so that it renders the spinner first
+            gloo_timers::future::TimeoutFuture::new(0).await;
+            copy();
+            busy.set(false);
+        });
     };
     let tooltip_class = match tooltip_below {
         false => "tooltip-above",
@@ -43,11 +164,52 @@ pub fn CopyToClipboard(
             <button
                 class=class
                 on:click=button_click
-                on:mouseout=button_mouseout
             >
-                <span class="tooltip-text">{tooltip_text}</span>
-                {children()}
+                <span class="tooltip-text">"Copy"</span>
+                <span class:hidden=busy>{children()}</span>
+                {move || busy.get().then(|| view! { <i class="fas fa-spinner fa-spin"></i> })}
             </button>
         </div>
     }
 }
+
+/// Dialog with text that the browser refused to copy, selected for the user to copy by hand.
+#[component]
+pub fn ManualCopyBox() -> impl IntoView {
+    let manual_copy = use_context::<ManualCopy>().expect("manual copy should exist in context");
+
+    view! {
+        <Show when=move || manual_copy.0.get().is_some()>
+            <ManualCopyDialog text=manual_copy.0.get_untracked().unwrap_or_default() />
+        </Show>
+    }
+}
+
+#[component]
+fn ManualCopyDialog(text: String) -> impl IntoView {
+    let manual_copy = use_context::<ManualCopy>().expect("manual copy should exist in context");
+    let close = move || manual_copy.0.set(None);
+    let area_ref = create_node_ref::<html::Textarea>();
+    area_ref.on_load(|area| {
+        let _ = area.focus();
+        area.select();
+    });
+
+    view! {
+        <Modal title="Copy" class="manual-copy" close=close>
+            <p>"The browser does not allow copying from this page. Press Ctrl+C (or ⌘+C) to copy the selected text."</p>
+            <textarea
+                class="manual-copy-text"
+                readonly
+                node_ref=area_ref
+                on:focus=move |_| {
+                    if let Some(area) = area_ref.get_untracked() {
+                        area.select();
+                    }
+                }
+            >
+                {text}
+            </textarea>
+        </Modal>
+    }
+}
@@ -1,27 +1,134 @@
 use std::rc::Rc;
 
-use leptos::{component, ev, view, CollectView, IntoView, View};
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_node_ref, create_rw_signal, ev, html, view, CollectView, IntoView, RwSignal,
+    SignalGet, SignalGetUntracked, SignalSet, View,
+};
 
+const ENTER_KEY: u32 = 13;
+const ESCAPE_KEY: u32 = 27;
+const ARROW_UP_KEY: u32 = 38;
+const ARROW_DOWN_KEY: u32 = 40;
+
+/// Button with a list of options that opens on hover or on click.
+///
+/// The list can be used with the keyboard:
+/// the arrow keys move between the options, Enter selects one and Escape closes the list.
+/// The names of dropdowns are unique, so they make up the ids of their elements.
 #[component]
 pub fn Dropdown(
     name: &'static str,
     #[prop(into)] options: Rc<[&'static str]>,
     select_option: impl Fn(&'static str) + Copy + 'static,
 ) -> impl IntoView {
-    let options_view = move || -> View {
-        options
-            .iter()
-            .map(|name| {
-                view! {
-                    <Option name=name select_option=select_option />
+    let open = create_rw_signal(false);
+    let active = create_rw_signal(Option::<usize>::None);
+    let container_ref = create_node_ref::<html::Div>();
+    let list_id = format!("dropdown-{}", name.to_lowercase().replace(' ', "-"));
+    let option_id = {
+        let list_id = list_id.clone();
+        move |index: usize| format!("{list_id}-{index}")
+    };
+
+    let close = move || {
+        open.set(false);
+        active.set(None);
+    };
+    let choose = move |option: &'static str| {
+        close();
+        select_option(option);
+    };
+    let option_count = options.len();
+    let handle_keydown = {
+        let options = options.clone();
+        move |event: ev::KeyboardEvent| {
+            let last = option_count.checked_sub(1);
+            match event.key_code() {
+                ARROW_DOWN_KEY => {
+                    event.prevent_default();
+                    open.set(true);
+                    active.set(match active.get_untracked() {
+                        Some(index) if Some(index) != last => Some(index + 1),
+                        Some(index) => Some(index),
+                        None => last.map(|_| 0),
+                    });
+                }
+                ARROW_UP_KEY => {
+                    event.prevent_default();
+                    open.set(true);
+                    active.set(match active.get_untracked() {
+                        Some(index) => Some(index.saturating_sub(1)),
+                        None => last,
+                    });
+                }
+                ENTER_KEY if open.get_untracked() => {
+                    event.prevent_default();
+                    if let Some(option) = active.get_untracked().and_then(|i| options.get(i)) {
+                        choose(option);
+                    }
+                }
+                ESCAPE_KEY if open.get_untracked() => {
+                    event.prevent_default();
+                    close();
                 }
-            })
-            .collect_view()
+                _ => {}
+            }
+        }
+    };
+    // Close the list when the focus leaves the dropdown
+    let handle_focusout = move |event: ev::FocusEvent| {
+        let focus_inside = event
+            .related_target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+            .zip(container_ref.get_untracked())
+            .is_some_and(|(target, container)| container.contains(Some(&target)));
+        if !focus_inside {
+            close();
+        }
+    };
+
+    let options_view = {
+        let option_id = option_id.clone();
+        move || -> View {
+            options
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    view! {
+                        <Option
+                            name=name
+                            id=option_id(index)
+                            index=index
+                            active=active
+                            select_option=choose
+                        />
+                    }
+                })
+                .collect_view()
+        }
     };
 
     view! {
-        <div class="dropdown">
-            <button class="button dropdown-button">
+        <div
+            class="dropdown"
+            class:open=open
+            node_ref=container_ref
+            on:keydown=handle_keydown
+            on:focusout=handle_focusout
+        >
+            <button
+                class="button dropdown-button"
+                role="combobox"
+                aria-haspopup="listbox"
+                aria-controls=list_id.clone()
+                aria-expanded=move || open.get().to_string()
+                aria-activedescendant=move || active.get().map(option_id.clone())
+                on:click=move |_| match open.get_untracked() {
+                    true => close(),
+                    false => open.set(true),
+                }
+            >
                 {name}" "
                 <i class="dropdown-chevron">
                     <svg xmlns="http://www.w3.org/2000/svg" width="13" height="9" viewBox="0 0 13 9" fill="none">
@@ -29,7 +136,7 @@ pub fn Dropdown(
                     </svg>
                 </i>
             </button>
-            <div class="dropdown-content">
+            <div class="dropdown-content" id=list_id role="listbox" aria-label=name>
                 {options_view}
             </div>
         </div>
@@ -37,11 +144,23 @@ pub fn Dropdown(
 }
 
 #[component]
-fn Option(name: &'static str, select_option: impl Fn(&'static str) + 'static) -> impl IntoView {
+fn Option(
+    name: &'static str,
+    id: String,
+    index: usize,
+    active: RwSignal<Option<usize>>,
+    select_option: impl Fn(&'static str) + 'static,
+) -> impl IntoView {
     let button_click = move |_event: ev::MouseEvent| select_option(name);
+    let is_active = move || active.get() == Some(index);
     view! {
         <button
             class="action-button"
+            class:active=is_active
+            id=id
+            role="option"
+            tabindex="-1"
+            aria-selected=move || is_active().to_string()
             on:click=button_click
         >
             {name}
@@ -5,12 +5,20 @@ mod app;
 mod copy_to_clipboard;
 mod dropdown;
 mod footer;
+mod modal;
 mod navbar;
 mod navigation;
 mod program_window;
 mod run_window;
+mod session;
+mod settings;
+mod shortcuts;
 mod state;
 mod string_box;
+mod toast;
 mod toolbar;
+mod tour;
+mod whats_new;
 
 pub use app::App;
+pub use state::LocalStorage;
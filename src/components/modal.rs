@@ -0,0 +1,35 @@
+use leptos::{component, view, Children, IntoView};
+
+/// Dialog over the dimmed page, with a title and a close button.
+///
+/// Clicks on the dimmed page or on the close button call `close`.
+/// The `class` of the dialog sets its size.
+#[component]
+pub fn Modal<F>(
+    title: &'static str,
+    #[prop(optional)] class: &'static str,
+    close: F,
+    children: Children,
+) -> impl IntoView
+where
+    F: Fn() + Copy + 'static,
+{
+    view! {
+        <div class="analyze-modal-overlay" on:click=move |_| close()>
+            <div
+                class=format!("analyze-modal {class}")
+                role="dialog"
+                aria-label=title
+                on:click=|event| event.stop_propagation()
+            >
+                <div class="analyze-modal-header">
+                    <h2>{title}</h2>
+                    <button class="analyze-modal-close" title="Close" on:click=move |_| close()>
+                        <i class="fas fa-times"></i>
+                    </button>
+                </div>
+                <div class="analyze-modal-content">{children()}</div>
+            </div>
+        </div>
+    }
+}
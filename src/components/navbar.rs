@@ -1,6 +1,7 @@
 use leptos::leptos_dom::Transparent;
 use leptos::{
-    component, ev, view, Children, ChildrenFn, IntoView, RwSignal, SignalGet, SignalSet, View,
+    component, create_effect, ev, view, Children, ChildrenFn, IntoView, RwSignal, Signal,
+    SignalGet, SignalGetUntracked, SignalSet, View,
 };
 
 #[component]
@@ -9,9 +10,13 @@ pub fn Navbar(
     children: Children,
     active_tab: RwSignal<&'static str>,
 ) -> impl IntoView {
-    active_tab.set(default_tab);
+    // Keep a tab that was chosen beforehand, like the tab of a share link
+    if active_tab.get_untracked().is_empty() {
+        active_tab.set(default_tab);
+    }
     let mut tabs_content = Vec::new();
     let mut button_bar = Vec::new();
+    let mut hidden_tabs = Vec::new();
 
     for child in children()
         .as_children()
@@ -21,13 +26,33 @@ pub fn Navbar(
         .cloned()
     {
         match child {
-            TabView::Tab { name, children } => {
+            TabView::Tab {
+                name,
+                error,
+                hidden,
+                children,
+            } => {
                 tabs_content.push((name, children));
-                button_bar.push(view! {<TabButton tab_name=name active_tab=active_tab />});
+                if let Some(hidden) = hidden {
+                    hidden_tabs.push((name, hidden));
+                }
+                button_bar.push(view! {
+                    <TabButton tab_name=name active_tab=active_tab error=error hidden=hidden />
+                });
             }
             TabView::Button { children } => button_bar.push(children().into_view()),
         }
     }
+    // Leave tabs that become hidden
+    create_effect(move |_| {
+        let active = active_tab.get();
+        if hidden_tabs
+            .iter()
+            .any(|(name, hidden)| *name == active && hidden.get())
+        {
+            active_tab.set(default_tab);
+        }
+    });
     let active_tab_content = move || -> ChildrenFn {
         tabs_content
             .iter()
@@ -45,9 +70,25 @@ pub fn Navbar(
     }
 }
 
+/// Tab of a `Navbar`.
+///
+/// If the content of the tab has an error, then the button of the tab shows a badge,
+/// whose tooltip is the error message.
+///
+/// Hidden tabs have no button. The default tab becomes active when the active tab is hidden.
 #[component(transparent)]
-pub fn Tab(name: &'static str, children: ChildrenFn) -> impl IntoView {
-    TabView::Tab { name, children }
+pub fn Tab(
+    name: &'static str,
+    #[prop(optional, into)] error: Option<Signal<Option<String>>>,
+    #[prop(optional, into)] hidden: Option<Signal<bool>>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    TabView::Tab {
+        name,
+        error,
+        hidden,
+        children,
+    }
 }
 
 #[component(transparent)]
@@ -59,6 +100,8 @@ pub fn Button(children: ChildrenFn) -> impl IntoView {
 enum TabView {
     Tab {
         name: &'static str,
+        error: Option<Signal<Option<String>>>,
+        hidden: Option<Signal<bool>>,
         children: ChildrenFn,
     },
     Button {
@@ -73,7 +116,12 @@ impl IntoView for TabView {
 }
 
 #[component]
-fn TabButton(tab_name: &'static str, active_tab: RwSignal<&'static str>) -> impl IntoView {
+fn TabButton(
+    tab_name: &'static str,
+    active_tab: RwSignal<&'static str>,
+    error: Option<Signal<Option<String>>>,
+    hidden: Option<Signal<bool>>,
+) -> impl IntoView {
     let button_click = move |_event: ev::MouseEvent| active_tab.set(tab_name);
     let button_class = move || match active_tab.get() == tab_name {
         true => "tab active",
@@ -82,9 +130,16 @@ fn TabButton(tab_name: &'static str, active_tab: RwSignal<&'static str>) -> impl
     view! {
         <button
             class=button_class
+            class:hidden=move || hidden.is_some_and(|hidden| hidden.get())
             on:click=button_click
+            data-tab=tab_name
         >
             {tab_name}
+            {move || {
+                error.and_then(|error| error.get()).map(|message| {
+                    view! { <span class="tab-error-badge" title=message>"!"</span> }
+                })
+            }}
         </button>
     }
 }
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use leptos::{component, create_memo, use_context, view, IntoView, Signal, SignalWith};
+
+use crate::components::analysis::{Analysis, ProgramMetrics};
+use crate::components::program_window::Program;
+use crate::components::settings::Settings;
+use crate::util::Expression;
+
+/// Button that docks or undocks the analysis panel.
+#[component]
+pub fn DockButton() -> impl IntoView {
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let label = move || match settings.dock_analysis() {
+        true => "Undock analysis",
+        false => "Dock analysis beside editor",
+    };
+
+    view! {
+        <button
+            class="flat-button bordered"
+            type="button"
+            title="Dock or undock the analysis (Alt+A)"
+            on:click=move |_| settings.toggle_dock_analysis()
+        >
+            <i class="fas fa-columns"></i>
+            " "
+            {label}
+        </button>
+    }
+}
+
+/// Analysis of the current program that stays visible while editing.
+///
+/// The analysis is updated whenever the program is compiled.
+#[component]
+pub fn AnalysisPanel() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let expression = create_memo(move |_| {
+        program.text.with(|_| ());
+        program.lazy_compiled.with(|_| ());
+        program
+            .satisfied()
+            .ok()
+            .map(|satisfied| Arc::clone(satisfied.redeem()))
+    });
+    let expression: Signal<Option<Arc<Expression>>> = expression.into();
+    let cmr = move || match program.lazy_compiled.with(|compiled| compiled.cmr.clone()) {
+        Ok(cmr) => cmr.to_string(),
+        Err(..) => "The program does not compile".to_string(),
+    };
+    let no_expression = move || expression.with(Option::is_none);
+
+    view! {
+        <aside class="analysis-panel">
+            <div class="analysis-panel-header">
+                <h3 class="tab-title">"Analysis"</h3>
+                <DockButton />
+            </div>
+            <div class="analysis-panel-cmr">
+                <div class="analysis-item-label">"CMR:"</div>
+                <code>{cmr}</code>
+            </div>
+            <ProgramMetrics />
+            {move || no_expression().then(|| view! {
+                <div class="neutral-box">"Analysis needs a program that compiles and is satisfied."</div>
+            })}
+            <Analysis program=expression />
+        </aside>
+    }
+}
@@ -22,6 +22,7 @@ pub fn AnalyzeButton() -> impl IntoView {
             class="button"
             class:button-active=is_active
             on:click=toggle_analyze
+            data-tour="analyze"
         >
             " Analyze"
         </button>
@@ -7,7 +7,8 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 
 use crate::components::analysis::Analysis;
-use crate::components::program_window::Runtime;
+use crate::components::program_window::node_tree::NodeTree;
+use crate::components::program_window::{DockButton, Runtime};
 use crate::util::dag::{build_dag_export, NodeMeta};
 use crate::util::Expression;
 
@@ -44,6 +45,9 @@ pub fn AnalyzeView() -> impl IntoView {
 
     view! {
         <div class="tab-content analyze-view">
+            <div class="button-row is-small">
+                <DockButton />
+            </div>
             // Existing Analysis metrics
             <Analysis program=program_expr />
 
@@ -57,6 +61,8 @@ pub fn AnalyzeView() -> impl IntoView {
                             <DagCanvas dag_json=serde_json::to_string(&dag).unwrap_or_default() selected=selected />
                             <DagDetails node=selected_node() />
                         </div>
+                        <h3 class="dag-section-title">"Node tree"</h3>
+                        <NodeTree dag=dag />
                     }.into_view(),
                     None => view! {
                         <div class="dag-empty-state">
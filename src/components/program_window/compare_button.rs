@@ -0,0 +1,20 @@
+use leptos::{component, use_context, view, IntoView, SignalGet, SignalSet};
+
+use crate::components::app::ActiveProgramView;
+
+#[component]
+pub fn CompareButton() -> impl IntoView {
+    let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+
+    let toggle_compare = move |_| match active_view.0.get() {
+        "Compare" => active_view.0.set("Run"),
+        _ => active_view.0.set("Compare"),
+    };
+    let is_active = move || active_view.0.get() == "Compare";
+
+    view! {
+        <button class="button" class:button-active=is_active on:click=toggle_compare>
+            " Compare"
+        </button>
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use leptos::{
+    component, create_memo, create_rw_signal, event_target_value, use_context, view, CollectView,
+    IntoView, Memo, RwSignal, SignalGet, SignalGetUntracked, SignalSet, SignalWith,
+};
+use simplicity::jet::Elements;
+use simplicity::CommitNode;
+use simplicityhl::simplicity;
+
+use crate::components::program_window::Program;
+use crate::pipeline::compile_text;
+use crate::util::compare::{self, MAX_DIFFERENCES};
+
+/// Compile the program `text` without debug symbols, which would change its CMR.
+fn commit_text(text: &str) -> Option<Result<Arc<CommitNode<Elements>>, String>> {
+    match text.trim().is_empty() {
+        true => None,
+        false => Some(
+            compile_text(text, false)
+                .map(|compiled| compiled.commit())
+                .map_err(|error| error.to_string()),
+        ),
+    }
+}
+
+/// Two programs side by side, with their CMRs and the differences between their compiled trees.
+#[component]
+pub fn CompareView() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let left_text = create_rw_signal(program.text.get_untracked());
+    let right_text = create_rw_signal(String::new());
+    let left = create_memo(move |_| left_text.with(|text| commit_text(text)));
+    let right = create_memo(move |_| right_text.with(|text| commit_text(text)));
+
+    let comparison = move || match (left.get(), right.get()) {
+        (Some(Ok(left)), Some(Ok(right))) => Some(compare::differences(&left, &right)),
+        _ => None,
+    };
+
+    view! {
+        <div class="tab-content compare-view">
+            <div class="compare-columns">
+                <CompareSide label="Left program" text=left_text />
+                <CompareSide label="Right program" text=right_text />
+            </div>
+            <div class="compare-cmrs">
+                <CompareCmr label="Left CMR:" compiled=left />
+                <CompareCmr label="Right CMR:" compiled=right />
+            </div>
+            {move || comparison().map(|differences| match differences.is_empty() {
+                true => view! {
+                    <div class="compare-result cmr-match">
+                        <i class="fas fa-check"></i>" The programs have the same CMR"
+                    </div>
+                }
+                .into_view(),
+                false => view! {
+                    <div class="compare-result cmr-mismatch">
+                        <i class="fas fa-times"></i>" The programs have different CMRs"
+                    </div>
+                    {(MAX_DIFFERENCES <= differences.len()).then(|| view! {
+                        <p class="tab-description">
+                            {format!("Only the first {MAX_DIFFERENCES} differences are shown.")}
+                        </p>
+                    })}
+                    <div class="compare-differences">
+                        {differences
+                            .into_iter()
+                            .map(|difference| view! {
+                                <div class="compare-difference">
+                                    <div class="compare-difference-path">{difference.path}</div>
+                                    <code>{difference.left}</code>
+                                    <code>{difference.right}</code>
+                                </div>
+                            })
+                            .collect_view()}
+                    </div>
+                }
+                .into_view(),
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn CompareSide(label: &'static str, text: RwSignal<String>) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let load_current = move |_| {
+        program.flush_text();
+        text.set(program.text.get_untracked());
+    };
+
+    view! {
+        <div class="compare-side">
+            <div class="compare-side-header">
+                <span>{label}</span>
+                <button class="button is-small" on:click=load_current>"Load current program"</button>
+            </div>
+            <textarea
+                class="program-input-field"
+                rows="20"
+                spellcheck="false"
+                placeholder="Paste a program here"
+                prop:value=text
+                on:input=move |event| text.set(event_target_value(&event))
+            ></textarea>
+        </div>
+    }
+}
+
+#[component]
+fn CompareCmr(
+    label: &'static str,
+    compiled: Memo<Option<Result<Arc<CommitNode<Elements>>, String>>>,
+) -> impl IntoView {
+    view! {
+        <div class="analysis-item">
+            <div class="analysis-item-label">{label}</div>
+            {move || match compiled.get() {
+                None => view! { <span class="analysis-item-value">"No program"</span> }.into_view(),
+                Some(Ok(commit)) => view! {
+                    <code class="analysis-item-value">{commit.cmr().to_string()}</code>
+                }
+                .into_view(),
+                Some(Err(error)) => view! {
+                    <pre class="compare-error">{error}</pre>
+                }
+                .into_view(),
+            }}
+        </div>
+    }
+}
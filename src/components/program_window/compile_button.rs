@@ -0,0 +1,42 @@
+use leptos::{component, event_target_checked, use_context, view, IntoView, Show};
+
+use crate::components::program_window::Program;
+use crate::components::settings::Settings;
+
+/// Compile the program on demand, if automatic compilation is disabled.
+#[component]
+pub fn CompileButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let manual_compile = move || !settings.auto_compile();
+
+    view! {
+        <Show when=manual_compile>
+            <button class="button" on:click=move |_| program.compile()>
+                <i class="fas fa-hammer"></i>
+                " Compile"
+            </button>
+        </Show>
+    }
+}
+
+/// Checkbox that compiles the program with debug symbols, so runs show the output of `dbg!`.
+#[component]
+pub fn DebugSymbolsToggle() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let toggle = move |event| {
+        settings.set_debug_symbols(event_target_checked(&event));
+        program.compile();
+    };
+
+    view! {
+        <label
+            class="button debug-symbols-toggle"
+            title="Show the values of dbg! expressions when the program runs. Debug symbols change the CMR."
+        >
+            <input type="checkbox" prop:checked=move || settings.debug_symbols() on:change=toggle />
+            " Debug symbols"
+        </label>
+    }
+}
@@ -0,0 +1,80 @@
+use leptos::{component, use_context, view, IntoView, SignalGet, SignalWith};
+
+use crate::components::program_window::Program;
+use crate::pipeline::ProgramError;
+
+/// Badges that show separately whether the program compiles and whether its witness satisfies it.
+///
+/// A program can compile but fail to be satisfied; for example, if a witness value is missing.
+#[component]
+pub fn CompileStatus() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let compiled = move || {
+        program
+            .lazy_compiled
+            .with(|compiled| compiled.cmr.as_ref().err().cloned())
+    };
+    let satisfied = move || {
+        program
+            .lazy_satisfied
+            .with(|satisfied| satisfied.as_ref().err().cloned())
+    };
+    let badge = |label: &'static str, error: Option<ProgramError>| match error {
+        None => view! { <span class="status-badge ok">{label}" ✓"</span> },
+        Some(error) => view! {
+            <span class="status-badge failed" title=error.to_string()>{label}" ✗"</span>
+        },
+    };
+    // Programs that can lock coins take no input and produce no output
+    let arrow = move || {
+        program
+            .lazy_compiled
+            .with(|compiled| {
+                compiled
+                    .arrow
+                    .clone()
+                    .map(|arrow| (arrow, compiled.predicate))
+            })
+            .map(|(arrow, predicate)| {
+                let title = match predicate {
+                    true => "The program is a valid predicate",
+                    false => "The program is not a predicate: its type should be 1 → 1",
+                };
+                view! {
+                    <span class="status-badge type-arrow" class:failed=!predicate title=title>
+                        {arrow}
+                    </span>
+                }
+            })
+    };
+
+    move || {
+        // Nothing was compiled yet
+        if program.cached_text.with(String::is_empty) {
+            return None;
+        }
+        // Errors of the previous text would be stale
+        if program.compiling.get() {
+            return Some(view! {
+                <div class="compile-status">
+                    <span class="status-badge skipped">
+                        <i class="fas fa-spinner fa-spin"></i>
+                        " compiling…"
+                    </span>
+                </div>
+            });
+        }
+        let compiles = compiled();
+        let satisfies = match compiles {
+            Some(..) => view! { <span class="status-badge skipped">"satisfies –"</span> },
+            None => badge("satisfies", satisfied()),
+        };
+        Some(view! {
+            <div class="compile-status">
+                {badge("compiles", compiles)}
+                {satisfies}
+                {arrow}
+            </div>
+        })
+    }
+}
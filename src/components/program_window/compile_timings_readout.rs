@@ -0,0 +1,24 @@
+use leptos::{component, use_context, view, IntoView, SignalGet};
+
+use crate::components::program_window::Program;
+
+/// Time that parsing, compiling and satisfying the program took, if it was measured.
+#[component]
+pub fn CompileTimingsReadout() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+
+    move || {
+        program.timings.get().map(|timings| {
+            view! {
+                <div class="compile-timings">
+                    {format!(
+                        "parse {:.1} ms · compile {:.1} ms · satisfy {:.1} ms",
+                        timings.parse,
+                        timings.compile,
+                        timings.satisfy,
+                    )}
+                </div>
+            }
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use leptos::{
+    component, create_effect, create_rw_signal, event_target_value, use_context, view, IntoView,
+    Signal, SignalGet, SignalSet, SignalWith,
+};
+
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::Program;
+
+/// Button to copy the program, either as source or as CMR.
+///
+/// The CMR can only be copied if the program compiles.
+#[component]
+pub fn CopyProgram() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let copy_cmr = create_rw_signal(false);
+    let cmr = move || {
+        program
+            .lazy_compiled
+            .with(|compiled| compiled.cmr.as_ref().ok().map(ToString::to_string))
+    };
+    let content = Signal::derive(move || match copy_cmr.get() {
+        true => cmr().unwrap_or_else(|| program.text.get()),
+        false => program.text.get(),
+    });
+    let compiles = move || program.lazy_compiled.with(|compiled| compiled.cmr.is_ok());
+    create_effect(move |_| {
+        if !compiles() {
+            copy_cmr.set(false);
+        }
+    });
+
+    view! {
+        <select
+            class="copy-mode"
+            title="What to copy"
+            prop:value=move || match copy_cmr.get() {
+                true => "cmr",
+                false => "source",
+            }
+            on:change=move |event| copy_cmr.set(event_target_value(&event) == "cmr")
+        >
+            <option value="source">"Source"</option>
+            <option value="cmr" disabled=move || !compiles()>"CMR"</option>
+        </select>
+        <CopyToClipboard content=content class="copy-button" tooltip_below=true>
+            <i class="far fa-copy"></i>
+        </CopyToClipboard>
+    }
+}
@@ -0,0 +1,45 @@
+use leptos::{component, create_memo, use_context, view, IntoView, SignalGet, SignalWith};
+
+use crate::components::program_window::{select_example, Program};
+use crate::examples;
+
+/// Suggestion to load an example that shows how to avoid the current compile error.
+///
+/// The suggestion is hidden for errors without a matching example.
+#[component]
+pub fn ErrorExample() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let example_name = create_memo(move |_| {
+        program.lazy_compiled.with(|compiled| {
+            compiled
+                .cmr
+                .as_ref()
+                .err()
+                .and_then(|error| examples::for_error(&error.to_string()))
+        })
+    });
+    let load_example = move |_| {
+        if let Some(example) = example_name.get().and_then(examples::get) {
+            select_example(example);
+        }
+    };
+
+    move || {
+        example_name.get().map(|name| {
+            view! {
+                <div class="error-example">
+                    <i class="fas fa-lightbulb"></i>
+                    {format!(" The example “{name}” shows how to avoid this error.")}
+                    <button
+                        class="flat-button bordered is-small"
+                        type="button"
+                        title="Replace the current program by the example"
+                        on:click=load_example
+                    >
+                        "Load example"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
@@ -1,10 +1,13 @@
-use leptos::{component, use_context, view, IntoView, SignalGetUntracked, SignalSet, SignalUpdate};
+use leptos::{
+    component, use_context, view, IntoView, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWithUntracked,
+};
 
 use crate::components::app::ActiveRunTab;
 use crate::components::dropdown::Dropdown;
-use crate::components::program_window::Program;
+use crate::components::program_window::{Program, SavedPrograms};
 use crate::components::run_window::{SignedData, TxEnv};
-use crate::examples::Example;
+use crate::examples::{self, Example};
 use crate::util::{HashedData, SigningKeys};
 
 pub fn select_example(example: Example) {
@@ -20,7 +23,7 @@ pub fn select_example(example: Example) {
     let arguments = example.arguments(&signing_keys.public_keys, &hashed_data.hashes);
     let program_text = format!("{arguments}\n\n{}", example.template_text());
     program.text.set(program_text.clone());
-    program.update_on_read();
+    program.compile();
 
     let witness = example.witness(
         &signing_keys.secret_keys,
@@ -32,14 +35,48 @@ pub fn select_example(example: Example) {
     active_run_tab.0.update(|_| {}); // refresh active tab
 }
 
+/// Check whether loading another program would lose the current one,
+/// because it is neither empty, nor saved under a name, nor an unedited example.
+///
+/// Loaded examples start with their argument and witness modules, which differ between users.
+pub fn is_unsaved_work(program: Program, saved_programs: SavedPrograms) -> bool {
+    program.flush_text();
+    program.text.with_untracked(|text| {
+        let text = text.trim();
+        !text.is_empty()
+            && saved_programs
+                .0
+                .with_untracked(|programs| programs.iter().all(|(_, saved)| saved.trim() != text))
+            && examples::keys()
+                .filter_map(examples::get)
+                .all(|example| !text.ends_with(example.template_text().trim()))
+    })
+}
+
+/// Ask the user whether `replacement`, such as "the example", may replace the unsaved program.
+pub fn confirm_replace(replacement: &str) -> bool {
+    let message = format!(
+        "Loading {replacement} replaces the current program, which is not saved. Continue?"
+    );
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(&message).ok())
+        .unwrap_or(false)
+}
+
 #[component]
 pub fn ExamplesDropdown() -> impl IntoView {
-    let examples = crate::examples::keys().collect::<Vec<&'static str>>();
-    let select_example = move |selected| match crate::examples::get(selected) {
-        Some(example) => select_example(example),
-        None => {
-            // do nothing
+    let program = use_context::<Program>().expect("program should exist in context");
+    let saved_programs =
+        use_context::<SavedPrograms>().expect("saved programs should exist in context");
+    let examples = examples::keys().collect::<Vec<&'static str>>();
+    let select_example = move |selected| {
+        let Some(example) = examples::get(selected) else {
+            return;
+        };
+        if is_unsaved_work(program, saved_programs) && !confirm_replace("the example") {
+            return;
         }
+        select_example(example);
     };
 
     view! {
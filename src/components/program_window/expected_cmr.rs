@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+use leptos::{
+    component, create_rw_signal, event_target_value, use_context, view, IntoView, SignalGet,
+    SignalSet,
+};
+use simplicityhl::simplicity;
+
+use crate::components::program_window::Program;
+
+/// Field for an expected CMR that is compared with the CMR of the program.
+#[component]
+pub fn ExpectedCmr() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let expected = create_rw_signal(String::new());
+    let check = move || -> Option<Result<bool, String>> {
+        let expected = expected.get();
+        let expected = expected.trim();
+        if expected.is_empty() {
+            return None;
+        }
+        let expected = match simplicity::Cmr::from_str(expected.trim_start_matches("0x")) {
+            Ok(cmr) => cmr,
+            Err(error) => return Some(Err(format!("Invalid CMR: {error}"))),
+        };
+        Some(match program.cmr() {
+            Ok(cmr) => Ok(cmr == expected),
+            Err(..) => Err("The program does not compile".to_string()),
+        })
+    };
+
+    view! {
+        <div class="expected-cmr">
+            <input
+                class="input"
+                type="text"
+                spellcheck="false"
+                placeholder="Expected CMR (hex)"
+                on:input=move |event| expected.set(event_target_value(&event))
+            />
+            {move || check().map(|result| match result {
+                Ok(true) => view! {
+                    <span class="cmr-match"><i class="fas fa-check"></i>" CMR matches"</span>
+                },
+                Ok(false) => view! {
+                    <span class="cmr-mismatch"><i class="fas fa-times"></i>" CMR does not match"</span>
+                },
+                Err(error) => view! { <span class="cmr-mismatch">{error}</span> },
+            })}
+        </div>
+    }
+}
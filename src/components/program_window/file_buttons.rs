@@ -0,0 +1,113 @@
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, ev, spawn_local, use_context, view, IntoView, SignalSet, SignalWithUntracked,
+};
+
+use super::examples_dropdown::{confirm_replace, is_unsaved_work};
+use crate::components::program_window::{Program, SavedPrograms};
+use crate::components::toast::{ToastKind, Toasts};
+use crate::download;
+use crate::util::source;
+
+/// Check whether the file looks like a text file, judging by its MIME type.
+///
+/// Browsers do not know `.simf` files, so files without a type count as text.
+fn is_text_file(file: &web_sys::File) -> bool {
+    let mime_type = file.type_();
+    mime_type.is_empty() || mime_type.starts_with("text/")
+}
+
+/// Check whether the content of a file is text rather than binary data.
+///
+/// Browsers replace bytes that are not UTF-8 by the replacement character when they read text.
+fn is_text(content: &str) -> bool {
+    !content.contains(['\0', char::REPLACEMENT_CHARACTER])
+}
+
+/// Button that loads a program from a file on disk into the editor.
+#[component]
+pub fn OpenFileButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let saved_programs =
+        use_context::<SavedPrograms>().expect("saved programs should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+
+    let open = move |event: ev::Event| {
+        let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Allow the same file to be chosen again
+        input.set_value("");
+        if !is_text_file(&file) {
+            toasts.push(
+                ToastKind::Error,
+                format!("`{}` is not a text file", file.name()),
+            );
+            return;
+        }
+        if is_unsaved_work(program, saved_programs) && !confirm_replace("the file") {
+            return;
+        }
+        spawn_local(async move {
+            let text = wasm_bindgen_futures::JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|text| text.as_string());
+            match text {
+                Some(text) if is_text(&text) => {
+                    program.flush_text();
+                    program.text.set(text);
+                }
+                Some(..) => toasts.push(
+                    ToastKind::Error,
+                    format!("`{}` is not a text file", file.name()),
+                ),
+                None => toasts.push(
+                    ToastKind::Error,
+                    format!("The file `{}` could not be read", file.name()),
+                ),
+            }
+        });
+    };
+
+    view! {
+        <label class="button" title="Open a program from a file">
+            <i class="fas fa-folder-open"></i>
+            " Open"
+            <input class="hidden" type="file" accept=".simf,text/plain" on:change=open />
+        </label>
+    }
+}
+
+/// Button that saves the program to a file, named after the comment on its first line.
+#[component]
+pub fn DownloadFileButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+
+    let download = move |_| {
+        program.flush_text();
+        let result = program.text.with_untracked(|text| {
+            download::download_file(&source::file_name(text), "text/plain", text.as_bytes())
+        });
+        if result.is_err() {
+            toasts.push(
+                ToastKind::Error,
+                "The browser could not download the program",
+            );
+        }
+    };
+
+    view! {
+        <button class="button" type="button" title="Save the program to a file" on:click=download>
+            <i class="fas fa-download"></i>
+            " Download"
+        </button>
+    }
+}
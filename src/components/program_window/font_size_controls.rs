@@ -0,0 +1,43 @@
+use leptos::{component, use_context, view, IntoView, SignalWithUntracked};
+
+use crate::components::settings::Settings;
+
+/// Buttons to change the font size of the editor.
+#[component]
+pub fn FontSizeControls() -> impl IntoView {
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let font_size = move || {
+        settings
+            .0
+            .with_untracked(|preferences| preferences.font_size)
+    };
+
+    view! {
+        <span class="font-size-controls">
+            <button
+                class="copy-button"
+                type="button"
+                title="Smaller font"
+                on:click=move |_| settings.set_font_size(font_size().saturating_sub(1))
+            >
+                <i class="fas fa-minus"></i>
+            </button>
+            <button
+                class="copy-button"
+                type="button"
+                title="Reset font size"
+                on:click=move |_| settings.set_font_size(Settings::DEFAULT_FONT_SIZE)
+            >
+                {move || format!("{}px", settings.font_size())}
+            </button>
+            <button
+                class="copy-button"
+                type="button"
+                title="Larger font"
+                on:click=move |_| settings.set_font_size(font_size() + 1)
+            >
+                <i class="fas fa-plus"></i>
+            </button>
+        </span>
+    }
+}
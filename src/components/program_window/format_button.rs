@@ -0,0 +1,39 @@
+use leptos::{component, use_context, view, IntoView, SignalSet, SignalWithUntracked};
+
+use crate::components::program_window::Program;
+use crate::components::settings::Settings;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::util::format;
+
+/// Button that formats the program with the tab width of the settings.
+///
+/// Programs that do not parse are left as they are.
+#[component]
+pub fn FormatButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+
+    let format_program = move |_| {
+        program.flush_text();
+        let indent = settings.tab_width();
+        let formatted = program.text.with_untracked(|text| {
+            format::format(text, indent).map(|formatted| (formatted != *text).then_some(formatted))
+        });
+        match formatted {
+            Ok(Some(formatted)) => program.text.set(formatted),
+            Ok(None) => toasts.push(ToastKind::Info, "The program is already formatted"),
+            Err(error) => toasts.push(
+                ToastKind::Error,
+                format!("The program does not parse, so it was not formatted:\n{error}"),
+            ),
+        }
+    };
+
+    view! {
+        <button class="button" type="button" title="Format the program" on:click=format_program>
+            <i class="fas fa-align-left"></i>
+            " Format"
+        </button>
+    }
+}
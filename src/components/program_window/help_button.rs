@@ -1,12 +1,21 @@
-use leptos::{component, view, IntoView};
+use leptos::{component, use_context, view, IntoView};
 
+use crate::components::app::HelpAllowed;
+use crate::components::settings::Settings;
+
+/// Link to the documentation, unless the user or the linking page hid it.
 #[component]
 pub fn HelpButton() -> impl IntoView {
-    view! {
-        <form action="https://github.com/BlockstreamResearch/simplicity-webide/blob/master/doc/README.md" target="_blank">
-            <button class="button" type="submit">
-                " Help"
-            </button>
-        </form>
+    let allowed = use_context::<HelpAllowed>().expect("help setting should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+
+    move || {
+        (allowed.0 && settings.help_button()).then(|| view! {
+            <form action="https://github.com/BlockstreamResearch/simplicity-webide/blob/master/doc/README.md" target="_blank">
+                <button class="button" type="submit">
+                    " Help"
+                </button>
+            </form>
+        })
     }
 }
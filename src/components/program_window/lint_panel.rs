@@ -0,0 +1,62 @@
+use leptos::{
+    component, create_memo, use_context, view, CollectView, IntoView, SignalGet, SignalUpdate,
+    SignalWith,
+};
+
+use crate::components::program_window::Program;
+use crate::util::editor;
+
+/// Warnings of the last compilation, with the lines they refer to.
+///
+/// Warnings do not block running the program, unlike the errors in the execution tab.
+/// The panel is hidden while there is nothing to warn about.
+#[component]
+pub fn LintPanel() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let lints = create_memo(move |_| {
+        program
+            .lazy_compiled
+            .with(|compiled| compiled.warnings.clone())
+    });
+    let has_suspicious_chars = create_memo(move |_| {
+        program
+            .text
+            .with(|text| !editor::suspicious_chars(text).is_empty())
+    });
+    let sanitize = move |_| {
+        program.flush_text();
+        program.text.update(|text| *text = editor::sanitize(text));
+    };
+
+    move || {
+        let lints = lints.get();
+        (!lints.is_empty()).then(|| {
+            view! {
+                <div class="lint-panel">
+                    <div class="lint-panel-title">
+                        {format!("Warnings ({})", lints.len())}
+                        {move || has_suspicious_chars.get().then(|| view! {
+                            <button
+                                class="flat-button bordered lint-sanitize"
+                                type="button"
+                                title="Replace invisible characters, smart quotes and dashes by ASCII"
+                                on:click=sanitize
+                            >
+                                "Sanitize"
+                            </button>
+                        })}
+                    </div>
+                    {lints
+                        .into_iter()
+                        .map(|lint| view! {
+                            <div class="lint-row">
+                                <span class="lint-line">{format!("Line {}", lint.line)}</span>
+                                <span class="lint-message">{lint.message}</span>
+                            </div>
+                        })
+                        .collect_view()}
+                </div>
+            }
+        })
+    }
+}
@@ -1,10 +1,35 @@
 mod address_button;
+mod analysis_panel;
 mod analyze_button;
 mod analyze_view;
+mod compare_button;
+mod compare_view;
+mod compile_button;
+mod compile_status;
+mod compile_timings_readout;
+mod copy_program;
+mod error_example;
 mod examples_dropdown;
+mod expected_cmr;
+mod file_buttons;
+mod font_size_controls;
+mod format_button;
 mod help_button;
+mod lint_panel;
+mod network_select;
+mod node_tree;
+mod outline_panel;
+mod pipeline_button;
+mod pipeline_view;
+mod program_cmr;
 mod program_tab;
+mod program_tabs;
+mod read_only_toggle;
+mod repro_button;
+mod reset_button;
 mod run_button;
+mod runtime;
+mod saved_programs;
 mod share_button;
 mod tools_dropdown;
 mod transaction_button;
@@ -13,35 +38,80 @@ use leptos::create_signal;
 use leptos::{component, use_context, view, IntoView, SignalGet, SignalSet};
 
 use self::address_button::AddressButton;
+use self::analysis_panel::AnalysisPanel;
 use self::analyze_button::AnalyzeButton;
 use self::analyze_view::AnalyzeView;
+use self::compare_button::CompareButton;
+use self::compare_view::CompareView;
+use self::compile_button::{CompileButton, DebugSymbolsToggle};
 use self::examples_dropdown::ExamplesDropdown;
+use self::file_buttons::{DownloadFileButton, OpenFileButton};
+use self::format_button::FormatButton;
 use self::help_button::HelpButton;
+use self::network_select::NetworkSelect;
+use self::pipeline_button::PipelineButton;
+use self::pipeline_view::PipelineView;
 use self::program_tab::ProgramTab;
+use self::program_tabs::ProgramTabStrip;
+use self::repro_button::ReproButton;
+use self::reset_button::ResetButton;
 use self::run_button::RunButton;
+use self::saved_programs::SavedProgramsButton;
 use self::share_button::ShareButton;
+use self::tools_dropdown::ToolsDropdown;
 use self::transaction_button::TransactionButton;
-use crate::components::app::ActiveProgramView;
+use crate::components::app::{ActiveProgramView, EmbedMode};
+use crate::components::settings::{Settings, SettingsButton};
 use crate::components::toolbar::Toolbar;
 
+pub use self::analysis_panel::DockButton;
 pub use self::examples_dropdown::select_example;
-pub use self::program_tab::{Program, Runtime};
+pub use self::program_tab::{Program, ReadOnly, ShowDesugared};
+pub use self::program_tabs::ProgramTabs;
+pub use self::reset_button::SharedState;
+pub use self::runtime::{RunRecord, Runtime};
+pub use self::saved_programs::SavedPrograms;
 
 #[component]
 pub fn ProgramWindow() -> impl IntoView {
     let (mobile_open, set_mobile_open) = create_signal(false);
     let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+    let embedded = use_context::<EmbedMode>().expect("embed mode should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+
+    if embedded.0 {
+        return view! {
+            <Toolbar>
+                <RunButton />
+            </Toolbar>
+            <ProgramTab />
+        }
+        .into_view();
+    }
 
     view! {
         <Toolbar>
             <RunButton />
+            <CompileButton />
+            <DebugSymbolsToggle />
+            <NetworkSelect />
+            <FormatButton />
             <ExamplesDropdown />
+            <SavedProgramsButton />
+            <OpenFileButton />
+            <DownloadFileButton />
+            <ToolsDropdown />
 
             <div class="mobile-hidden"  class:open = move || mobile_open.get() >
                 <AddressButton />
                 <TransactionButton />
                 <AnalyzeButton />
+                <CompareButton />
+                <PipelineButton />
                 <ShareButton />
+                <ReproButton />
+                <ResetButton />
+                <SettingsButton />
                 <HelpButton />
             </div>
 
@@ -59,9 +129,24 @@ pub fn ProgramWindow() -> impl IntoView {
         </Toolbar>
 
         // Toggle between code editor and analyze view
-        {move || match active_view.0.get() {
-            "Analyze" => view! { <AnalyzeView /> }.into_view(),
-            _ => view! { <ProgramTab /> }.into_view(),
+        {move || match (active_view.0.get(), settings.dock_analysis()) {
+            ("Analyze", _) => view! { <AnalyzeView /> }.into_view(),
+            ("Compare", _) => view! { <CompareView /> }.into_view(),
+            ("Pipeline", _) => view! { <PipelineView /> }.into_view(),
+            (_, true) => view! {
+                <ProgramTabStrip />
+                <div class="docked-analysis">
+                    <ProgramTab />
+                    <AnalysisPanel />
+                </div>
+            }
+            .into_view(),
+            _ => view! {
+                <ProgramTabStrip />
+                <ProgramTab />
+            }
+            .into_view(),
         }}
     }
+    .into_view()
 }
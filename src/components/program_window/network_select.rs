@@ -0,0 +1,37 @@
+use leptos::{
+    component, ev, event_target_value, use_context, view, CollectView, IntoView, SignalUpdate,
+    SignalWith, SignalWithUntracked,
+};
+
+use crate::components::run_window::TxEnv;
+use crate::components::state::LocalStorage;
+use crate::network::Network;
+
+/// Select box that chooses the network whose chain parameters runs see.
+///
+/// The transaction environment is rebuilt from the new network,
+/// which changes the genesis block hash and the bitcoin asset of introspection jets.
+#[component]
+pub fn NetworkSelect() -> impl IntoView {
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let select = move |event: ev::Event| {
+        if let Some(network) = Network::from_name(&event_target_value(&event)) {
+            tx_env.params.update(|x| x.network = network);
+            tx_env.params.with_untracked(LocalStorage::store_in_storage);
+        }
+    };
+    let options = Network::ALL
+        .map(|network| view! { <option value=network.name()>{network.name()}</option> })
+        .collect_view();
+
+    view! {
+        <select
+            class="button network-select"
+            title="Network whose genesis block hash and bitcoin asset the program sees"
+            prop:value=move || tx_env.params.with(|x| x.network.name())
+            on:change=select
+        >
+            {options}
+        </select>
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use leptos::{
+    component, create_rw_signal, view, CollectView, IntoView, SignalGet, SignalUpdate, View,
+};
+
+use crate::util::dag::{DagExport, NodeMeta};
+
+/// Depth up to which the tree is expanded initially.
+const INITIAL_DEPTH: usize = 2;
+
+/// Committed program as a tree whose nodes can be expanded and collapsed.
+///
+/// Shared nodes appear once for each of their parents.
+/// Children are only rendered when their parent is expanded,
+/// so large programs stay responsive.
+#[component]
+pub fn NodeTree(dag: DagExport) -> impl IntoView {
+    let nodes: Rc<HashMap<String, NodeMeta>> = Rc::new(
+        dag.nodes
+            .into_iter()
+            .map(|node| (node.id.clone(), node))
+            .collect(),
+    );
+
+    view! {
+        <div class="node-tree">{tree_node(nodes, dag.root_id, 0)}</div>
+    }
+}
+
+fn tree_node(nodes: Rc<HashMap<String, NodeMeta>>, id: String, depth: usize) -> View {
+    let Some(node) = nodes.get(&id).cloned() else {
+        return View::default();
+    };
+    let expanded = create_rw_signal(depth < INITIAL_DEPTH);
+    let toggle = move |_| expanded.update(|expanded| *expanded = !*expanded);
+    let is_leaf = node.children.is_empty();
+    let marker = move || match (is_leaf, expanded.get()) {
+        (true, _) => "•",
+        (false, true) => "▾",
+        (false, false) => "▸",
+    };
+    let NodeMeta {
+        kind,
+        kind_class,
+        type_arrow,
+        cmr,
+        children,
+        ..
+    } = node;
+    let details = move || {
+        expanded.get().then(|| {
+            let children = children
+                .iter()
+                .map(|child| tree_node(Rc::clone(&nodes), child.clone(), depth + 1))
+                .collect_view();
+            view! {
+                <div class="node-tree-details">"CMR " <code>{cmr.clone()}</code></div>
+                <div class="node-tree-children">{children}</div>
+            }
+        })
+    };
+
+    view! {
+        <div class="node-tree-item">
+            <div class="node-tree-row" on:click=toggle>
+                <span class="node-tree-marker">{marker}</span>
+                <span class="dag-detail-value dag-kind" data-kind-class=kind_class>{kind}</span>
+                <span class="node-tree-type">{type_arrow}</span>
+            </div>
+            {details}
+        </div>
+    }
+    .into_view()
+}
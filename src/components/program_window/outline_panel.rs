@@ -0,0 +1,45 @@
+use leptos::{
+    component, create_memo, use_context, view, CollectView, IntoView, SignalGet, SignalWith,
+};
+
+use crate::components::program_window::Program;
+use crate::util::source::{self, SymbolKind};
+
+/// Collapsible list of the definitions of the program.
+///
+/// Clicking a definition moves the cursor of the editor to its line.
+#[component]
+pub fn OutlinePanel(go_to_line: impl Fn(usize) + Copy + 'static) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let symbols = create_memo(move |_| program.text.with(|text| source::outline(text)));
+    // Only the entries are rendered again, so the panel stays open while the user edits
+    let entries = move || {
+        symbols
+            .get()
+            .into_iter()
+            .map(|symbol| {
+                let line = symbol.line;
+                view! {
+                    <button
+                        class="outline-entry"
+                        class:outline-nested=symbol.kind == SymbolKind::Constant
+                        type="button"
+                        title=format!("Go to line {line}")
+                        on:click=move |_| go_to_line(line)
+                    >
+                        <span class="outline-keyword">{symbol.kind.keyword()}</span>
+                        " "
+                        {symbol.name}
+                    </button>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="outline-panel" class:hidden=move || symbols.with(Vec::is_empty)>
+            <summary>"Outline"</summary>
+            {entries}
+        </details>
+    }
+}
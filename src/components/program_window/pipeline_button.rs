@@ -0,0 +1,26 @@
+use leptos::{component, use_context, view, IntoView, SignalGet, SignalSet};
+
+use crate::components::app::ActiveProgramView;
+
+/// Open the view of all compilation artifacts, or return to the editor.
+#[component]
+pub fn PipelineButton() -> impl IntoView {
+    let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+
+    let toggle_pipeline = move |_| match active_view.0.get() {
+        "Pipeline" => active_view.0.set("Run"),
+        _ => active_view.0.set("Pipeline"),
+    };
+    let is_active = move || active_view.0.get() == "Pipeline";
+
+    view! {
+        <button
+            class="button"
+            class:button-active=is_active
+            on:click=toggle_pipeline
+            title="Show all intermediate artifacts of the compilation"
+        >
+            " Pipeline"
+        </button>
+    }
+}
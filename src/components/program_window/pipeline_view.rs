@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use hex_conservative::DisplayHex;
+use leptos::{
+    component, create_memo, create_rw_signal, use_context, view, IntoView, Show, Signal, SignalGet,
+    SignalWith,
+};
+
+use crate::components::analysis::Analysis;
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::navbar::{Navbar, Tab};
+use crate::components::program_window::node_tree::NodeTree;
+use crate::components::program_window::Program;
+use crate::util::dag::build_dag_export;
+use crate::util::{desugar, minify, Expression};
+
+/// All intermediate artifacts of compiling the program, each in its own read-only tab.
+///
+/// The artifacts follow the program as it is edited.
+/// Most of them are computed from the compiled and satisfied program, so they need
+/// a program that compiles and whose witness satisfies it.
+/// The minified program only needs a program that parses.
+#[component]
+pub fn PipelineView() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let active_tab = create_rw_signal("Source");
+    let expression = create_memo(move |_| {
+        program.text.with(|_| ());
+        program.lazy_compiled.with(|_| ());
+        program
+            .satisfied()
+            .map(|satisfied| Arc::clone(satisfied.redeem()))
+    });
+    let satisfied_expression: Signal<Option<Arc<Expression>>> =
+        Signal::derive(move || expression.get().ok());
+    let desugared = create_memo(move |_| program.text.with(|text| desugar::desugar_text(text)));
+
+    let minified = create_memo(move |_| program.text.with(|text| minify::minify(text)));
+    let minified_text = Signal::derive(move || minified.get().unwrap_or_default());
+
+    let source = move || program.text.get();
+    let desugared = move || desugared.get().unwrap_or_else(|error| error);
+    let node_tree = move || match expression.get() {
+        Ok(expression) => view! { <NodeTree dag=build_dag_export(&expression) /> }.into_view(),
+        Err(error) => view! { <pre class="pipeline-error">{error.to_string()}</pre> }.into_view(),
+    };
+    let roots = move || {
+        let cmr = program.lazy_compiled.with(|compiled| compiled.cmr.clone());
+        let redeem_roots = expression
+            .get()
+            .map(|expression| format!("AMR {}\nIHR {}", expression.amr(), expression.ihr()));
+        let cmr = cmr.map_or_else(|error| error.to_string(), |cmr| format!("CMR {cmr}"));
+        match redeem_roots {
+            Ok(roots) => format!("{cmr}\n{roots}"),
+            Err(..) => cmr,
+        }
+    };
+    let bytes = move || match expression.get() {
+        Ok(expression) => {
+            let (program_bytes, witness_bytes) = expression.to_vec_with_witness();
+            format!(
+                "Program ({} bytes)\n{}\n\nWitness ({} bytes)\n{}",
+                program_bytes.len(),
+                program_bytes.as_hex(),
+                witness_bytes.len(),
+                witness_bytes.as_hex()
+            )
+        }
+        Err(error) => error.to_string(),
+    };
+    let no_cost = move || satisfied_expression.with(Option::is_none);
+
+    view! {
+        <div class="tab-content pipeline-view">
+            <Navbar default_tab="Source" active_tab=active_tab>
+                <Tab name="Source">
+                    <pre class="pipeline-artifact">{source}</pre>
+                </Tab>
+                <Tab name="Desugared">
+                    <pre class="pipeline-artifact">{desugared}</pre>
+                </Tab>
+                <Tab name="Minified">
+                    <div class="pipeline-minified">
+                        <Show
+                            when=move || minified.with(Result::is_ok)
+                            fallback=move || view! {
+                                <pre class="pipeline-error">{move || minified.get().err()}</pre>
+                            }
+                        >
+                            <CopyToClipboard content=minified_text class="copy-button">
+                                <i class="far fa-copy"></i>
+                                " Copy minified program"
+                            </CopyToClipboard>
+                            <pre class="pipeline-artifact pipeline-bytes">{minified_text}</pre>
+                        </Show>
+                    </div>
+                </Tab>
+                <Tab name="Node tree">
+                    <div class="pipeline-artifact">{node_tree}</div>
+                </Tab>
+                <Tab name="Roots">
+                    <pre class="pipeline-artifact">{roots}</pre>
+                </Tab>
+                <Tab name="Bytes">
+                    <pre class="pipeline-artifact pipeline-bytes">{bytes}</pre>
+                </Tab>
+                <Tab name="Cost">
+                    {move || no_cost().then(|| view! {
+                        <div class="neutral-box">"The cost needs a program that compiles and is satisfied."</div>
+                    })}
+                    <Analysis program=satisfied_expression />
+                </Tab>
+            </Navbar>
+        </div>
+    }
+}
@@ -0,0 +1,72 @@
+use leptos::{component, use_context, view, IntoView, Show, Signal, SignalGet, SignalWith};
+
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::Program;
+
+/// CMR of the last successful compilation, followed by the AMR and IMR of the satisfied program.
+///
+/// The CMR is labeled as stale if the program changed or failed to compile since then.
+/// The AMR and IMR depend on the witness data, so they require a satisfied program.
+#[component]
+pub fn ProgramCmr() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let is_stale = move || {
+        let edited = program
+            .text
+            .with(|text| program.cached_text.with(|cached| text != cached));
+        match program.lazy_compiled.with(|compiled| compiled.cmr.is_ok()) {
+            true if edited => Some("not compiled since the last edit"),
+            true => None,
+            false if edited => Some("from an earlier version; not compiled since the last edit"),
+            false => Some("from an earlier version; the current program does not compile"),
+        }
+    };
+    let cmr = Signal::derive(move || program.last_cmr.get().map(|cmr| cmr.to_string()));
+    let amr = Signal::derive(move || {
+        program
+            .lazy_amr
+            .with(|amr| amr.as_ref().ok().map(ToString::to_string))
+    });
+    let imr = Signal::derive(move || {
+        program
+            .lazy_imr
+            .with(|imr| imr.as_ref().ok().map(ToString::to_string))
+    });
+
+    view! {
+        <Show when=move || cmr.with(Option::is_some)>
+            <div class="program-cmr">
+                <MerkleRoot name="CMR" root=cmr />
+                {move || is_stale().map(|label| view! { <span class="stale">" (" {label} ")"</span> })}
+            </div>
+            <div class="program-cmr">
+                <MerkleRoot name="AMR" root=amr />
+            </div>
+            <div class="program-cmr">
+                <MerkleRoot name="IMR" root=imr />
+            </div>
+        </Show>
+    }
+}
+
+/// Hex of one Merkle root of the program with a button to copy it.
+///
+/// Roots that are missing show that they require a satisfying witness.
+#[component]
+fn MerkleRoot(name: &'static str, root: Signal<Option<String>>) -> impl IntoView {
+    let content = Signal::derive(move || root.get().unwrap_or_default());
+
+    view! {
+        {name}" "
+        {move || match root.get() {
+            Some(root) => view! {
+                <code>{root}</code>
+                <CopyToClipboard content=content class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            }
+            .into_view(),
+            None => view! { <span class="stale">"requires witness"</span> }.into_view(),
+        }}
+    }
+}
@@ -1,28 +1,170 @@
-use std::sync::Arc;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 
-use itertools::Itertools;
 use leptos::{
-    component, create_node_ref, create_rw_signal, ev, event_target_value, html, spawn_local,
-    use_context, view, IntoView, ReadSignal, RwSignal, Signal, SignalGetUntracked, SignalSet,
-    SignalUpdate, SignalWith, SignalWithUntracked,
+    component, create_effect, create_memo, create_node_ref, create_rw_signal, ev,
+    event_target_value, html, spawn_local, store_value, use_context, view, CollectView, IntoView,
+    RwSignal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked, StoredValue,
 };
 use simplicityhl::parse::ParseFromStr;
-use simplicityhl::simplicity::jet::elements::ElementsEnv;
-use simplicityhl::{elements, simplicity};
-use simplicityhl::{CompiledProgram, SatisfiedProgram, WitnessValues};
+use simplicityhl::simplicity;
+use simplicityhl::SatisfiedProgram;
 
-use crate::components::copy_to_clipboard::CopyToClipboard;
-use crate::function::Runner;
-use crate::util::Expression;
+use crate::components::program_window::compile_status::CompileStatus;
+use crate::components::program_window::compile_timings_readout::CompileTimingsReadout;
+use crate::components::program_window::copy_program::CopyProgram;
+use crate::components::program_window::error_example::ErrorExample;
+use crate::components::program_window::examples_dropdown::{confirm_replace, is_unsaved_work};
+use crate::components::program_window::expected_cmr::ExpectedCmr;
+use crate::components::program_window::font_size_controls::FontSizeControls;
+use crate::components::program_window::lint_panel::LintPanel;
+use crate::components::program_window::outline_panel::OutlinePanel;
+use crate::components::program_window::program_cmr::ProgramCmr;
+use crate::components::program_window::program_tabs::replace_program;
+use crate::components::program_window::read_only_toggle::ReadOnlyToggle;
+use crate::components::program_window::{Runtime, SavedPrograms};
+use crate::components::settings::Settings;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::jet;
+use crate::pipeline::{compile_text, satisfy_text, ProgramError};
+use crate::util::diagnostic::{self, Diagnostic, Severity};
+use crate::util::lint::Lint;
+use crate::util::{self, binding, desugar, editor, format, highlight, lint, source, witness};
+
+/// Whether the program editor is locked against edits.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ReadOnly(pub RwSignal<bool>);
+
+impl ReadOnly {
+    pub fn new(read_only: bool) -> Self {
+        Self(create_rw_signal(read_only))
+    }
+}
+
+/// Whether the desugared intermediate form is shown next to the program.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ShowDesugared(pub RwSignal<bool>);
+
+/// Outcome of compiling a program.
+///
+/// Warnings do not prevent the program from running,
+/// so they are reported even if the program compiles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompileResult {
+    /// CMR of the compiled program, or the compilation error.
+    pub cmr: Result<simplicity::Cmr, ProgramError>,
+    /// Type arrow of the compiled program, such as `1 → 1`.
+    pub arrow: Option<String>,
+    /// Whether the compiled program has the type `1 → 1` of a predicate.
+    pub predicate: bool,
+    /// Number of distinct nodes in the commitment DAG of the compiled program.
+    pub commit_nodes: Option<usize>,
+    /// Warnings about likely mistakes in the program, ordered by line.
+    pub warnings: Vec<Lint>,
+}
+
+impl Default for CompileResult {
+    fn default() -> Self {
+        Self {
+            cmr: Err(ProgramError::NotCompiled),
+            arrow: None,
+            predicate: false,
+            commit_nodes: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Time that each stage of the last compilation took, in milliseconds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CompileTimings {
+    pub parse: f64,
+    /// Compiling parses the program again,
+    /// so this time includes the time of parsing.
+    pub compile: f64,
+    pub satisfy: f64,
+}
+
+/// Current time in milliseconds, with sub-millisecond precision if the browser allows it.
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+/// Results of compiling and satisfying a program text, which the compilation cache keeps.
+#[derive(Clone)]
+struct CachedCompilation {
+    /// Hash of `text` and `debug_symbols`, to find the entry quickly.
+    hash: u64,
+    text: String,
+    /// Whether the program was compiled with debug symbols.
+    debug_symbols: bool,
+    compiled: CompileResult,
+    satisfied: Result<SatisfiedProgram, ProgramError>,
+}
+
+/// Number of program texts whose compilation results are kept.
+const COMPILE_CACHE_SIZE: usize = 16;
+
+/// Return the hash of the program `text`, compiled with or without debug symbols.
+fn text_hash(text: &str, debug_symbols: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    debug_symbols.hash(&mut hasher);
+    hasher.finish()
+}
 
 #[derive(Copy, Clone, Debug)]
 pub struct Program {
     pub text: RwSignal<String>,
-    cached_text: RwSignal<String>,
-    pub lazy_cmr: RwSignal<Result<simplicity::Cmr, String>>,
-    lazy_satisfied: RwSignal<Result<SatisfiedProgram, String>>,
+    /// Text of the last compilation.
+    pub cached_text: RwSignal<String>,
+    /// Whether the last compilation included debug symbols.
+    cached_debug_symbols: StoredValue<bool>,
+    /// Settings for the compilation and for the formatting of stored copies.
+    settings: Settings,
+    pub lazy_compiled: RwSignal<CompileResult>,
+    /// CMR of the last program that compiled successfully.
+    ///
+    /// Unlike the CMR of `lazy_compiled`, this CMR is kept when compilation fails.
+    pub last_cmr: RwSignal<Option<simplicity::Cmr>>,
+    pub lazy_satisfied: RwSignal<Result<SatisfiedProgram, ProgramError>>,
+    /// AMR of the satisfied program, which commits to the types and to the witness data.
+    pub lazy_amr: RwSignal<Result<simplicity::Amr, ProgramError>>,
+    /// Identity root of the satisfied program, which `simplicity` computes as its IHR.
+    pub lazy_imr: RwSignal<Result<simplicity::Ihr, ProgramError>>,
+    /// Whether a compilation is scheduled because the program text changed.
+    pub compiling: RwSignal<bool>,
+    /// Number of scheduled compilations, to ignore outdated timers.
+    compile_generation: StoredValue<usize>,
+    /// Whether compilations measure the time of each stage.
+    pub show_timings: RwSignal<bool>,
+    /// Timings of the last compilation, if they were measured.
+    pub timings: RwSignal<Option<CompileTimings>>,
+    /// Results of the last compilations, least recently used first.
+    compile_cache: StoredValue<VecDeque<CachedCompilation>>,
+    /// Text that was typed into the editor but is not yet in `text`.
+    pending_text: StoredValue<Option<String>>,
+    /// Number of edits of the pending text, to ignore outdated timers.
+    pending_generation: StoredValue<usize>,
+    /// Text field of the editor, once it is mounted.
+    editor: StoredValue<Option<web_sys::HtmlTextAreaElement>>,
 }
 
+/// Time without typing after which the edited text becomes the program text, in milliseconds.
+///
+/// Every change of the program text triggers recompilation,
+/// which is too slow to do on each keystroke for large programs.
+const TEXT_DEBOUNCE_MS: u32 = 250;
+
+/// Time after the last change of the program text after which it is compiled, in milliseconds.
+///
+/// Compilation runs outside the render that follows an edit, so typing stays responsive.
+const COMPILE_DEBOUNCE_MS: u32 = 300;
+
 impl Default for Program {
     fn default() -> Self {
         Self::new(String::default())
@@ -34,59 +176,407 @@ impl Program {
         let program = Self {
             text: create_rw_signal(text),
             cached_text: create_rw_signal(String::new()),
-            lazy_cmr: create_rw_signal(Err(String::new())),
-            lazy_satisfied: create_rw_signal(Err(String::new())),
+            cached_debug_symbols: store_value(false),
+            settings: Settings::default(),
+            lazy_compiled: create_rw_signal(CompileResult::default()),
+            last_cmr: create_rw_signal(None),
+            lazy_satisfied: create_rw_signal(Err(ProgramError::NotCompiled)),
+            lazy_amr: create_rw_signal(Err(ProgramError::NotCompiled)),
+            lazy_imr: create_rw_signal(Err(ProgramError::NotCompiled)),
+            compiling: create_rw_signal(false),
+            compile_generation: store_value(0),
+            show_timings: create_rw_signal(false),
+            timings: create_rw_signal(None),
+            compile_cache: store_value(VecDeque::new()),
+            pending_text: store_value(None),
+            pending_generation: store_value(0),
+            editor: store_value(None),
         };
         program.update_on_read();
         program
     }
 
+    /// Compile and format the stored copies as the given settings say.
+    pub fn with_settings(self, settings: Settings) -> Self {
+        Self { settings, ..self }
+    }
+
+    /// Return the program text to store or share, which is formatted if that is enabled.
+    ///
+    /// Programs that do not parse are never formatted.
+    pub fn text_to_store(self) -> String {
+        self.text.with_untracked(|text| {
+            self.settings
+                .0
+                .with_untracked(|preferences| {
+                    preferences.format_on_save.then_some(preferences.tab_width)
+                })
+                .and_then(|indent| format::format(text, indent).ok())
+                .unwrap_or_else(|| text.clone())
+        })
+    }
+
     pub fn is_empty(&self) -> bool {
         self.text.with_untracked(String::is_empty)
     }
 
-    pub fn cmr(self) -> Result<simplicity::Cmr, String> {
-        self.update_on_read();
-        self.lazy_cmr.get_untracked()
+    /// Return the CMR of the last compilation.
+    ///
+    /// The program is not compiled here; compilation follows edits with a delay.
+    pub fn cmr(self) -> Result<simplicity::Cmr, ProgramError> {
+        self.lazy_compiled.with(|compiled| compiled.cmr.clone())
+    }
+
+    /// Return the problems that the last compilation found, located in the compiled text.
+    ///
+    /// The compiled text lags behind the program text while the program is edited.
+    pub fn diagnostics(self) -> Vec<Diagnostic> {
+        self.cached_text.with(|text| {
+            self.lazy_compiled.with(|compiled| {
+                let error = compiled
+                    .cmr
+                    .as_ref()
+                    .err()
+                    .filter(|error| **error != ProgramError::NotCompiled)
+                    .map(|error| match error.span() {
+                        Some(span) => Diagnostic::from_span(span, error.message()),
+                        None => Diagnostic::from_error(text, &error.to_string()),
+                    });
+                let warnings = compiled.warnings.iter().map(|lint| {
+                    Diagnostic::line(text, lint.line, lint.message.clone(), Severity::Warning)
+                });
+                error.into_iter().chain(warnings).collect()
+            })
+        })
+    }
+
+    /// Return the satisfied program of the last compilation.
+    ///
+    /// The program is not compiled here; compilation follows edits with a delay.
+    pub fn satisfied(self) -> Result<SatisfiedProgram, ProgramError> {
+        self.lazy_satisfied.get()
+    }
+
+    /// Set the program text after the user paused typing.
+    ///
+    /// Each call restarts the wait.
+    pub fn set_text_debounced(self, text: String) {
+        self.pending_text.set_value(Some(text));
+        self.pending_generation
+            .update_value(|generation| *generation += 1);
+        let generation = self.pending_generation.get_value();
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(TEXT_DEBOUNCE_MS).await;
+            if self.pending_generation.get_value() == generation {
+                self.flush_text();
+            }
+        });
+    }
+
+    /// Set the program text to the pending text immediately, if there is any.
+    pub fn flush_text(self) {
+        if let Some(text) = self.pending_text.try_update_value(Option::take).flatten() {
+            self.text.set(text);
+        }
+    }
+
+    /// Use `editor` as the text field that shows the program text.
+    pub fn attach_editor(self, editor: web_sys::HtmlTextAreaElement) {
+        self.editor.set_value(Some(editor));
+    }
+
+    /// Set the program text to the text of the editor, if they differ.
+    ///
+    /// Some edits change the editor without an input event,
+    /// such as edits of browser extensions, so the program text can fall behind.
+    /// Return whether the program text was out of sync.
+    pub fn sync_editor(self) -> bool {
+        self.flush_text();
+        let Some(shown) = self.editor.with_value(|editor| {
+            editor
+                .as_ref()
+                .filter(|editor| editor.is_connected())
+                .map(web_sys::HtmlTextAreaElement::value)
+        }) else {
+            return false;
+        };
+        if self.text.with_untracked(|text| *text == shown) {
+            return false;
+        }
+        self.text.set(shown);
+        true
+    }
+
+    /// Move the cursor of the editor to the start of the 1-based `line` and scroll to it.
+    pub fn go_to_line(self, line: usize) {
+        self.flush_text();
+        let Some(element) = self
+            .editor
+            .get_value()
+            .filter(|editor| editor.is_connected())
+        else {
+            return;
+        };
+        let (start, line_count) = self.text.with_untracked(|s| {
+            let offset = s
+                .split_inclusive('\n')
+                .take(line.saturating_sub(1))
+                .map(str::len)
+                .sum();
+            (editor::utf16_offset(s, offset), s.lines().count())
+        });
+        let _result = element.focus();
+        // safety: text areas with more than 2^32 characters are unrealistic
+        let _result = element.set_selection_range(start as u32, start as u32);
+        scroll_to_line(&element, line, line_count);
+    }
+
+    /// Return the text that is selected in the editor, if there is a selection.
+    pub fn selected_text(self) -> Option<String> {
+        self.editor.with_value(|editor| {
+            let editor = editor.as_ref()?;
+            let start = editor.selection_start().ok()??;
+            let end = editor.selection_end().ok()??;
+            let value = editor.value();
+            let start = editor::byte_offset(&value, start as usize);
+            let end = editor::byte_offset(&value, end as usize);
+            let selected = value.get(start..end)?.to_string();
+            (!selected.trim().is_empty()).then_some(selected)
+        })
+    }
+
+    /// Check whether the program is compiled with debug symbols.
+    pub fn includes_debug_symbols(self) -> bool {
+        self.settings
+            .0
+            .with_untracked(|preferences| preferences.debug_symbols)
     }
 
-    pub fn satisfied(self) -> Result<SatisfiedProgram, String> {
-        self.update_on_read();
-        self.lazy_satisfied.get_untracked()
+    /// Check whether the program is compiled automatically.
+    fn auto_compiles(self) -> bool {
+        self.settings
+            .0
+            .with_untracked(|preferences| preferences.auto_compile)
     }
 
+    /// Compile the program if its text changed since the last compilation
+    /// and if automatic compilation is enabled.
     pub fn update_on_read(self) {
-        let needs_update = self.text.with_untracked(|text| {
-            self.cached_text
-                .with_untracked(|cached_text| text != cached_text)
+        if self.auto_compiles() {
+            self.compile();
+        }
+    }
+
+    /// Set the satisfied program together with its Merkle roots.
+    fn set_satisfied(self, satisfied: Result<SatisfiedProgram, ProgramError>) {
+        let redeem = satisfied.as_ref().map(SatisfiedProgram::redeem);
+        self.lazy_amr
+            .set(redeem.map(|redeem| redeem.amr()).map_err(Clone::clone));
+        self.lazy_imr
+            .set(redeem.map(|redeem| redeem.ihr()).map_err(Clone::clone));
+        self.lazy_satisfied.set(satisfied);
+    }
+
+    /// Check whether the text or the debug setting changed since the last compilation.
+    fn needs_compilation(self) -> bool {
+        self.includes_debug_symbols() != self.cached_debug_symbols.get_value()
+            || self.text.with_untracked(|text| {
+                self.cached_text
+                    .with_untracked(|cached_text| text != cached_text)
+            })
+    }
+
+    /// Compile the program shortly after the last change of its text,
+    /// if automatic compilation is enabled.
+    ///
+    /// Each call restarts the wait. Until the compilation runs, the program counts as compiling.
+    pub fn compile_debounced(self) {
+        if !self.auto_compiles() {
+            return;
+        }
+        if !self.needs_compilation() {
+            return;
+        }
+        self.compiling.set(true);
+        self.compile_generation
+            .update_value(|generation| *generation += 1);
+        let generation = self.compile_generation.get_value();
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(COMPILE_DEBOUNCE_MS).await;
+            if self.compile_generation.get_value() == generation {
+                self.compile();
+            }
         });
-        if !needs_update {
+    }
+
+    /// Compile the program if its text changed since the last compilation.
+    ///
+    /// Texts that were compiled recently take their results from a cache,
+    /// unless the timings of the compilation are measured.
+    pub fn compile(self) {
+        self.flush_text();
+        // Compiling now supersedes any scheduled compilation
+        self.compile_generation
+            .update_value(|generation| *generation += 1);
+        if self.compiling.get_untracked() {
+            self.compiling.set(false);
+        }
+        if !self.needs_compilation() {
             return;
         }
+        let measure = self.show_timings.get_untracked();
+        let debug_symbols = self.includes_debug_symbols();
         self.text.with_untracked(|text| {
             self.cached_text.set(text.clone());
-            let compiled = simplicityhl::Arguments::parse_from_str(text)
-                .map_err(|error| error.to_string())
-                .and_then(|args| {
-                    CompiledProgram::new(
-                        text.as_str(),
-                        args,
-                        false, /* include debug symbols */
-                    )
-                });
+            self.cached_debug_symbols.set_value(debug_symbols);
+            let hash = text_hash(text, debug_symbols);
+            if !measure {
+                if let Some(cached) = self.take_cached(hash, text, debug_symbols) {
+                    if let Ok(cmr) = cached.compiled.cmr {
+                        self.last_cmr.set(Some(cmr));
+                    }
+                    self.lazy_compiled.set(cached.compiled.clone());
+                    self.set_satisfied(cached.satisfied.clone());
+                    self.timings.set(None);
+                    self.cache_compilation(cached);
+                    return;
+                }
+            }
+            let started = now_ms();
+            if measure {
+                // The compiler does not report its stages, so parse separately
+                let _parsed = simplicityhl::parse::Program::parse_from_str(text);
+            }
+            let parsed = now_ms();
+            let compiled = compile_text(text, debug_symbols);
+            let compiled_at = now_ms();
             let cmr = compiled
                 .as_ref()
                 .map(|x| x.commit().cmr())
                 .map_err(Clone::clone);
-            self.lazy_cmr.set(cmr);
-            let satisfied = compiled.and_then(|x| {
-                let witness = WitnessValues::parse_from_str(text)?;
-                x.satisfy(witness)
+            if let Ok(cmr) = cmr {
+                self.last_cmr.set(Some(cmr));
+            }
+            let arrow = compiled
+                .as_ref()
+                .ok()
+                .map(|x| util::type_arrow(x.commit().arrow()));
+            let predicate = compiled
+                .as_ref()
+                .is_ok_and(|x| util::is_predicate(x.commit().arrow()));
+            let commit_nodes = compiled
+                .as_ref()
+                .ok()
+                .map(|x| util::get_complexity_metrics(x.commit().as_ref()).unique_nodes);
+            let compile_result = CompileResult {
+                cmr,
+                arrow,
+                predicate,
+                commit_nodes,
+                warnings: lint::lint(text),
+            };
+            self.lazy_compiled.set(compile_result.clone());
+            let satisfying = now_ms();
+            let satisfied = compiled.and_then(|x| satisfy_text(text, &x));
+            let satisfied_at = now_ms();
+            self.set_satisfied(satisfied.clone());
+            self.cache_compilation(CachedCompilation {
+                hash,
+                text: text.clone(),
+                debug_symbols,
+                compiled: compile_result,
+                satisfied,
             });
-            self.lazy_satisfied.set(satisfied);
+            self.timings.set(measure.then_some(CompileTimings {
+                parse: parsed - started,
+                compile: compiled_at - parsed,
+                satisfy: satisfied_at - satisfying,
+            }));
         });
     }
 
+    /// Remove the cached results of compiling `text`, whose hash is `hash`, from the cache.
+    fn take_cached(self, hash: u64, text: &str, debug_symbols: bool) -> Option<CachedCompilation> {
+        self.compile_cache
+            .try_update_value(|cache| {
+                let index = cache.iter().position(|cached| {
+                    cached.hash == hash
+                        && cached.text == text
+                        && cached.debug_symbols == debug_symbols
+                })?;
+                cache.remove(index)
+            })
+            .flatten()
+    }
+
+    /// Add the results of a compilation to the cache as the most recently used entry,
+    /// evicting the least recently used entry if the cache is full.
+    fn cache_compilation(self, cached: CachedCompilation) {
+        self.compile_cache.update_value(|cache| {
+            cache.retain(|entry| {
+                entry.hash != cached.hash
+                    || entry.text != cached.text
+                    || entry.debug_symbols != cached.debug_symbols
+            });
+            if cache.len() == COMPILE_CACHE_SIZE {
+                cache.pop_front();
+            }
+            cache.push_back(cached);
+        });
+    }
+
+    /// Show or hide the timings of the compilation stages.
+    ///
+    /// Showing the timings recompiles the program to measure them.
+    pub fn toggle_timings(self) {
+        let show = !self.show_timings.get_untracked();
+        self.show_timings.set(show);
+        match show {
+            true => {
+                self.cached_text.update(String::clear);
+                self.compile();
+            }
+            false => self.timings.set(None),
+        }
+    }
+
+    /// Compile the program with debug symbols and satisfy it.
+    ///
+    /// Debug symbols change the CMR of the program,
+    /// so the result is not cached.
+    pub fn satisfied_with_debug_symbols(self) -> Result<SatisfiedProgram, ProgramError> {
+        self.text.with_untracked(|text| {
+            compile_text(text, true /* include debug symbols */)
+                .and_then(|compiled| satisfy_text(text, &compiled))
+        })
+    }
+
+    /// Forget the results of the last compilation, because another program replaces this one.
+    ///
+    /// The next program is compiled from scratch, so none of the results of this one remain.
+    pub fn reset_compilation(self) {
+        // Scheduled compilations belong to the replaced program
+        self.compile_generation
+            .update_value(|generation| *generation += 1);
+        self.compiling.set(false);
+        self.cached_text.update(String::clear);
+        self.lazy_compiled.set(CompileResult::default());
+        self.last_cmr.set(None);
+        self.set_satisfied(Err(ProgramError::NotCompiled));
+        self.timings.set(None);
+    }
+
+    /// Replace the program by an empty main function.
+    ///
+    /// If `with_modules`, the program also gets empty witness and parameter modules.
+    pub fn start_new(self, with_modules: bool) {
+        self.flush_text();
+        self.text.set("fn main() {\n}\n".to_string());
+        if with_modules {
+            self.add_default_modules();
+        }
+    }
+
     pub fn add_default_modules(self) {
         let (contains_witness, contains_param) = self
             .text
@@ -102,145 +592,643 @@ impl Program {
     }
 }
 
-#[derive(Copy, Clone)]
-pub struct Runtime {
-    program: Program,
-    env: Signal<ElementsEnv<Arc<elements::Transaction>>>,
-    pub run_succeeded: RwSignal<Option<bool>>,
-    pub debug_output: RwSignal<String>,
-    pub error_output: RwSignal<String>,
-    program_expr: RwSignal<Option<Arc<Expression>>>,
-}
-
-impl Runtime {
-    pub fn new(program: Program, env: Signal<ElementsEnv<Arc<elements::Transaction>>>) -> Self {
-        Self {
-            program,
-            env,
-            run_succeeded: RwSignal::default(),
-            debug_output: RwSignal::default(),
-            error_output: RwSignal::default(),
-            program_expr: RwSignal::default(),
-        }
-    }
-
-    pub fn program_expr(&self) -> ReadSignal<Option<Arc<Expression>>> {
-        self.program_expr.read_only()
-    }
-
-    fn set_success(self, success: bool) {
-        spawn_local(async move {
-            self.run_succeeded.set(Some(success));
-            gloo_timers::future::TimeoutFuture::new(500).await;
-            self.run_succeeded.set(None);
-        });
-        web_sys::window()
-            .as_ref()
-            .map(web_sys::Window::navigator)
-            .map(|navigator| match success {
-                true => navigator.vibrate_with_duration(200),
-                false => navigator.vibrate_with_duration(500),
-            });
-    }
-
-    pub fn run(self) {
-        self.debug_output.update(String::clear);
-        let satisfied_program = match self.program.satisfied() {
-            Ok(x) => x,
-            Err(error) => {
-                self.error_output.set(error);
-                self.program_expr.set(None);
-                self.set_success(false);
-                return;
-            }
-        };
-        // Store the program expression for analysis
-        self.program_expr
-            .set(Some(satisfied_program.redeem().clone()));
-
-        let mut runner = Runner::for_program(&satisfied_program);
-        let success = self.env.with(|env| match runner.run(env) {
-            Ok(..) => {
-                self.error_output.update(String::clear);
-                true
-            }
-            Err(error) => {
-                self.error_output.set(error.to_string());
-                false
-            }
-        });
-        self.debug_output
-            .set(runner.debug_output().into_iter().join("\n"));
-        self.set_success(success);
-    }
-}
-
 const TAB_KEY: u32 = 9;
 const ENTER_KEY: u32 = 13;
+const ESCAPE_KEY: u32 = 27;
+const ARROW_LEFT_KEY: u32 = 37;
+const ARROW_UP_KEY: u32 = 38;
+const ARROW_RIGHT_KEY: u32 = 39;
+const ARROW_DOWN_KEY: u32 = 40;
+const SLASH_KEY: u32 = 191;
+const F9_KEY: u32 = 120;
+const F12_KEY: u32 = 123;
+
+/// Scroll the text area such that the given line is roughly in the middle.
+///
+/// Text areas don't expose the position of lines,
+/// so we assume that all lines have the same height.
+fn scroll_to_line(element: &web_sys::HtmlTextAreaElement, line: usize, line_count: usize) {
+    let line = i64::try_from(line.saturating_sub(1)).unwrap_or(i64::MAX);
+    let line_count = i64::try_from(line_count.max(1)).unwrap_or(i64::MAX);
+    let line_top = i64::from(element.scroll_height()).saturating_mul(line) / line_count;
+    let top = line_top - i64::from(element.client_height()) / 2;
+    element.set_scroll_top(i32::try_from(top.max(0)).unwrap_or(i32::MAX));
+}
 
 #[component]
 pub fn ProgramTab() -> impl IntoView {
     let program = use_context::<Program>().expect("program should exist in context");
     let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let show_desugared =
+        use_context::<ShowDesugared>().expect("desugar view setting should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let read_only = use_context::<ReadOnly>().expect("read-only setting should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let saved_programs =
+        use_context::<SavedPrograms>().expect("saved programs should exist in context");
     let textarea_ref = create_node_ref::<html::Textarea>();
+    let highlight_ref = create_node_ref::<html::Pre>();
+    textarea_ref.on_load(move |textarea| program.attach_editor((*textarea).clone()));
+    // Text as shown in the editor, which the program text follows after a delay
+    let typed = create_rw_signal(program.text.get_untracked());
+    create_effect(move |_| {
+        let text = program.text.get();
+        if typed.with_untracked(|typed| *typed != text) {
+            typed.set(text);
+        }
+    });
+    let highlighted = move || {
+        let semantic = settings.semantic_highlighting();
+        typed.with(|text| {
+            let pieces = match semantic {
+                true => highlight::highlight_semantic(text),
+                false => highlight::highlight(text),
+            };
+            let pieces = pieces
+                .into_iter()
+                .map(|(kind, piece)| match kind {
+                    Some(kind) => {
+                        view! { <span class=kind.class()>{piece.to_string()}</span> }.into_view()
+                    }
+                    None => piece.to_string().into_view(),
+                })
+                .collect_view();
+            // A trailing newline only takes up space if something follows it
+            (pieces, text.ends_with('\n').then_some(" "))
+        })
+    };
+    let diagnostics = create_memo(move |_| program.diagnostics());
+    let marked = move || {
+        typed.with(|text| {
+            // The problems of an older text would mark the wrong places
+            if program.cached_text.with(|compiled| compiled != text) {
+                return None;
+            }
+            diagnostics.with(|diagnostics| {
+                if diagnostics.is_empty() {
+                    return None;
+                }
+                let pieces = diagnostic::mark(text, diagnostics)
+                    .into_iter()
+                    .map(|(severity, piece)| match severity {
+                        Some(severity) => {
+                            // The pointer finds the problem under it by the offset of the piece
+                            let offset = piece.as_ptr() as usize - text.as_ptr() as usize;
+                            view! {
+                                <span class=severity.class() data-offset=offset>
+                                    {piece.to_string()}
+                                </span>
+                            }
+                            .into_view()
+                        }
+                        None => piece.to_string().into_view(),
+                    })
+                    .collect_view();
+                Some((pieces, text.ends_with('\n').then_some(" ")))
+            })
+        })
+    };
+    let diagnostics_ref = create_node_ref::<html::Pre>();
+    let sync_scroll = move |_| {
+        if let Some(textarea) = textarea_ref.get() {
+            for overlay in [highlight_ref.get(), diagnostics_ref.get()]
+                .into_iter()
+                .flatten()
+            {
+                overlay.set_scroll_top(textarea.scroll_top());
+                overlay.set_scroll_left(textarea.scroll_left());
+            }
+        }
+    };
+
+    let pasted = create_rw_signal(false);
+    let check_pasted_program = move || {
+        let result = program.text.with_untracked(|text| {
+            simplicityhl::parse::Program::parse_from_str(text)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        });
+        match result {
+            Ok(()) => toasts.push(ToastKind::Success, "Pasted program is valid SimplicityHL"),
+            Err(error) => toasts.push(
+                ToastKind::Error,
+                format!("Pasted program does not parse:\n{error}"),
+            ),
+        }
+    };
+    // Names that complete the partial jet, witness or parameter name before the cursor
+    let completions = create_rw_signal(Vec::<editor::Completion>::new());
+    let completion_index = create_rw_signal(0_usize);
+    let completion_style = create_rw_signal(String::new());
+    let close_completions = move || {
+        if !completions.with_untracked(Vec::is_empty) {
+            completions.set(vec![]);
+        }
+    };
+    let update_completions = move || {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let Ok(Some(cursor)) = element.selection_start() else {
+            return;
+        };
+        let found = typed.with_untracked(|s| {
+            let (span, completions) = editor::completions(s, cursor as usize)?;
+            let before = &s[..editor::byte_offset(s, span.start)];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = before.matches('\n').count();
+            let column = before[line_start..].chars().count();
+            Some((completions, line, column))
+        });
+        let Some((found, line, column)) = found else {
+            close_completions();
+            return;
+        };
+        // Place the list below the cursor, assuming that no line wraps
+        completion_style.set(format!(
+            "top: calc(13px + {} * 1.4em - {}px); left: calc(13px + {column}ch);",
+            line + 1,
+            element.scroll_top()
+        ));
+        completion_index.set(0);
+        completions.set(found);
+    };
+    let accept_completion = move |name: String| {
+        program.flush_text();
+        close_completions();
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let Ok(Some(cursor)) = element.selection_start() else {
+            return;
+        };
+        let mut new_cursor = None;
+        program.text.update(|s| {
+            if let Some((span, _)) = editor::completions(s, cursor as usize) {
+                let start = editor::byte_offset(s, span.start);
+                let end = editor::byte_offset(s, span.end);
+                s.replace_range(start..end, &name);
+                new_cursor = Some(editor::utf16_offset(s, start + name.len()));
+            }
+        });
+        if let Some(new_cursor) = new_cursor {
+            // safety: text areas with more than 2^32 characters are unrealistic
+            let _result = element.set_selection_range(new_cursor as u32, new_cursor as u32);
+        }
+        let _result = element.focus();
+    };
+    // Return whether the key was used to choose a completion
+    let handle_completion_key = move |event: &ev::KeyboardEvent| {
+        let count = completions.with_untracked(Vec::len);
+        if count == 0 || event.ctrl_key() || event.meta_key() || event.alt_key() {
+            return false;
+        }
+        match event.key_code() {
+            ARROW_DOWN_KEY => completion_index.update(|index| *index = (*index + 1) % count),
+            ARROW_UP_KEY => completion_index.update(|index| *index = (*index + count - 1) % count),
+            ENTER_KEY | TAB_KEY => {
+                let name = completions.with_untracked(|completions| {
+                    completions
+                        .get(completion_index.get_untracked())
+                        .map(|completion| completion.name.clone())
+                });
+                if let Some(name) = name {
+                    accept_completion(name);
+                }
+            }
+            ESCAPE_KEY => close_completions(),
+            ARROW_LEFT_KEY | ARROW_RIGHT_KEY => {
+                close_completions();
+                return false;
+            }
+            _ => return false,
+        }
+        event.prevent_default();
+        true
+    };
 
     let update_program_text = move |event: ev::Event| {
-        program.text.set(event_target_value(&event));
+        let text = event_target_value(&event);
+        typed.set(text.clone());
+        program.set_text_debounced(text);
+        update_completions();
+        if pasted.get_untracked() {
+            pasted.set(false);
+            program.flush_text();
+            check_pasted_program();
+        }
     };
-    let insert_4_spaces = move || {
+    let indent = move || {
+        program.flush_text();
         let element = textarea_ref.get().expect("<textarea> should be mounted");
         if let Ok(Some(start)) = element.selection_start() {
-            let start_ = start as usize; // safety: 32-bit machine of higher
-            program.text.update(|s| s.insert_str(start_, "    "));
-            let _result = element.set_selection_range(start + 4, start + 4);
+            let width = settings
+                .0
+                .with_untracked(|preferences| preferences.tab_width);
+            let (text, new_start) = program
+                .text
+                .with_untracked(|s| editor::indent(s, start as usize, width));
+            program.text.set(text);
+            // safety: text areas with more than 2^32 characters are unrealistic
+            let _result = element.set_selection_range(new_start as u32, new_start as u32);
         }
     };
-    let delete_4_spaces = move || {
+    let unindent = move || {
+        program.flush_text();
         let element = textarea_ref.get().expect("<textarea> should be mounted");
         if let Ok(Some(start)) = element.selection_start() {
-            let start_ = start as usize; // safety: 32-bit machine of higher
-            if start < 4 || program.text.with(|s| &s[start_ - 4..start_] != "    ") {
+            let width = settings
+                .0
+                .with_untracked(|preferences| preferences.tab_width);
+            let Some((text, new_start)) = program
+                .text
+                .with_untracked(|s| editor::unindent(s, start as usize, width))
+            else {
                 return;
-            }
-            program
+            };
+            program.text.set(text);
+            // safety: text areas with more than 2^32 characters are unrealistic
+            let _result = element.set_selection_range(new_start as u32, new_start as u32);
+        }
+    };
+    let toggle_comments = move || {
+        program.flush_text();
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        if let (Ok(Some(start)), Ok(Some(end))) =
+            (element.selection_start(), element.selection_end())
+        {
+            let (text, new_start, new_end) = program
                 .text
-                .update(|s| s.replace_range(start_ - 4..start_, ""));
-            let _result = element.set_selection_range(start - 4, start - 4);
+                .with(|s| editor::toggle_line_comments(s, start as usize, end as usize));
+            program.text.set(text);
+            // safety: text areas with more than 2^32 characters are unrealistic
+            let _result = element.set_selection_range(new_start as u32, new_end as u32);
+        }
+    };
+    let go_to_definition = move || {
+        program.flush_text();
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let Ok(Some(cursor)) = element.selection_start() else {
+            return;
+        };
+        let selection = program.text.with_untracked(|s| {
+            let offset = editor::byte_offset(s, cursor as usize);
+            source::definition_at(s, offset).map(|definition| {
+                let start = editor::utf16_offset(s, definition.name_span.start);
+                let end = editor::utf16_offset(s, definition.name_span.end);
+                (start, end, definition.line, s.lines().count())
+            })
+        });
+        if let Some((start, end, line, line_count)) = selection {
+            // safety: text areas with more than 2^32 characters are unrealistic
+            let _result = element.set_selection_range(start as u32, end as u32);
+            scroll_to_line(&element, line, line_count);
+        }
+    };
+    let go_to_line = move |line: usize| program.go_to_line(line);
+    let run_to_cursor = move || {
+        program.flush_text();
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        if let Ok(Some(cursor)) = element.selection_start() {
+            let line = program
+                .text
+                .with_untracked(|s| source::line_of(s, editor::byte_offset(s, cursor as usize)));
+            runtime.run_to_line(line);
+        }
+    };
+    let toggle_breakpoint = move || {
+        program.flush_text();
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        if let Ok(Some(cursor)) = element.selection_start() {
+            let line = program
+                .text
+                .with_untracked(|s| source::line_of(s, editor::byte_offset(s, cursor as usize)));
+            runtime.toggle_breakpoint(line);
         }
     };
     let handle_keydown = move |event: ev::KeyboardEvent| {
-        if event.ctrl_key() && event.key_code() == ENTER_KEY {
+        // Cmd on macOS takes the place of Ctrl
+        let ctrl_or_cmd = event.ctrl_key() || event.meta_key();
+        if handle_completion_key(&event) {
+            // The key chose a completion
+        } else if ctrl_or_cmd && event.shift_key() && event.key_code() == ENTER_KEY {
+            event.prevent_default();
+            run_to_cursor();
+        } else if ctrl_or_cmd && event.key_code() == ENTER_KEY {
+            event.prevent_default();
             runtime.run();
+        } else if read_only.0.get_untracked() {
+            // The remaining shortcuts edit the program
+        } else if ctrl_or_cmd && event.key_code() == SLASH_KEY {
+            event.prevent_default();
+            toggle_comments();
+        } else if event.key_code() == F9_KEY {
+            event.prevent_default();
+            toggle_breakpoint();
+        } else if event.key_code() == F12_KEY {
+            event.prevent_default();
+            go_to_definition();
         } else if event.key_code() == TAB_KEY {
             event.prevent_default();
             match event.shift_key() {
-                false => insert_4_spaces(),
-                true => delete_4_spaces(),
+                false => indent(),
+                true => unindent(),
+            }
+        }
+    };
+
+    // Hint about the name under the cursor, with the link to its documentation if it is a jet
+    let jet_hint = create_rw_signal(Option::<(String, Option<String>)>::None);
+    let bindings = create_memo(move |_| {
+        program
+            .text
+            .with(|text| binding::let_bindings(text).unwrap_or_default())
+    });
+    let update_jet_hint = move || {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let Ok(Some(cursor)) = element.selection_start() else {
+            return;
+        };
+        let problem = typed.with_untracked(|s| {
+            if program.cached_text.with_untracked(|compiled| compiled != s) {
+                return None;
+            }
+            let offset = editor::byte_offset(s, cursor as usize);
+            diagnostics.with_untracked(|diagnostics| {
+                diagnostic::at(s, diagnostics, offset).map(|problem| problem.message.clone())
+            })
+        });
+        let hint = problem.map(|problem| (problem, None)).or_else(|| {
+            program.text.with_untracked(|s| {
+                let offset = editor::byte_offset(s, cursor as usize);
+                let jet_hint = source::jet_at(s, offset)
+                    .map(|jet| (jet::description(jet), jet::documentation_url(jet)));
+                jet_hint.or_else(|| {
+                    let word = source::word_at(s, offset)?;
+                    let line = source::line_of(s, offset);
+                    bindings.with_untracked(|bindings| {
+                        binding::binding_at(bindings, &s[word], line)
+                            .map(|binding| (format!("{}: {}", binding.name, binding.ty), None))
+                    })
+                })
+            })
+        });
+        if jet_hint.get_untracked() != hint {
+            jet_hint.set(hint);
+        }
+    };
+
+    // The underlines are behind the text area, so the pointer is compared with their boxes
+    let hovered_problem = create_rw_signal(Option::<String>::None);
+    let update_hovered_problem = move |event: ev::MouseEvent| {
+        let (x, y) = (f64::from(event.client_x()), f64::from(event.client_y()));
+        let offset = diagnostics_ref.get_untracked().and_then(|overlay| {
+            let pieces = overlay.children();
+            (0..pieces.length())
+                .filter_map(|index| pieces.item(index))
+                .find(|piece| {
+                    let boxes = piece.get_client_rects();
+                    (0..boxes.length())
+                        .filter_map(|index| boxes.item(index))
+                        .any(|rect| {
+                            rect.left() <= x
+                                && x <= rect.right()
+                                && rect.top() <= y
+                                && y <= rect.bottom()
+                        })
+                })
+                .and_then(|piece| piece.get_attribute("data-offset"))
+                .and_then(|offset| offset.parse::<usize>().ok())
+        });
+        let problem = offset.and_then(|offset| {
+            typed.with_untracked(|s| {
+                diagnostics.with_untracked(|diagnostics| {
+                    diagnostic::at(s, diagnostics, offset).map(|problem| problem.message.clone())
+                })
+            })
+        });
+        if hovered_problem.get_untracked() != problem {
+            hovered_problem.set(problem);
+        }
+    };
+
+    let drag_over = create_rw_signal(false);
+    let handle_dragover = move |event: ev::DragEvent| {
+        event.prevent_default();
+        drag_over.set(true);
+    };
+    let handle_dragleave = move |_event: ev::DragEvent| drag_over.set(false);
+    let handle_drop = move |event: ev::DragEvent| {
+        // Prevent the browser from opening the file
+        event.prevent_default();
+        drag_over.set(false);
+        if read_only.0.get_untracked() {
+            return;
+        }
+        let Some(file) = event
+            .data_transfer()
+            .and_then(|transfer| transfer.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        if is_unsaved_work(program, saved_programs) && !confirm_replace("the dropped file") {
+            return;
+        }
+        spawn_local(async move {
+            let Ok(text) = wasm_bindgen_futures::JsFuture::from(file.text()).await else {
+                return;
+            };
+            let text = text.as_string().unwrap_or_default();
+            match simplicityhl::parse::Program::parse_from_str(&text) {
+                Ok(..) => {
+                    // Text typed while the file was read must not overwrite the dropped program
+                    program.flush_text();
+                    replace_program(program, &runtime, text);
+                }
+                Err(error) => runtime.error_output.set(Some(ProgramError::File {
+                    name: file.name(),
+                    error: Box::new(error),
+                })),
             }
+        });
+    };
+
+    let completion_option = move |index: usize, completion: &editor::Completion| {
+        let name = completion.name.clone();
+        // Keep the focus in the editor, so the list stays open until the link opens
+        let documentation = completion.documentation.clone().map(|url| {
+            view! {
+                <a
+                    class="completion-docs"
+                    href=url
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    tabindex="-1"
+                    title="Open the documentation of the jet"
+                    on:mousedown=|event| event.prevent_default()
+                >
+                    <i class="fas fa-book"></i>
+                </a>
+            }
+        });
+        view! {
+            <div class="completion-option">
+                <button
+                    class="action-button"
+                    class:active=move || completion_index.get() == index
+                    type="button"
+                    tabindex="-1"
+                    on:mousedown=move |event| {
+                        event.prevent_default();
+                        accept_completion(name.clone());
+                    }
+                >
+                    {completion.name.clone()}
+                    <span class="completion-detail">{completion.detail.clone()}</span>
+                </button>
+                {documentation}
+            </div>
         }
     };
+    let completion_list = move || {
+        completions.with(|completions| {
+            if completions.is_empty() {
+                return None;
+            }
+            let options = completions
+                .iter()
+                .enumerate()
+                .map(|(index, completion)| completion_option(index, completion))
+                .collect_view();
+            Some(view! {
+                <div class="editor-completions" style=move || completion_style.get()>
+                    {options}
+                </div>
+            })
+        })
+    };
+
+    let duplicates =
+        create_memo(move |_| program.text.with(|text| witness::duplicate_warnings(text)));
+    let desugared = create_memo(move |_| {
+        show_desugared
+            .0
+            .get()
+            .then(|| program.text.with(|text| desugar::desugar_text(text)))
+    });
 
     view! {
-        <div class="tab-content">
+        <div
+            class="tab-content"
+            class:drag-over=drag_over
+            on:dragover=handle_dragover
+            on:dragleave=handle_dragleave
+            on:drop=handle_drop
+            style=move || format!("--editor-font-size: {}px", settings.font_size())
+        >
             <div class="copy-program">
-                <CopyToClipboard content=program.text class="copy-button" tooltip_below=true>
-                    <i class="far fa-copy"></i>
-                </CopyToClipboard>
+                <FontSizeControls />
+                <ReadOnlyToggle />
+                <CopyProgram />
             </div>
+            <div class="program-columns">
+            <div class="program-editor">
+            <pre class="program-input-field program-highlight" aria-hidden="true" node_ref=highlight_ref>
+                {highlighted}
+            </pre>
+            <pre
+                class="program-input-field program-highlight program-diagnostics"
+                aria-hidden="true"
+                node_ref=diagnostics_ref
+            >
+                {marked}
+            </pre>
             <textarea
                 class="program-input-field"
                 placeholder="Enter your program here"
                 rows="25"
                 cols="80"
                 spellcheck="false"
+                readonly=read_only.0
+                class:read-only=read_only.0
                 prop:value=program.text
                 on:input=update_program_text
+                on:scroll=move |event| {
+                    sync_scroll(event);
+                    close_completions();
+                }
+                on:blur=move |_| {
+                    program.flush_text();
+                    close_completions();
+                }
                 on:keydown=handle_keydown
+                on:paste=move |_| pasted.set(true)
+                on:keyup=move |_| update_jet_hint()
+                on:click=move |_| {
+                    close_completions();
+                    update_jet_hint();
+                }
+                on:mousemove=update_hovered_problem
+                on:mouseleave=move |_| hovered_problem.set(None)
+                title=move || {
+                    hovered_problem
+                        .get()
+                        .or_else(|| jet_hint.get().map(|(hint, _)| hint))
+                        .unwrap_or_default()
+                }
                 node_ref=textarea_ref
                 name="program-input"
             >
                 {program.text.get_untracked()}
             </textarea>
+            {completion_list}
+            </div>
+            {move || desugared.get().map(|result| match result {
+                Ok(text) => view! {
+                    <textarea
+                        class="program-input-field desugared-view"
+                        rows="25"
+                        readonly
+                        spellcheck="false"
+                        prop:value=text
+                        name="desugared-program"
+                    ></textarea>
+                }.into_view(),
+                Err(error) => view! {
+                    <div class="desugared-view error-box"><pre>{error}</pre></div>
+                }.into_view(),
+            })}
+            {move || settings.show_whitespace().then(|| view! {
+                <textarea
+                    class="program-input-field whitespace-view"
+                    rows="25"
+                    readonly
+                    spellcheck="false"
+                    prop:value=move || program.text.with(|text| editor::visualize_whitespace(text))
+                    name="program-whitespace"
+                ></textarea>
+            })}
+            </div>
+            {move || jet_hint.get().map(|(hint, documentation)| view! {
+                <div class="jet-hint">
+                    {hint}
+                    {documentation.map(|url| view! {
+                        " "
+                        <a href=url target="_blank" rel="noopener noreferrer">"Docs"</a>
+                    })}
+                </div>
+            })}
+            {move || {
+                duplicates
+                    .get()
+                    .into_iter()
+                    .map(|warning| view! { <div class="source-warning">{warning}</div> })
+                    .collect_view()
+            }}
+            <LintPanel />
+            <OutlinePanel go_to_line=go_to_line />
+            <CompileTimingsReadout />
+            <ErrorExample />
+            <CompileStatus />
+            <ProgramCmr />
+            <ExpectedCmr />
         </div>
     }
 }
@@ -0,0 +1,303 @@
+use leptos::{
+    component, create_node_ref, create_rw_signal, ev, event_target_value, html, use_context, view,
+    CollectView, IntoView, RwSignal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalUpdateUntracked, SignalWith, SignalWithUntracked,
+};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::components::settings::Settings;
+use crate::components::state::LocalStorage;
+use crate::util::tabs::{self, new_tab_name};
+
+/// Programs that are open in the tabs above the editor, each under a name.
+///
+/// The editor holds the program of the active tab, so its stored text is only
+/// brought up to date when the tabs are stored or when the user leaves the tab.
+#[derive(Copy, Clone, Debug)]
+pub struct ProgramTabs {
+    /// Name and text of each tab, in the order of the tab strip.
+    pub tabs: RwSignal<Vec<(String, String)>>,
+    /// Index of the tab that is shown in the editor.
+    pub active: RwSignal<usize>,
+}
+
+impl ProgramTabs {
+    /// There is always at least one tab, so an empty list gets an empty tab.
+    pub fn new(mut tabs: Vec<(String, String)>, active: usize) -> Self {
+        if tabs.is_empty() {
+            tabs.push((new_tab_name(&tabs), String::new()));
+        }
+        let active = active.min(tabs.len() - 1);
+        Self {
+            tabs: create_rw_signal(tabs),
+            active: create_rw_signal(active),
+        }
+    }
+
+    /// Copy the text of `program` into the active tab.
+    ///
+    /// The tab strip only shows names, so it does not rerender for the new text.
+    pub fn sync(self, program: Program) {
+        program.flush_text();
+        let text = program.text.get_untracked();
+        let active = self.active.get_untracked();
+        self.tabs.update_untracked(|tabs| {
+            if let Some((_, tab_text)) = tabs.get_mut(active) {
+                *tab_text = text;
+            }
+        });
+    }
+
+    /// Store all tabs together with the program of the active tab.
+    pub fn store(self, program: Program) {
+        self.sync(program);
+        self.store_in_storage();
+        program.store_in_storage();
+    }
+
+    /// Open the program of a share link in a tab and make that tab active,
+    /// so the link does not replace the program of another tab.
+    ///
+    /// `stored_text` is the latest text of the active tab, if there is one in storage.
+    /// The program has its own tab, unless a tab already holds it,
+    /// such as after reloading the page of the link, or the active tab is blank.
+    pub fn open_shared(self, program: Program, stored_text: Option<String>) {
+        let active = self.active.get_untracked();
+        let text = program.text.get_untracked();
+        let mut index = active;
+        self.tabs.update(|tabs| {
+            if let (Some(stored_text), Some((_, tab_text))) = (stored_text, tabs.get_mut(active)) {
+                *tab_text = stored_text;
+            }
+            index = tabs::open_text(tabs, active, text);
+        });
+        self.active.set(index);
+    }
+
+    /// Show the program of the tab at `index` in the editor.
+    pub fn switch_to(self, program: Program, runtime: &Runtime, index: usize) {
+        if index == self.active.get_untracked() {
+            return;
+        }
+        let Some(text) = self
+            .tabs
+            .with_untracked(|tabs| tabs.get(index).map(|(_, text)| text.clone()))
+        else {
+            return;
+        };
+        self.sync(program);
+        self.active.set(index);
+        replace_program(program, runtime, text);
+        self.store(program);
+    }
+
+    /// Open a tab with a new program and show it in the editor.
+    ///
+    /// If `with_modules`, the program gets empty witness and parameter modules.
+    pub fn create(self, program: Program, runtime: &Runtime, with_modules: bool) {
+        self.sync(program);
+        self.tabs
+            .update(|tabs| tabs.push((new_tab_name(tabs), String::new())));
+        self.active.set(self.tabs.with_untracked(Vec::len) - 1);
+        runtime.forget_program();
+        program.reset_compilation();
+        program.start_new(with_modules);
+        self.store(program);
+    }
+
+    /// Close the tab at `index`.
+    ///
+    /// The last tab cannot be closed, so there is always a program to edit.
+    pub fn close(self, program: Program, runtime: &Runtime, index: usize) {
+        let count = self.tabs.with_untracked(Vec::len);
+        if count <= 1 || count <= index {
+            return;
+        }
+        // Pending edits belong to the active tab
+        program.flush_text();
+        self.tabs.update(|tabs| {
+            tabs.remove(index);
+        });
+        let active = self.active.get_untracked();
+        let next = tabs::active_after_close(active, index, count);
+        self.active.set(next);
+        if index == active {
+            let text = self.tabs.with_untracked(|tabs| tabs[next].1.clone());
+            replace_program(program, runtime, text);
+        }
+        self.store(program);
+    }
+
+    /// Rename the tab at `index`, unless the new name is blank.
+    pub fn rename(self, index: usize, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        self.tabs.update(|tabs| {
+            if let Some((tab_name, _)) = tabs.get_mut(index) {
+                *tab_name = name.to_string();
+            }
+        });
+        self.store_in_storage();
+    }
+}
+
+impl Default for ProgramTabs {
+    fn default() -> Self {
+        Self::new(vec![], 0)
+    }
+}
+
+/// Show `text` in the editor instead of the current program,
+/// such as the program of the tab that the user left.
+///
+/// The runtime and the compilation are reset, so nothing of the replaced program remains.
+pub fn replace_program(program: Program, runtime: &Runtime, text: String) {
+    runtime.forget_program();
+    program.reset_compilation();
+    program.text.set(text);
+}
+
+/// Ask the user whether the tab `name` with its program may be closed.
+fn confirm_close(name: &str) -> bool {
+    let message = format!("Closing `{name}` discards its program. Continue?");
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(&message).ok())
+        .unwrap_or(false)
+}
+
+/// Strip of tabs above the editor, to switch between programs and to open, close or rename them.
+///
+/// Double-click a tab to rename it.
+#[component]
+pub fn ProgramTabStrip() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let program_tabs = use_context::<ProgramTabs>().expect("program tabs should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let renaming = create_rw_signal(Option::<usize>::None);
+
+    let close = move |index: usize| {
+        let Some(name) = program_tabs
+            .tabs
+            .with_untracked(|tabs| tabs.get(index).map(|(name, _)| name.clone()))
+        else {
+            return;
+        };
+        let is_blank = match index == program_tabs.active.get_untracked() {
+            true => {
+                program.flush_text();
+                program.text.with_untracked(|text| text.trim().is_empty())
+            }
+            false => program_tabs
+                .tabs
+                .with_untracked(|tabs| tabs[index].1.trim().is_empty()),
+        };
+        if !is_blank && !confirm_close(&name) {
+            return;
+        }
+        renaming.set(None);
+        program_tabs.close(program, &runtime, index);
+    };
+    let can_close = move || program_tabs.tabs.with(|tabs| 1 < tabs.len());
+    let tabs = move || {
+        program_tabs.tabs.with(|tabs| {
+            tabs.iter()
+                .enumerate()
+                .map(|(index, (name, _))| {
+                    let name = name.clone();
+                    let label = move || match renaming.get() == Some(index) {
+                        true => view! {
+                            <TabNameInput index=index name=name.clone() renaming=renaming />
+                        }
+                        .into_view(),
+                        false => view! {
+                            <button
+                                class="program-tab-name"
+                                type="button"
+                                title="Double-click to rename"
+                                on:click=move |_| program_tabs.switch_to(program, &runtime, index)
+                                on:dblclick=move |_| renaming.set(Some(index))
+                            >
+                                {name.clone()}
+                            </button>
+                        }
+                        .into_view(),
+                    };
+                    view! {
+                        <div
+                            class="program-tab"
+                            class:active=move || program_tabs.active.get() == index
+                            role="tab"
+                        >
+                            {label}
+                            <button
+                                class="program-tab-close"
+                                class:hidden=move || !can_close()
+                                type="button"
+                                title="Close tab"
+                                on:click=move |_| close(index)
+                            >
+                                <i class="fas fa-times"></i>
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="program-tabs" role="tablist">
+            {tabs}
+            <button
+                class="program-tab-new"
+                type="button"
+                title="Open a new program in a tab"
+                on:click=move |_| program_tabs.create(program, &runtime, settings.default_modules())
+            >
+                <i class="fas fa-plus"></i>
+            </button>
+        </div>
+    }
+}
+
+/// Text field that renames the tab at `index` when Enter is pressed or when it loses the focus.
+///
+/// Escape keeps the old name.
+#[component]
+fn TabNameInput(index: usize, name: String, renaming: RwSignal<Option<usize>>) -> impl IntoView {
+    let program_tabs = use_context::<ProgramTabs>().expect("program tabs should exist in context");
+    let input_ref = create_node_ref::<html::Input>();
+    input_ref.on_load(|input| {
+        let _ = input.focus();
+        input.select();
+    });
+    let handle_keydown = move |event: ev::KeyboardEvent| match event.key().as_str() {
+        "Escape" => renaming.set(None),
+        "Enter" => {
+            if let Some(input) = input_ref.get_untracked() {
+                let _ = input.blur();
+            }
+        }
+        _ => {}
+    };
+
+    view! {
+        <input
+            class="input program-tab-rename"
+            type="text"
+            value=name
+            node_ref=input_ref
+            aria-label="Tab name"
+            on:keydown=handle_keydown
+            on:blur=move |event: ev::FocusEvent| {
+                if renaming.get_untracked() == Some(index) {
+                    program_tabs.rename(index, &event_target_value(&event));
+                    renaming.set(None);
+                }
+            }
+        />
+    }
+}
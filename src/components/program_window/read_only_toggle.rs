@@ -0,0 +1,27 @@
+use leptos::{component, use_context, view, IntoView, SignalGet, SignalUpdate};
+
+use crate::components::program_window::ReadOnly;
+
+/// Button to lock and unlock the program editor.
+#[component]
+pub fn ReadOnlyToggle() -> impl IntoView {
+    let read_only = use_context::<ReadOnly>().expect("read-only setting should exist in context");
+
+    view! {
+        <button
+            class="copy-button"
+            class:active=read_only.0
+            type="button"
+            title=move || match read_only.0.get() {
+                true => "Unlock the editor",
+                false => "Lock the editor against edits",
+            }
+            on:click=move |_| read_only.0.update(|locked| *locked = !*locked)
+        >
+            <i class=move || match read_only.0.get() {
+                true => "fas fa-lock",
+                false => "fas fa-lock-open",
+            }></i>
+        </button>
+    }
+}
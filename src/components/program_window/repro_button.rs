@@ -0,0 +1,29 @@
+use leptos::{component, use_context, view, IntoView, SignalWithUntracked};
+
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::Program;
+use crate::components::run_window::TxEnv;
+use crate::url_sharing;
+
+/// Copy a link that reproduces the program, its witness and parameters,
+/// and the transaction environment, for example for a bug report.
+#[component]
+pub fn ReproButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+
+    let repro_url = move || {
+        program.text.with_untracked(|text| {
+            tx_env.params.with_untracked(|params| {
+                url_sharing::build_repro_url(text, params)
+                    .unwrap_or_else(|| "Empty program".to_string())
+            })
+        })
+    };
+
+    view! {
+        <CopyToClipboard content=repro_url class="button" tooltip_below=true deferred=true>
+            " Repro"
+        </CopyToClipboard>
+    }
+}
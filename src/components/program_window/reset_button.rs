@@ -0,0 +1,63 @@
+use leptos::{component, use_context, view, IntoView, SignalSet, SignalWithUntracked};
+
+use crate::components::program_window::{select_example, Program};
+use crate::components::run_window::TxEnv;
+use crate::components::state::LocalStorage;
+use crate::examples;
+use crate::transaction::TxParams;
+
+/// Program and transaction environment as they were decoded from a shared link.
+///
+/// Links without a transaction environment leave the environment unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct SharedState(pub Option<(String, Option<TxParams>)>);
+
+/// Ask the user to confirm that the program may be replaced.
+fn confirm(message: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(message).ok())
+        .unwrap_or(false)
+}
+
+/// Restore the program and environment of the shared link from which the IDE was loaded.
+///
+/// Without a shared link, restore the starter program and forget the stored program.
+#[component]
+pub fn ResetButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let shared = use_context::<SharedState>().expect("shared state should exist in context");
+    let Some((shared_text, shared_params)) = shared.0 else {
+        let reset = move |_| {
+            if !confirm("Discard your program and restore the starter program?") {
+                return;
+            }
+            Program::remove_from_storage();
+            select_example(examples::get(examples::STARTER).expect("starter example should exist"));
+        };
+        return view! {
+            <button class="button" type="button" on:click=reset title="Restore the starter program">
+                " Reset"
+            </button>
+        }
+        .into_view();
+    };
+
+    let reset = move |_| {
+        let edited = program.text.with_untracked(|text| *text != shared_text);
+        if edited && !confirm("Discard your edits and restore the shared program?") {
+            return;
+        }
+        program.text.set(shared_text.clone());
+        if let Some(params) = &shared_params {
+            tx_env.params.set(params.clone());
+        }
+    };
+
+    view! {
+        <button class="button" type="button" on:click=reset title="Restore the shared program">
+            " Reset"
+        </button>
+    }
+    .into_view()
+}
@@ -1,7 +1,10 @@
-use leptos::{component, ev, use_context, view, IntoView, SignalGet, SignalSet};
+use leptos::{
+    component, ev, use_context, view, IntoView, SignalGet, SignalGetUntracked, SignalSet,
+};
 
 use crate::components::app::ActiveProgramView;
 use crate::components::program_window::{Program, Runtime};
+use crate::components::settings::Settings;
 use crate::components::state::update_local_storage;
 
 #[component]
@@ -9,12 +12,18 @@ pub fn RunButton() -> impl IntoView {
     let program = use_context::<Program>().expect("program should exist in context");
     let runtime = use_context::<Runtime>().expect("runtime should exist in context");
     let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
 
     let run_program = move |_event: ev::MouseEvent| {
+        if runtime.running.get_untracked() {
+            return runtime.cancel();
+        }
         // Switch back to code editor view
         active_view.0.set("Run");
 
-        program.add_default_modules();
+        if settings.default_modules() {
+            program.add_default_modules();
+        }
         update_local_storage();
         runtime.run();
     };
@@ -24,13 +33,18 @@ pub fn RunButton() -> impl IntoView {
         Some(true) => "button run-button success",
     };
 
+    let label = move || match runtime.running.get() {
+        false => view! { <i class="fas fa-play"></i>" Run" },
+        true => view! { <i class="fas fa-times"></i>" Cancel" },
+    };
+
     view! {
         <button
             class=button_class
+            title=move || runtime.running.get().then_some("Cancel the run and discard its output")
             on:click=run_program
         >
-            <i class="fas fa-play"></i>
-            " Run"
+            {label}
         </button>
     }
 }
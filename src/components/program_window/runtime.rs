@@ -0,0 +1,1126 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use leptos::{
+    spawn_local, store_value, use_context, ReadSignal, RwSignal, Signal, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, StoredValue,
+};
+use simplicityhl::simplicity::jet::elements::ElementsEnv;
+use simplicityhl::SatisfiedProgram;
+use simplicityhl::{elements, simplicity};
+use web_sys::MessageEvent;
+
+use crate::components::program_window::Program;
+use crate::components::settings::Settings;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::function::{
+    ErrorKind, ExecutionTrace, Frames, Introspection, Progress, ResourceSummary, Runner,
+};
+use crate::pipeline::{load_satisfied, load_text, ProgramError, RunOutcome};
+use crate::transaction::TxParams;
+use crate::util::{source, Expression};
+use crate::worker::{BatchRequest, BatchResponse, RunReport};
+
+/// Position at which the execution of a program was paused.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pause {
+    /// Line of the call that is about to be executed.
+    pub line: usize,
+    /// Text of the call.
+    pub call: String,
+    /// Simplicity input value of the call.
+    pub input: String,
+}
+
+/// Line at which runs pause, if the program reaches a call on that line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakpoint {
+    pub line: usize,
+    /// Simplicity input value at which the call pauses.
+    ///
+    /// Calls pause at every input if the condition is empty.
+    pub condition: String,
+}
+
+impl Breakpoint {
+    fn is_hit(&self, lines: &[usize], input: &simplicity::Value) -> bool {
+        let condition = self.condition.trim();
+        lines.contains(&self.line) && (condition.is_empty() || input.to_string() == condition)
+    }
+}
+
+/// Number of steps that a run executes before it lets the browser render the page.
+const STEPS_PER_PORTION: usize = 100_000;
+/// Number of single steps that the debugger can go back.
+const MAX_STEP_HISTORY: usize = 256;
+
+/// Script of the Web Worker that runs batches of programs, which Trunk builds next to the page.
+const BATCH_WORKER_SCRIPT: &str = "./worker.js";
+/// Milliseconds between the checks whether the worker finished its batch.
+const WORKER_POLL_MS: u32 = 20;
+
+/// State of the Web Worker that runs batches of programs.
+#[derive(Clone, Debug)]
+enum BatchWorker {
+    /// No worker is idle, because none was started yet or because a batch uses it.
+    NoneIdle,
+    /// The worker finished its last batch and waits for the next one.
+    Idle(web_sys::Worker),
+    /// The browser cannot run the worker, so batches run on the page.
+    Unavailable,
+}
+
+/// Maximum number of runs that are kept in the history.
+const MAX_RUN_HISTORY: usize = 20;
+
+/// Outcome of a past run of the program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    /// Number of the run in this session, which stays the same while newer runs push it down.
+    pub id: usize,
+    /// Time at which the run finished, in ISO format.
+    pub timestamp: String,
+    pub success: bool,
+    /// Wall-clock time from starting the run until it finished, in milliseconds.
+    pub elapsed_ms: f64,
+    /// CMR of the program, if it compiled.
+    pub cmr: Option<simplicity::Cmr>,
+    pub error_output: String,
+    pub debug_output: String,
+}
+
+#[derive(Copy, Clone)]
+pub struct Runtime {
+    program: Program,
+    env: Signal<ElementsEnv<Arc<elements::Transaction>>>,
+    /// Default transaction environment, for runs that ignore the edits of the environment.
+    fresh_env: Signal<ElementsEnv<Arc<elements::Transaction>>>,
+    /// Environment of the current execution, which continued executions keep using.
+    run_env: StoredValue<Signal<ElementsEnv<Arc<elements::Transaction>>>>,
+    pub run_succeeded: RwSignal<Option<bool>>,
+    pub debug_output: RwSignal<String>,
+    /// Debug output with the values as compact bits instead of `SimplicityHL` values.
+    pub debug_output_bits: RwSignal<String>,
+    pub error_output: RwSignal<Option<ProgramError>>,
+    pub warning_output: RwSignal<String>,
+    /// Whether the next runs record an execution trace.
+    pub trace_enabled: RwSignal<bool>,
+    /// Execution trace of the last run, if tracing was enabled.
+    pub trace: RwSignal<Option<Arc<ExecutionTrace>>>,
+    /// Number of calls of each jet during the last run.
+    pub jet_calls: RwSignal<Vec<(simplicity::jet::Elements, usize)>>,
+    /// What the jets read from the transaction environment during the last run.
+    pub introspections: RwSignal<Vec<Introspection>>,
+    /// Resources of the Bit Machine that the program of the last run uses.
+    pub resources: RwSignal<Option<ResourceSummary>>,
+    /// Position of the call that made the last run fail, if it was found in the program text.
+    pub failed_call: RwSignal<Option<Range<usize>>>,
+    /// Position at which the current execution is paused.
+    pub paused_at: RwSignal<Option<Pause>>,
+    /// Number of executed steps, if the current execution stopped at the step limit.
+    pub step_limit_reached: RwSignal<Option<usize>>,
+    /// Whether a run is in progress.
+    pub running: RwSignal<bool>,
+    /// Reason why the current execution was interrupted, such as a timeout.
+    pub interrupted: RwSignal<Option<String>>,
+    /// Number of the latest run, so that replaced runs stop.
+    run_generation: StoredValue<usize>,
+    stop_requested: StoredValue<bool>,
+    /// Web Worker that runs batches of programs.
+    batch_worker: StoredValue<BatchWorker>,
+    /// Breakpoints of the program, ordered by line.
+    pub breakpoints: RwSignal<Vec<Breakpoint>>,
+    /// Runner of the paused execution.
+    paused_runner: StoredValue<Option<Runner>>,
+    /// Runner of the last execution before its first step, to restart the execution.
+    initial_runner: StoredValue<Option<Runner>>,
+    /// Runners of the paused execution before each of its last single steps, oldest first.
+    step_history: StoredValue<VecDeque<Runner>>,
+    /// Whether the paused execution can go back to the state before its last single step.
+    pub can_step_back: RwSignal<bool>,
+    /// Frames of the paused execution.
+    pub frames: RwSignal<Option<Frames>>,
+    /// Past runs of this session, newest first.
+    pub history: RwSignal<Vec<RunRecord>>,
+    /// Time at which the current run started, in milliseconds since the epoch.
+    run_started: StoredValue<f64>,
+    program_expr: RwSignal<Option<Arc<Expression>>>,
+    toasts: Toasts,
+    settings: Settings,
+}
+
+impl Runtime {
+    pub fn new(program: Program, env: Signal<ElementsEnv<Arc<elements::Transaction>>>) -> Self {
+        let fresh_env = Signal::derive(move || {
+            program.lazy_compiled.with(|compiled| {
+                let cmr = compiled.cmr.clone().unwrap_or(simplicity::Cmr::unit());
+                TxParams::default().tx_env(cmr)
+            })
+        });
+        Self {
+            program,
+            env,
+            fresh_env,
+            run_env: store_value(env),
+            run_succeeded: RwSignal::default(),
+            debug_output: RwSignal::default(),
+            debug_output_bits: RwSignal::default(),
+            error_output: RwSignal::default(),
+            warning_output: RwSignal::default(),
+            trace_enabled: RwSignal::default(),
+            trace: RwSignal::default(),
+            jet_calls: RwSignal::default(),
+            introspections: RwSignal::default(),
+            resources: RwSignal::default(),
+            failed_call: RwSignal::default(),
+            paused_at: RwSignal::default(),
+            step_limit_reached: RwSignal::default(),
+            running: RwSignal::default(),
+            interrupted: RwSignal::default(),
+            run_generation: store_value(0),
+            stop_requested: store_value(false),
+            batch_worker: store_value(BatchWorker::NoneIdle),
+            breakpoints: RwSignal::default(),
+            paused_runner: store_value(None),
+            initial_runner: store_value(None),
+            step_history: store_value(VecDeque::new()),
+            can_step_back: RwSignal::default(),
+            frames: RwSignal::default(),
+            history: RwSignal::default(),
+            run_started: store_value(0.0),
+            program_expr: RwSignal::default(),
+            toasts: use_context::<Toasts>().expect("toasts should exist in context"),
+            settings: use_context::<Settings>().expect("settings should exist in context"),
+        }
+    }
+
+    pub fn program_expr(&self) -> ReadSignal<Option<Arc<Expression>>> {
+        self.program_expr.read_only()
+    }
+
+    fn set_success(self, success: bool) {
+        self.record_run(success);
+        match success {
+            true => self.toasts.push(ToastKind::Success, "Program succeeded"),
+            false => self.toasts.push(ToastKind::Error, "Program failed"),
+        }
+        spawn_local(async move {
+            self.run_succeeded.set(Some(success));
+            gloo_timers::future::TimeoutFuture::new(500).await;
+            self.run_succeeded.set(None);
+        });
+        if !self
+            .settings
+            .0
+            .with_untracked(|preferences| preferences.haptics)
+        {
+            return;
+        }
+        web_sys::window()
+            .as_ref()
+            .map(web_sys::Window::navigator)
+            .map(|navigator| match success {
+                true => navigator.vibrate_with_duration(200),
+                false => navigator.vibrate_with_duration(500),
+            });
+    }
+
+    fn record_run(self, success: bool) {
+        let id = self
+            .history
+            .with_untracked(|history| history.first().map_or(0, |newest| newest.id + 1));
+        let record = RunRecord {
+            id,
+            timestamp: js_sys::Date::new_0()
+                .to_iso_string()
+                .as_string()
+                .unwrap_or_default(),
+            success,
+            elapsed_ms: js_sys::Date::now() - self.run_started.get_value(),
+            cmr: self
+                .program
+                .lazy_compiled
+                .with_untracked(|compiled| compiled.cmr.clone().ok()),
+            error_output: self.error_output.with_untracked(|error| {
+                error.as_ref().map(ToString::to_string).unwrap_or_default()
+            }),
+            debug_output: self.debug_output.get_untracked(),
+        };
+        self.history.update(|history| {
+            history.insert(0, record);
+            history.truncate(MAX_RUN_HISTORY);
+        });
+    }
+
+    fn reset_output(self) {
+        self.run_started.set_value(js_sys::Date::now());
+        self.debug_output.update(String::clear);
+        self.debug_output_bits.update(String::clear);
+        self.warning_output.update(String::clear);
+        self.failed_call.set(None);
+        self.resources.set(None);
+        self.paused_at.set(None);
+        self.step_limit_reached.set(None);
+        self.interrupted.set(None);
+        self.stop_requested.set_value(false);
+        self.paused_runner.set_value(None);
+        self.clear_step_history();
+        self.frames.set(None);
+        self.run_env.set_value(
+            match self
+                .settings
+                .0
+                .with_untracked(|preferences| preferences.fresh_environment)
+            {
+                true => self.fresh_env,
+                false => self.env,
+            },
+        );
+        // Stop any run that is still in progress
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        self.running.set(false);
+    }
+
+    /// Discard the runs and the breakpoints of the program, because another program replaces it.
+    ///
+    /// A run in progress is abandoned, and a paused execution can no longer be continued,
+    /// stepped or restarted.
+    pub fn forget_program(self) {
+        self.reset_output();
+        self.initial_runner.set_value(None);
+        self.breakpoints.update(Vec::clear);
+        self.error_output.set(None);
+        self.run_succeeded.set(None);
+        self.program_expr.set(None);
+        self.trace.set(None);
+        self.jet_calls.update(Vec::clear);
+        self.introspections.update(Vec::clear);
+    }
+
+    /// Run the expression `selection` of the program in a harness
+    /// that asserts that the expression is true.
+    ///
+    /// The harness is compiled on its own, so it runs in the transaction environment `params`
+    /// with its own CMR.
+    /// Like other runs, it runs in portions of steps and can be stopped.
+    pub fn run_selection(self, selection: &str, params: &TxParams) {
+        self.reset_output();
+        self.program.sync_editor();
+        let harness = self
+            .program
+            .text
+            .with_untracked(|text| source::selection_harness(text, selection));
+        let fresh = self
+            .settings
+            .0
+            .with_untracked(|preferences| preferences.fresh_environment);
+        let params = match fresh {
+            true => TxParams::default(),
+            false => params.clone(),
+        };
+        self.program_expr.set(None);
+        self.trace.set(None);
+        self.jet_calls.update(Vec::clear);
+        self.introspections.update(Vec::clear);
+        self.warning_output.set(format!(
+            "This output is of the selection `{}`, not of the entire program.",
+            selection.trim()
+        ));
+        let debug_symbols = self.program.includes_debug_symbols();
+        let (mut runner, env) = match load_text(&harness, &params, debug_symbols) {
+            Ok(x) => x,
+            Err(error) => return self.finish_selection(Err(error)),
+        };
+        runner.limit_steps(self.settings.step_limit());
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        let generation = self.run_generation.get_value();
+        self.running.set(true);
+        let started = js_sys::Date::now();
+        let selection = selection.trim().to_string();
+
+        spawn_local(async move {
+            let deadline = self.deadline(started);
+            let run_portion = |runner: &mut Runner| {
+                runner.run_steps(&env, STEPS_PER_PORTION, false, |_, _| false)
+            };
+            let result = self
+                .run_portions(&mut runner, run_portion, generation, started, deadline)
+                .await;
+            self.show_debug_output(&runner);
+            match result {
+                Ok(result) => {
+                    self.running.set(false);
+                    self.finish_selection(result.map_err(ProgramError::from));
+                }
+                Err(Some(reason)) => {
+                    // The harness is not the program, so its run cannot be continued
+                    self.running.set(false);
+                    self.warning_output.set(format!(
+                        "The run of the selection `{selection}` was interrupted: {reason} ({} steps).",
+                        runner.executed_steps()
+                    ));
+                }
+                Err(None) => {}
+            }
+        });
+    }
+
+    fn finish_selection(self, result: Result<(), ProgramError>) {
+        match result {
+            Ok(()) => {
+                self.error_output.set(None);
+                self.toasts.push(ToastKind::Success, "Selection is true");
+            }
+            Err(error) => {
+                self.error_output.set(Some(error));
+                self.toasts.push(ToastKind::Error, "Selection failed");
+            }
+        }
+    }
+
+    fn fail_to_start(self, error: ProgramError) {
+        self.error_output.set(Some(error));
+        self.program_expr.set(None);
+        self.trace.set(None);
+        self.jet_calls.update(Vec::clear);
+        self.introspections.update(Vec::clear);
+        self.set_success(false);
+    }
+
+    fn start_runner(self, satisfied_program: &SatisfiedProgram) -> Result<Runner, ProgramError> {
+        let mut runner = load_satisfied(satisfied_program)?;
+        // Store the program expression for analysis
+        self.program_expr
+            .set(Some(satisfied_program.redeem().clone()));
+        runner.limit_steps(self.settings.step_limit());
+        if self.trace_enabled.get_untracked() {
+            runner.enable_trace();
+        }
+        self.initial_runner.set_value(Some(runner.clone()));
+        Ok(runner)
+    }
+
+    /// Add a breakpoint on the given line, or remove the breakpoint that is already there.
+    pub fn toggle_breakpoint(self, line: usize) {
+        self.breakpoints.update(|breakpoints| {
+            match breakpoints.iter().position(|b| b.line == line) {
+                Some(index) => {
+                    breakpoints.remove(index);
+                }
+                None => {
+                    breakpoints.push(Breakpoint {
+                        line,
+                        condition: String::new(),
+                    });
+                    breakpoints.sort_by_key(|b| b.line);
+                }
+            }
+        });
+    }
+
+    /// Run the program until the end or until it reaches a breakpoint.
+    ///
+    /// Breakpoints need debug symbols, which change the CMR of the program.
+    pub fn run(self) {
+        self.reset_output();
+        // Never run a program that differs from what the editor shows
+        self.program.sync_editor();
+        // Running is an explicit action, so compile even without automatic compilation
+        self.program.compile();
+        let satisfied_program = match self.breakpoints.with_untracked(Vec::is_empty) {
+            true => self.program.satisfied(),
+            false => self.program.satisfied_with_debug_symbols(),
+        };
+        let satisfied_program = match satisfied_program {
+            Ok(x) => x,
+            Err(error) => return self.fail_to_start(error),
+        };
+        match self.start_runner(&satisfied_program) {
+            Ok(runner) => self.resume(runner, None),
+            Err(error) => self.fail_to_start(error),
+        }
+    }
+
+    /// Run the program until it is about to execute a call on the given line.
+    ///
+    /// Only calls have debug symbols, such as assertions, jets, unwraps and `dbg!`.
+    /// The program is compiled with debug symbols, which changes its CMR.
+    pub fn run_to_line(self, line: usize) {
+        self.reset_output();
+        self.program.sync_editor();
+        let satisfied_program = match self.program.satisfied_with_debug_symbols() {
+            Ok(x) => x,
+            Err(error) => return self.fail_to_start(error),
+        };
+        match self.start_runner(&satisfied_program) {
+            Ok(runner) => self.resume(runner, Some(line)),
+            Err(error) => self.fail_to_start(error),
+        }
+    }
+
+    /// Continue the paused execution until the end of the program or the next breakpoint.
+    ///
+    /// An execution that stopped at the step limit may run for as many steps again.
+    pub fn continue_run(self) {
+        let Some(mut runner) = self.paused_runner.try_update_value(Option::take).flatten() else {
+            return;
+        };
+        if self.step_limit_reached.get_untracked().is_some() {
+            runner.limit_steps(self.settings.step_limit());
+        }
+        self.paused_at.set(None);
+        self.step_limit_reached.set(None);
+        self.interrupted.set(None);
+        self.stop_requested.set_value(false);
+        self.frames.set(None);
+        self.clear_step_history();
+        self.resume(runner, None);
+    }
+
+    /// Execute a single step of the paused execution and show the new frames.
+    ///
+    /// The execution stays paused, unless the step finishes the program or fails.
+    pub fn step_once(self) {
+        let Some(mut runner) = self.paused_runner.try_update_value(Option::take).flatten() else {
+            return;
+        };
+        let snapshot = runner.clone();
+        if self.step_limit_reached.get_untracked().is_some() {
+            // Allow one step beyond the limit; continuing resets the limit
+            runner.limit_steps(Some(1));
+        }
+        let result = self
+            .run_env
+            .get_value()
+            .with_untracked(|env| runner.step(env));
+        match result {
+            Ok(()) if !runner.is_finished() => {
+                self.step_history.update_value(|history| {
+                    if history.len() == MAX_STEP_HISTORY {
+                        history.pop_front();
+                    }
+                    history.push_back(snapshot);
+                });
+                self.can_step_back.set(true);
+                self.show_step(runner);
+            }
+            Ok(()) => {
+                self.clear_pause();
+                self.finish(&mut runner, Ok(()));
+            }
+            Err(ErrorKind::StepLimitReached) => {
+                self.paused_runner.set_value(Some(runner));
+            }
+            Err(error) => {
+                self.clear_pause();
+                self.finish(&mut runner, Err(error));
+            }
+        }
+    }
+
+    /// Pause the last execution before its first step, to step through it from the start.
+    ///
+    /// The program is neither compiled nor satisfied again,
+    /// so the execution is that of the program as it was when the run started.
+    pub fn restart(self) {
+        let Some(runner) = self.initial_runner.get_value() else {
+            return;
+        };
+        // A portion of the run that is still pending sees the new generation and returns
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        self.running.set(false);
+        self.clear_step_history();
+        self.step_limit_reached.set(None);
+        self.interrupted.set(None);
+        self.reset_output();
+        self.error_output.set(None);
+        self.run_succeeded.set(None);
+        self.show_step(runner);
+    }
+
+    /// Return the paused execution to its state before the last single step.
+    ///
+    /// Only the last `MAX_STEP_HISTORY` steps are remembered.
+    pub fn step_back(self) {
+        let Some(runner) = self
+            .step_history
+            .try_update_value(VecDeque::pop_back)
+            .flatten()
+        else {
+            return;
+        };
+        let can_step_back = self.step_history.with_value(|history| !history.is_empty());
+        self.can_step_back.set(can_step_back);
+        self.show_step(runner);
+    }
+
+    /// Show the frames and the next call of the paused execution after a single step.
+    fn show_step(self, runner: Runner) {
+        if let Some((call, input)) = runner.next_call() {
+            let line = self
+                .program
+                .text
+                .with_untracked(|text| source::call_lines(text, call.text()).first().copied());
+            if let Some(line) = line {
+                self.paused_at.set(Some(Pause {
+                    line,
+                    call: call.text().to_string(),
+                    input: input.to_string(),
+                }));
+            }
+        }
+        self.frames.set(Some(runner.frames()));
+        self.show_debug_output(&runner);
+        self.paused_runner.set_value(Some(runner));
+    }
+
+    /// Forget the states to which the paused execution can step back.
+    fn clear_step_history(self) {
+        self.step_history.update_value(VecDeque::clear);
+        self.can_step_back.set(false);
+    }
+
+    /// Forget where the execution was paused.
+    fn clear_pause(self) {
+        self.clear_step_history();
+        self.paused_at.set(None);
+        self.step_limit_reached.set(None);
+        self.interrupted.set(None);
+        self.frames.set(None);
+    }
+
+    /// Run until the end, a pause or the timeout, in portions between which the browser
+    /// renders the page and handles input.
+    fn resume(self, mut runner: Runner, pause_line: Option<usize>) {
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        let generation = self.run_generation.get_value();
+        self.running.set(true);
+        let text = self.program.text.get_untracked();
+        let breakpoints = self.breakpoints.get_untracked();
+        let pausing = pause_line.is_some() || !breakpoints.is_empty();
+        let started = js_sys::Date::now();
+        let deadline = self
+            .settings
+            .run_timeout()
+            .map(|seconds| started + f64::from(seconds) * 1000.0);
+
+        spawn_local(async move {
+            let mut reached_line = None;
+            let mut force_first = true;
+            let result = loop {
+                let progress = self.run_env.get_value().with_untracked(|env| {
+                    runner.run_steps(env, STEPS_PER_PORTION, force_first, |call, input| {
+                        if !pausing {
+                            return false;
+                        }
+                        let lines = source::call_lines(&text, call.text());
+                        reached_line =
+                            pause_line.filter(|line| lines.contains(line)).or_else(|| {
+                                breakpoints
+                                    .iter()
+                                    .find(|b| b.is_hit(&lines, input))
+                                    .map(|b| b.line)
+                            });
+                        reached_line.is_some()
+                    })
+                });
+                force_first = false;
+                if !matches!(progress, Ok(Progress::Unfinished)) {
+                    break progress;
+                }
+                if deadline.is_some_and(|deadline| deadline <= js_sys::Date::now()) {
+                    let seconds = (js_sys::Date::now() - started) / 1000.0;
+                    return self.interrupt(runner, format!("Timed out after {seconds:.1} s"));
+                }
+                gloo_timers::future::TimeoutFuture::new(0).await;
+                if self.run_generation.get_value() != generation {
+                    // A new run replaced this one
+                    return;
+                }
+                if self.stop_requested.get_value() {
+                    return self.interrupt(runner, "Stopped".to_string());
+                }
+            };
+            self.running.set(false);
+            match (result, reached_line) {
+                (Ok(Progress::Paused), Some(line)) => self.pause(runner, line),
+                (Ok(..), _) => {
+                    if let Some(line) = pause_line {
+                        self.warning_output.set(format!(
+                            "Warning: The execution did not reach a call on line {line}."
+                        ));
+                    }
+                    self.finish(&mut runner, Ok(()));
+                }
+                (Err(ErrorKind::StepLimitReached), _) => self.stop_at_limit(runner),
+                (Err(error), _) => self.finish(&mut runner, Err(error)),
+            }
+        });
+    }
+
+    /// Run each program with its transaction environment, one after another,
+    /// and pass their reports to `done` in the same order.
+    ///
+    /// The batch runs in a Web Worker, so the page stays responsive.
+    /// If the browser cannot run the worker, the batch runs on the page instead
+    /// and yields to the browser between portions of steps, like other runs.
+    /// The batch uses the step limit and the timeout of the settings.
+    /// A timeout or a stop ends the batch and passes the reason to `done`.
+    /// A cancelled batch, or one that another run replaced, never calls `done`.
+    pub fn run_batch<F>(self, runs: Vec<(String, TxParams)>, done: F)
+    where
+        F: FnOnce(Result<Vec<RunReport>, String>) + 'static,
+    {
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        let generation = self.run_generation.get_value();
+        self.running.set(true);
+        self.stop_requested.set_value(false);
+        let started = js_sys::Date::now();
+
+        spawn_local(async move {
+            let reports = match self.take_batch_worker() {
+                Some(worker) => {
+                    match self
+                        .run_batch_in_worker(&worker, &runs, generation, started)
+                        .await
+                    {
+                        Some(reports) => reports,
+                        None => {
+                            // The worker failed, so this and later batches run on the page
+                            worker.terminate();
+                            self.batch_worker.set_value(BatchWorker::Unavailable);
+                            self.run_batch_portions(runs, generation, started).await
+                        }
+                    }
+                }
+                None => self.run_batch_portions(runs, generation, started).await,
+            };
+            match reports {
+                Ok(reports) => {
+                    self.running.set(false);
+                    done(Ok(reports));
+                }
+                Err(Some(reason)) => {
+                    self.running.set(false);
+                    done(Err(reason));
+                }
+                Err(None) => {}
+            }
+        });
+    }
+
+    /// Take the idle worker that runs batches, or start a new one.
+    ///
+    /// A batch keeps its worker until it finishes, so a batch that replaces
+    /// a pending batch gets a worker of its own.
+    /// Return `None` if the browser cannot start the worker.
+    fn take_batch_worker(self) -> Option<web_sys::Worker> {
+        match self.batch_worker.get_value() {
+            BatchWorker::Idle(worker) => {
+                self.batch_worker.set_value(BatchWorker::NoneIdle);
+                Some(worker)
+            }
+            BatchWorker::NoneIdle => {
+                let worker = web_sys::Worker::new(BATCH_WORKER_SCRIPT).ok();
+                if worker.is_none() {
+                    self.batch_worker.set_value(BatchWorker::Unavailable);
+                }
+                worker
+            }
+            BatchWorker::Unavailable => None,
+        }
+    }
+
+    /// Keep the worker of a finished batch for the next batch,
+    /// unless another worker is already idle.
+    fn return_batch_worker(self, worker: web_sys::Worker) {
+        match self.batch_worker.get_value() {
+            BatchWorker::NoneIdle => self.batch_worker.set_value(BatchWorker::Idle(worker)),
+            BatchWorker::Idle(..) | BatchWorker::Unavailable => worker.terminate(),
+        }
+    }
+
+    /// Let the worker run the programs of a batch and wait for its response.
+    ///
+    /// The worker is terminated if the batch is stopped, cancelled or times out,
+    /// because it cannot be interrupted otherwise.
+    /// Return `None` if the worker failed.
+    async fn run_batch_in_worker(
+        self,
+        worker: &web_sys::Worker,
+        runs: &[(String, TxParams)],
+        generation: usize,
+        started: f64,
+    ) -> Option<Result<Vec<RunReport>, Option<String>>> {
+        let request = BatchRequest::new(
+            generation,
+            runs,
+            self.settings.step_limit(),
+            self.program.includes_debug_symbols(),
+        );
+        let request = serde_json::to_string(&request).expect("requests can be serialized");
+        let deadline = self.deadline(started);
+        // Response of the worker, or `None` if the worker failed
+        let reply = Rc::new(RefCell::new(Option::<Option<String>>::None));
+        let on_message = {
+            let reply = reply.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                *reply.borrow_mut() = Some(event.data().as_string());
+            })
+        };
+        let on_error = {
+            let reply = reply.clone();
+            Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+                *reply.borrow_mut() = Some(None);
+            })
+        };
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        if worker.post_message(&JsValue::from_str(&request)).is_err() {
+            *reply.borrow_mut() = Some(None);
+        }
+
+        let response = loop {
+            if let Some(response) = reply.borrow_mut().take() {
+                break response;
+            }
+            if let Err(reason) = self.check_run(generation, started, deadline) {
+                worker.terminate();
+                return Some(Err(reason));
+            }
+            gloo_timers::future::TimeoutFuture::new(WORKER_POLL_MS).await;
+        };
+        // The closures are dropped at the end of this function
+        worker.set_onmessage(None);
+        worker.set_onerror(None);
+        let response: BatchResponse = serde_json::from_str(&response?).ok()?;
+        if response.id != generation || response.reports.len() != runs.len() {
+            return None;
+        }
+        self.return_batch_worker(worker.clone());
+        Some(Ok(response.reports))
+    }
+
+    /// Return the time at which a run that started at `started` times out, if it does.
+    fn deadline(self, started: f64) -> Option<f64> {
+        self.settings
+            .run_timeout()
+            .map(|seconds| started + f64::from(seconds) * 1000.0)
+    }
+
+    /// Run the programs of a batch on the page, in portions of steps.
+    ///
+    /// Return the reason why the batch was interrupted,
+    /// or no reason if another run replaced it.
+    async fn run_batch_portions(
+        self,
+        runs: Vec<(String, TxParams)>,
+        generation: usize,
+        started: f64,
+    ) -> Result<Vec<RunReport>, Option<String>> {
+        let step_limit = self.settings.step_limit();
+        let debug_symbols = self.program.includes_debug_symbols();
+        let deadline = self.deadline(started);
+        let mut reports = Vec::with_capacity(runs.len());
+        for (text, params) in runs {
+            let outcome = match load_text(&text, &params, debug_symbols) {
+                Ok((mut runner, env)) => {
+                    runner.limit_steps(step_limit);
+                    let run_portion = |runner: &mut Runner| {
+                        runner.run_steps(&env, STEPS_PER_PORTION, false, |_, _| false)
+                    };
+                    let result = self
+                        .run_portions(&mut runner, run_portion, generation, started, deadline)
+                        .await?;
+                    RunOutcome::of_run(&runner, result)
+                }
+                Err(error) => RunOutcome::failed(error),
+            };
+            reports.push(RunReport::from(outcome));
+            self.yield_in_run(generation, started, deadline).await?;
+        }
+        Ok(reports)
+    }
+
+    /// Run the runner to the end on the page, in portions of steps,
+    /// for runs that are neither paused nor continued.
+    ///
+    /// `run_portion` lets the runner run a portion of steps in the environment of the run.
+    /// Such runs are aborted at the step limit, because they cannot be continued.
+    /// Return the reason why the run was interrupted,
+    /// or no reason if another run replaced it.
+    async fn run_portions<F>(
+        self,
+        runner: &mut Runner,
+        mut run_portion: F,
+        generation: usize,
+        started: f64,
+        deadline: Option<f64>,
+    ) -> Result<Result<(), ErrorKind>, Option<String>>
+    where
+        F: FnMut(&mut Runner) -> Result<Progress, ErrorKind>,
+    {
+        loop {
+            match run_portion(runner) {
+                Ok(Progress::Unfinished) => {}
+                Ok(..) => return Ok(Ok(())),
+                Err(ErrorKind::StepLimitReached) => {
+                    return Ok(Err(ErrorKind::ExecutionAborted {
+                        steps: runner.executed_steps(),
+                    }))
+                }
+                Err(error) => return Ok(Err(error)),
+            }
+            self.yield_in_run(generation, started, deadline).await?;
+        }
+    }
+
+    /// Let the browser render the page and handle input,
+    /// then check whether the run of the given generation goes on.
+    async fn yield_in_run(
+        self,
+        generation: usize,
+        started: f64,
+        deadline: Option<f64>,
+    ) -> Result<(), Option<String>> {
+        gloo_timers::future::TimeoutFuture::new(0).await;
+        self.check_run(generation, started, deadline)
+    }
+
+    /// Check whether the run of the given generation goes on.
+    ///
+    /// Return the reason why the run was interrupted,
+    /// or no reason if another run replaced it.
+    fn check_run(
+        self,
+        generation: usize,
+        started: f64,
+        deadline: Option<f64>,
+    ) -> Result<(), Option<String>> {
+        if self.run_generation.get_value() != generation {
+            return Err(None);
+        }
+        if self.stop_requested.get_value() {
+            return Err(Some("Stopped".to_string()));
+        }
+        match deadline.is_some_and(|deadline| deadline <= js_sys::Date::now()) {
+            true => {
+                let seconds = (js_sys::Date::now() - started) / 1000.0;
+                Err(Some(format!("Timed out after {seconds:.1} s")))
+            }
+            false => Ok(()),
+        }
+    }
+
+    /// Stop the current run after its current portion of steps.
+    ///
+    /// The stopped run can be continued.
+    pub fn stop(self) {
+        if self.running.get_untracked() {
+            self.stop_requested.set_value(true);
+        }
+    }
+
+    /// Abort the current run and discard it, so that none of its results are shown.
+    ///
+    /// Unlike a stopped run, a cancelled run cannot be continued.
+    pub fn cancel(self) {
+        if !self.running.get_untracked() {
+            return;
+        }
+        // The pending portion of the run sees the new generation and returns
+        self.run_generation
+            .update_value(|generation| *generation += 1);
+        self.running.set(false);
+        self.stop_requested.set_value(false);
+        self.paused_runner.set_value(None);
+        self.clear_pause();
+        self.debug_output.update(String::clear);
+        self.debug_output_bits.update(String::clear);
+        self.warning_output
+            .set("The run was cancelled.".to_string());
+        self.toasts.push(ToastKind::Info, "Run cancelled");
+    }
+
+    fn interrupt(self, runner: Runner, reason: String) {
+        self.running.set(false);
+        self.interrupted.set(Some(format!(
+            "{reason} ({} steps)",
+            runner.executed_steps()
+        )));
+        self.error_output.set(None);
+        self.show_debug_output(&runner);
+        self.frames.set(Some(runner.frames()));
+        self.paused_runner.set_value(Some(runner));
+    }
+
+    fn show_debug_output(self, runner: &Runner) {
+        self.debug_output
+            .set(runner.debug_output().iter().join("\n"));
+        self.debug_output_bits
+            .set(runner.debug_output_bits().iter().join("\n"));
+    }
+
+    fn stop_at_limit(self, runner: Runner) {
+        self.step_limit_reached.set(Some(runner.executed_steps()));
+        self.error_output.set(None);
+        self.show_debug_output(&runner);
+        self.frames.set(Some(runner.frames()));
+        self.paused_runner.set_value(Some(runner));
+    }
+
+    fn pause(self, runner: Runner, line: usize) {
+        let (call, input) = runner.next_call().expect("runner paused at call");
+        self.paused_at.set(Some(Pause {
+            line,
+            call: call.text().to_string(),
+            input: input.to_string(),
+        }));
+        self.error_output.set(None);
+        self.show_debug_output(&runner);
+        self.frames.set(Some(runner.frames()));
+        self.paused_runner.set_value(Some(runner));
+    }
+
+    fn finish(self, runner: &mut Runner, result: Result<(), ErrorKind>) {
+        let success = match result {
+            Ok(()) => {
+                self.error_output.set(None);
+                true
+            }
+            Err(error) => {
+                match &error {
+                    ErrorKind::SimplicityHLCallFailed(call) => {
+                        let text = self.program.text.get_untracked();
+                        self.locate_call(&text, call.text());
+                    }
+                    _ => self.explain_failure(),
+                }
+                self.error_output.set(Some(ProgramError::from(error)));
+                false
+            }
+        };
+        self.trace.set(runner.take_trace().map(Arc::new));
+        self.jet_calls.set(runner.jet_calls());
+        self.introspections.set(runner.introspections().to_vec());
+        self.resources.set(Some(runner.resource_summary()));
+        if success && runner.executed_witnesses() == 0 {
+            self.warn_about_unused_witness();
+        }
+        self.show_debug_output(runner);
+        self.set_success(success);
+    }
+
+    /// Find the call that made the program fail and add it in its source context
+    /// to the error of the run.
+    ///
+    /// Programs without debug symbols only report what kind of node failed,
+    /// so the program is run again with debug symbols to find the failing call.
+    /// That run goes in portions of steps, with the step limit and the timeout of the settings,
+    /// and it is abandoned if another run starts.
+    fn explain_failure(self) {
+        let Ok(satisfied) = self.program.satisfied_with_debug_symbols() else {
+            return;
+        };
+        let Ok(mut runner) = load_satisfied(&satisfied) else {
+            return;
+        };
+        runner.limit_steps(self.settings.step_limit());
+        let env = self.run_env.get_value();
+        let generation = self.run_generation.get_value();
+        let started = js_sys::Date::now();
+
+        spawn_local(async move {
+            let deadline = self.deadline(started);
+            let run_portion = |runner: &mut Runner| {
+                env.with_untracked(|env| {
+                    runner.run_steps(env, STEPS_PER_PORTION, false, |_, _| false)
+                })
+            };
+            let Ok(Err(error)) = self
+                .run_portions(&mut runner, run_portion, generation, started, deadline)
+                .await
+            else {
+                return;
+            };
+            let Some(explanation) = self.explanation(&error) else {
+                return;
+            };
+            self.error_output.update(|output| {
+                if let Some(ProgramError::Runtime {
+                    explanation: slot @ None,
+                    ..
+                }) = output
+                {
+                    *slot = Some(explanation);
+                }
+            });
+        });
+    }
+
+    /// Explain which call of the program caused the error of its run with debug symbols.
+    fn explanation(self, error: &ErrorKind) -> Option<String> {
+        let ErrorKind::SimplicityHLCallFailed(call) = error else {
+            return None;
+        };
+        let text = self.program.text.get_untracked();
+        if let Some(line) = self.locate_call(&text, call.text()) {
+            return Some(format!(
+                "The failure was caused by this call on line {line}:\n{error}"
+            ));
+        }
+        let context = source::call_lines(&text, call.text())
+            .into_iter()
+            .map(|line| format!("Line {line}:\n{}", source::line_context(&text, line)))
+            .collect::<Vec<_>>();
+        let location = match context.is_empty() {
+            true => String::new(),
+            false => format!("\n{}", context.join("\n")),
+        };
+        Some(format!(
+            "The failure was caused by this call:\n{error}{location}"
+        ))
+    }
+
+    /// Remember the position of the call that made the run fail and return its line.
+    ///
+    /// Return `None` if the text of the call does not occur exactly once in the program,
+    /// because debug symbols cannot tell the occurrences apart.
+    fn locate_call(self, text: &str, call_text: &str) -> Option<usize> {
+        let [span] = <[_; 1]>::try_from(source::call_spans(text, call_text)).ok()?;
+        let line = source::line_of(text, span.start);
+        self.failed_call.set(Some(span));
+        Some(line)
+    }
+
+    /// Warn if the program declares witness values but never read any of them.
+    ///
+    /// Such a program is trivially satisfiable,
+    /// which is rarely what the author of a covenant wants.
+    fn warn_about_unused_witness(self) {
+        let declared = self
+            .program
+            .text
+            .with_untracked(|text| source::declarations(text, source::ModuleKind::Witness));
+        if declared.is_empty() {
+            return;
+        }
+        let names = declared.iter().map(|d| format!("`{}`", d.name)).join(", ");
+        self.warning_output.set(format!(
+            "Warning: The program declares witness values ({names}), \
+            but none of them was read during execution. \
+            Anyone can satisfy this program without knowing the witness."
+        ));
+    }
+}
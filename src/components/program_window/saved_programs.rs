@@ -0,0 +1,244 @@
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, use_context, view, CollectView, IntoView,
+    RwSignal, Show, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked,
+};
+
+use crate::components::modal::Modal;
+use crate::components::program_window::Program;
+use crate::components::state::LocalStorage;
+use crate::components::string_box::ErrorBox;
+use crate::pipeline::compile_text;
+
+/// Programs that the user saved under a name, in the order that the user arranged them.
+#[derive(Copy, Clone, Debug)]
+pub struct SavedPrograms(pub RwSignal<Vec<(String, String)>>);
+
+impl SavedPrograms {
+    pub fn new(programs: Vec<(String, String)>) -> Self {
+        Self(create_rw_signal(programs))
+    }
+
+    /// Move the program at index `from` to index `to` and store the new order.
+    pub fn move_program(self, from: usize, to: usize) {
+        self.0.update(|programs| {
+            if from < programs.len() && to < programs.len() {
+                let program = programs.remove(from);
+                programs.insert(to, program);
+            }
+        });
+        self.store_in_storage();
+    }
+}
+
+impl Default for SavedPrograms {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+/// Button that opens the list of saved programs.
+#[component]
+pub fn SavedProgramsButton() -> impl IntoView {
+    let open = create_rw_signal(false);
+
+    view! {
+        <button class="button" type="button" on:click=move |_| open.set(true)>
+            " Saved"
+        </button>
+        <Show when=move || open.get()>
+            <Modal title="Saved programs" class="saved-programs-modal" close=move || open.set(false)>
+                <SavedProgramList open=open />
+            </Modal>
+        </Show>
+    }
+}
+
+/// List of saved programs, which can be reordered by dragging.
+#[component]
+fn SavedProgramList(open: RwSignal<bool>) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let saved = use_context::<SavedPrograms>().expect("saved programs should exist in context");
+    let name = create_rw_signal(String::new());
+    let error = create_rw_signal(String::new());
+    // Index of the entry that is being dragged
+    let dragged = create_rw_signal(Option::<usize>::None);
+    // Outcome of compiling each saved program, by name, after validating them
+    let validation = create_rw_signal(Option::<Vec<(String, Result<(), String>)>>::None);
+
+    let save = move |_| {
+        let name = name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            error.set("Enter a name for the program".to_string());
+            return;
+        }
+        error.update(String::clear);
+        program.flush_text();
+        let text = program.text_to_store();
+        saved.0.update(
+            |programs| match programs.iter_mut().find(|(saved, _)| *saved == name) {
+                Some((_, saved_text)) => *saved_text = text,
+                None => programs.push((name, text)),
+            },
+        );
+        saved.store_in_storage();
+        validation.set(None);
+    };
+    let load = move |index: usize| {
+        let Some((_, text)) = saved
+            .0
+            .with_untracked(|programs| programs.get(index).cloned())
+        else {
+            return;
+        };
+        program.text.set(text);
+        open.set(false);
+    };
+    let delete = move |index: usize| {
+        saved.0.update(|programs| {
+            if index < programs.len() {
+                programs.remove(index);
+            }
+        });
+        saved.store_in_storage();
+        validation.set(None);
+    };
+    let validate_all = move |_| {
+        let outcomes = saved.0.with_untracked(|programs| {
+            programs
+                .iter()
+                .map(|(name, text)| {
+                    let outcome = compile_text(text, false)
+                        .map(|_| ())
+                        .map_err(|error| error.to_string());
+                    (name.clone(), outcome)
+                })
+                .collect()
+        });
+        validation.set(Some(outcomes));
+    };
+    let outcome = move |name: &str| {
+        validation.with(|validation| {
+            validation.as_ref().and_then(|outcomes| {
+                outcomes
+                    .iter()
+                    .find(|(validated, _)| validated == name)
+                    .map(|(_, outcome)| outcome.clone())
+            })
+        })
+    };
+    let summary = move || {
+        validation.with(|validation| {
+            validation.as_ref().map(|outcomes| {
+                let failed = outcomes
+                    .iter()
+                    .filter(|(_, outcome)| outcome.is_err())
+                    .count();
+                match failed {
+                    0 => format!("All {} saved programs compile.", outcomes.len()),
+                    _ => format!(
+                        "{failed} of {} saved programs do not compile.",
+                        outcomes.len()
+                    ),
+                }
+            })
+        })
+    };
+    let drop_on = move |index: usize| {
+        if let Some(from) = dragged.get_untracked() {
+            saved.move_program(from, index);
+        }
+        dragged.set(None);
+    };
+    let rows = move || {
+        saved.0.with(|programs| {
+            programs
+                .iter()
+                .enumerate()
+                .map(|(index, (name, _))| {
+                    let status = {
+                        let name = name.clone();
+                        move || match outcome(&name) {
+                            Some(Ok(())) => view! {
+                                <span class="saved-program-status ok">"compiles"</span>
+                            }
+                            .into_view(),
+                            Some(Err(error)) => view! {
+                                <span class="saved-program-status failed" title=error>"fails"</span>
+                            }
+                            .into_view(),
+                            None => ().into_view(),
+                        }
+                    };
+                    view! {
+                        <div
+                            class="saved-program"
+                            class:dragging=move || dragged.get() == Some(index)
+                            draggable="true"
+                            on:dragstart=move |event: ev::DragEvent| {
+                                // Firefox only starts dragging if there is data
+                                if let Some(transfer) = event.data_transfer() {
+                                    let _result = transfer.set_data("text/plain", &index.to_string());
+                                }
+                                dragged.set(Some(index));
+                            }
+                            on:dragover=|event: ev::DragEvent| event.prevent_default()
+                            on:drop=move |event: ev::DragEvent| {
+                                event.prevent_default();
+                                drop_on(index);
+                            }
+                            on:dragend=move |_| dragged.set(None)
+                        >
+                            <i class="fas fa-grip-vertical saved-program-handle" title="Drag to reorder"></i>
+                            <button class="action-button" type="button" on:click=move |_| load(index)>
+                                {name.clone()}
+                            </button>
+                            {status}
+                            <button
+                                class="action-button saved-program-delete"
+                                type="button"
+                                title="Delete"
+                                on:click=move |_| delete(index)
+                            >
+                                <i class="fas fa-trash"></i>
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let no_saved = move || saved.0.with(Vec::is_empty);
+
+    view! {
+        <div class="saved-programs">
+            <div class="saved-programs-save">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Name"
+                    on:input=move |event| name.set(event_target_value(&event))
+                />
+                <button class="button" type="button" on:click=save>"Save current program"</button>
+            </div>
+            <ErrorBox error=error />
+            <Show when=no_saved>
+                <p>"No saved programs yet."</p>
+            </Show>
+            {rows}
+            <Show when=move || !no_saved()>
+                <div class="saved-programs-validate">
+                    <button
+                        class="button"
+                        type="button"
+                        title="Compile every saved program to find the ones that no longer compile"
+                        on:click=validate_all
+                    >
+                        "Validate all"
+                    </button>
+                    <span>{summary}</span>
+                </div>
+            </Show>
+        </div>
+    }
+}
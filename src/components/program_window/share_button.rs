@@ -1,27 +1,217 @@
-use leptos::{component, use_context, view, IntoView, SignalWithUntracked};
+use leptos::{
+    component, create_rw_signal, event_target_checked, use_context, view, IntoView, SignalGet,
+    SignalGetUntracked, SignalSet, SignalWithUntracked,
+};
 
-use crate::components::copy_to_clipboard::CopyToClipboard;
+use simplicityhl::parse::ParseFromStr;
+
+use crate::components::app::{ActiveProgramView, ActiveRunTab};
+use crate::components::copy_to_clipboard::{CopyToClipboard, ManualCopy};
+use crate::components::modal::Modal;
 use crate::components::program_window::Program;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::download;
 use crate::url_sharing;
+use crate::util::source::{self, ModuleKind};
+use crate::util::witness;
+
+/// Ask the user whether to share the program `text` if it does not parse.
+///
+/// Return `true` if the program parses or if the user wants to share it anyway.
+fn confirm_if_broken(text: &str) -> bool {
+    if text.trim().is_empty() {
+        return true;
+    }
+    let Err(error) = simplicityhl::parse::Program::parse_from_str(text) else {
+        return true;
+    };
+    let message = format!("This program has errors:\n\n{error}\n\nShare it anyway?");
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(&message).ok())
+        .unwrap_or(false)
+}
 
 #[component]
 pub fn ShareButton() -> impl IntoView {
     let program = use_context::<Program>().expect("program should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let manual_copy = use_context::<ManualCopy>().expect("manual copy should exist in context");
+    let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+    let active_run_tab =
+        use_context::<ActiveRunTab>().expect("active run tab should exist in context");
+    // Share link that was too long to copy
+    let long_url = create_rw_signal(Option::<String>::None);
+    // Witnesses that look private, which the user is asked about before sharing them
+    let private_witnesses = create_rw_signal(Option::<Vec<String>>::None);
+    let include_witness = create_rw_signal(true);
 
+    // Recipients land on the view that is open when sharing
+    let shared_tab = move || match active_view.0.get_untracked() {
+        "Analyze" => "Analyze",
+        _ => active_run_tab.0.get_untracked(),
+    };
+    let shared_text = move || {
+        let text = program.text_to_store();
+        match include_witness.get_untracked() {
+            true => text,
+            false => source::remove_module(&text, ModuleKind::Witness),
+        }
+    };
     let share_url = move || {
-        program.text.with_untracked(|text| {
-            url_sharing::build_share_url(text).unwrap_or_else(|| "Empty program".to_string())
-        })
+        url_sharing::build_share_url(&shared_text(), Some(shared_tab()))
+            .unwrap_or_else(|| "Empty program".to_string())
     };
-    let update_hash = Box::new(move || {
-        program.text.with_untracked(|text| {
-            url_sharing::set_url_hash(text);
-        });
+    let check_before_sharing = Box::new(move |url: &str| {
+        if !program.text.with_untracked(|text| confirm_if_broken(text)) {
+            return false;
+        }
+        if include_witness.get_untracked() {
+            let private = program
+                .text
+                .with_untracked(|text| witness::private_looking(text));
+            if !private.is_empty() {
+                private_witnesses.set(Some(private));
+                return false;
+            }
+        }
+        if url.len() <= url_sharing::MAX_SHARE_URL_LENGTH {
+            return true;
+        }
+        long_url.set(Some(url.to_string()));
+        false
     });
+    let update_hash = move || {
+        url_sharing::set_url_hash(&shared_text(), Some(shared_tab()));
+    };
+    // Share after the user decided about the private witnesses
+    let share_with_witness = move |include: bool| {
+        private_witnesses.set(None);
+        include_witness.set(include);
+        let url = share_url();
+        if url_sharing::MAX_SHARE_URL_LENGTH < url.len() {
+            long_url.set(Some(url));
+            return;
+        }
+        manual_copy.copy(url, toasts, "Copied to clipboard");
+        update_hash();
+    };
+    let download_program = move |_| {
+        let result = program.text.with_untracked(|text| {
+            download::download_file(&source::file_name(text), "text/plain", text.as_bytes())
+        });
+        if result.is_err() {
+            toasts.push(
+                ToastKind::Error,
+                "The browser could not download the program",
+            );
+        }
+        long_url.set(None);
+    };
+    // Check that the link loads before sending it to someone
+    let open_in_new_tab = move |_| {
+        if !program.text.with_untracked(|text| confirm_if_broken(text)) {
+            return;
+        }
+        let Some(url) = url_sharing::build_share_url(&shared_text(), Some(shared_tab())) else {
+            toasts.push(ToastKind::Info, "There is no program to share");
+            return;
+        };
+        let opened = web_sys::window()
+            .and_then(|window| window.open_with_url_and_target(&url, "_blank").ok())
+            .flatten();
+        if opened.is_none() {
+            toasts.push(ToastKind::Error, "The browser blocked opening a new tab");
+        }
+    };
+    let copy_anyway = move |_| {
+        if let Some(url) = long_url.get() {
+            manual_copy.copy(url, toasts, "Copied to clipboard");
+        }
+        long_url.set(None);
+    };
 
     view! {
-        <CopyToClipboard content=share_url on_copy=update_hash class="button" tooltip_below=true>
+        <CopyToClipboard
+            content=share_url
+            on_copy=Box::new(update_hash)
+            before_copy=check_before_sharing
+            class="button"
+            tooltip_below=true
+            deferred=true
+        >
             " Share"
         </CopyToClipboard>
+        <button
+            class="button share-open-button"
+            type="button"
+            title="Open the share link in a new tab"
+            on:click=open_in_new_tab
+        >
+            <i class="fas fa-external-link-alt"></i>
+        </button>
+        <label
+            class="button share-witness-toggle"
+            title="Include the witness module in share links"
+        >
+            <input
+                type="checkbox"
+                prop:checked=include_witness
+                on:change=move |event| include_witness.set(event_target_checked(&event))
+            />
+            " Witness"
+        </label>
+        {move || private_witnesses.get().map(|names| view! {
+            <Modal title="Share the witness?" class="share-fallback-modal" close=move || private_witnesses.set(None)>
+            <p>
+                {format!(
+                    "The witness module contains values that look private: {}. \
+                    Anyone with the link can read them.",
+                    names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", "),
+                )}
+            </p>
+            <div class="button-row">
+                <button
+                    class="flat-button"
+                    type="button"
+                    on:click=move |_| share_with_witness(false)
+                >
+                    <i class="fas fa-eye-slash"></i>
+                    "Share without witness"
+                </button>
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    on:click=move |_| share_with_witness(true)
+                >
+                    <i class="fas fa-share"></i>
+                    "Share with witness"
+                </button>
+            </div>
+            </Modal>
+        })}
+        {move || long_url.get().map(|url| view! {
+            <Modal title="Share link too long" class="share-fallback-modal" close=move || long_url.set(None)>
+            <p>
+                {format!(
+                    "The share link would be {} characters long, \
+                    but links longer than {} characters break in some browsers and chat apps. \
+                    Share the program as a file instead: \
+                    dropping the file on the editor opens it.",
+                    url.len(),
+                    url_sharing::MAX_SHARE_URL_LENGTH,
+                )}
+            </p>
+            <div class="button-row">
+                <button class="flat-button" type="button" on:click=download_program>
+                    <i class="fas fa-download"></i>
+                    "Download program"
+                </button>
+                <button class="flat-button bordered" type="button" on:click=copy_anyway>
+                    <i class="fas fa-copy"></i>
+                    "Copy link anyway"
+                </button>
+            </div>
+            </Modal>
+        })}
     }
 }
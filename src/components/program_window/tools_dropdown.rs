@@ -1,11 +1,182 @@
-use leptos::{component, view, IntoView};
+use leptos::{
+    component, use_context, view, IntoView, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWithUntracked,
+};
 
+use crate::components::app::ActiveRunTab;
+use crate::components::copy_to_clipboard::ManualCopy;
 use crate::components::dropdown::Dropdown;
+use crate::components::program_window::{Program, Runtime, ShowDesugared};
+use crate::components::run_window::TxEnv;
+use crate::components::settings::Settings;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::download;
+use crate::pipeline::same_outcome;
+use crate::util::{rust_test, source, witness};
+use crate::worker::RunReport;
+
+const KEY_STORE: &str = "🔑️ Key Store";
+const HASH_STORE: &str = "#️⃣ Hash Store";
+const NEW_PROGRAM: &str = "New program";
+const NORMALIZE_WITNESS: &str = "Normalize witness";
+const STUB_WITNESS: &str = "Generate witness stub";
+const TOGGLE_DESUGARED: &str = "Toggle desugared view";
+const TOGGLE_WHITESPACE: &str = "Toggle whitespace view";
+const COPY_RUST_TEST: &str = "Copy as Rust test";
+const TOGGLE_MODULES: &str = "Toggle witness & param modules";
+const DOWNLOAD_PROGRAM: &str = "Download program (.simplicity)";
+const DOWNLOAD_REDEEM: &str = "Download program & witness";
+const VERIFY_DETERMINISM: &str = "Verify determinism";
+const TOGGLE_TIMINGS: &str = "Toggle compilation timings";
+const RUN_SELECTION: &str = "Run selection";
+
+/// Let the browser download the serialized program and, if `with_witness`, its witness data.
+///
+/// Both files use the bit encoding of Simplicity, which command-line tools read.
+fn download_program(program: Program, with_witness: bool) -> Result<(), String> {
+    // Downloading is an explicit action, so compile without waiting
+    program.compile();
+    let satisfied = program.satisfied().map_err(|error| error.to_string())?;
+    let (program_bytes, witness_bytes) = satisfied.redeem().to_vec_with_witness();
+    let download = |name: &str, bytes: &[u8]| {
+        download::download_file(name, "application/octet-stream", bytes)
+            .map_err(|_| format!("The browser could not download `{name}`"))
+    };
+    download("program.simplicity", &program_bytes)?;
+    if with_witness {
+        download("program.witness", &witness_bytes)?;
+    }
+    Ok(())
+}
+
+/// Ask the user whether to discard the program, unless it is empty.
+fn confirm_discard(program: Program) -> bool {
+    program.is_empty()
+        || web_sys::window()
+            .and_then(|window| {
+                window
+                    .confirm_with_message("Discard the current program and start a new one?")
+                    .ok()
+            })
+            .unwrap_or(false)
+}
 
 #[component]
 pub fn ToolsDropdown() -> impl IntoView {
-    let tools = ["🔑️ Key Store", "#️⃣ Hash Store"];
-    let select_tool = move |selected| leptos::logging::log!("You selected {selected}!");
+    let program = use_context::<Program>().expect("program should exist in context");
+    let show_desugared =
+        use_context::<ShowDesugared>().expect("desugar view setting should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let manual_copy = use_context::<ManualCopy>().expect("manual copy should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let active_run_tab =
+        use_context::<ActiveRunTab>().expect("active run tab should exist in context");
+    let tools = [
+        KEY_STORE,
+        HASH_STORE,
+        NEW_PROGRAM,
+        NORMALIZE_WITNESS,
+        STUB_WITNESS,
+        TOGGLE_MODULES,
+        TOGGLE_DESUGARED,
+        TOGGLE_WHITESPACE,
+        COPY_RUST_TEST,
+        DOWNLOAD_PROGRAM,
+        DOWNLOAD_REDEEM,
+        VERIFY_DETERMINISM,
+        TOGGLE_TIMINGS,
+        RUN_SELECTION,
+    ];
+    let select_tool = move |selected| {
+        if selected == KEY_STORE {
+            active_run_tab.0.set("Key Store");
+        } else if selected == HASH_STORE {
+            active_run_tab.0.set("Hash Store");
+        } else if selected == NEW_PROGRAM {
+            if confirm_discard(program) {
+                program.start_new(settings.default_modules());
+            }
+        } else if selected == NORMALIZE_WITNESS {
+            program.flush_text();
+            let normalized = program.text.with_untracked(|s| {
+                let order = witness::witness_order(s)?;
+                source::normalize_witness_module(s, &order)
+                    .ok_or_else(|| "There is no witness module to normalize".to_string())
+            });
+            match normalized {
+                Ok(text) => program.text.set(text),
+                Err(error) => toasts.push(ToastKind::Info, error),
+            }
+        } else if selected == STUB_WITNESS {
+            program.flush_text();
+            match program
+                .text
+                .with_untracked(|s| witness::stub_witness_module(s))
+            {
+                Ok(text) => program.text.set(text),
+                Err(error) => toasts.push(ToastKind::Info, error),
+            }
+        } else if selected == TOGGLE_MODULES {
+            program
+                .text
+                .update(|text| *text = source::toggle_module_comments(text));
+        } else if selected == TOGGLE_DESUGARED {
+            show_desugared.0.update(|show| *show = !*show);
+        } else if selected == TOGGLE_WHITESPACE {
+            settings.set_show_whitespace(!settings.show_whitespace());
+        } else if selected == COPY_RUST_TEST {
+            // Expect the outcome of the last run, or success if there was no run
+            let expect_success = runtime
+                .history
+                .with_untracked(|history| history.first().map_or(true, |record| record.success));
+            let test = program.text.with_untracked(|text| {
+                tx_env
+                    .params
+                    .with_untracked(|params| rust_test::rust_test(text, params, expect_success))
+            });
+            manual_copy.copy(test, toasts, "Copied Rust test to clipboard");
+        } else if selected == DOWNLOAD_PROGRAM || selected == DOWNLOAD_REDEEM {
+            if let Err(error) = download_program(program, selected == DOWNLOAD_REDEEM) {
+                toasts.push(ToastKind::Error, error);
+            }
+        } else if selected == RUN_SELECTION {
+            match program.selected_text() {
+                Some(selection) => {
+                    runtime.run_selection(&selection, &tx_env.params.get_untracked());
+                }
+                None => toasts.push(
+                    ToastKind::Info,
+                    "Select a boolean expression in the editor to run it",
+                ),
+            }
+        } else if selected == TOGGLE_TIMINGS {
+            program.toggle_timings();
+        } else if selected == VERIFY_DETERMINISM {
+            program.flush_text();
+            let text = program.text.get_untracked();
+            let params = tx_env.params.get_untracked();
+            let batch = vec![(text.clone(), params.clone()), (text, params)];
+            runtime.run_batch(batch, move |outcomes| {
+                let [first, second] = match outcomes.map(<[RunReport; 2]>::try_from) {
+                    Ok(Ok(outcomes)) => outcomes,
+                    Ok(Err(..)) => unreachable!("a batch has one outcome per run"),
+                    Err(reason) => return toasts.push(ToastKind::Info, reason),
+                };
+                match same_outcome(first, second) {
+                    Ok(..) => toasts.push(ToastKind::Success, "Both runs had the same outcome"),
+                    Err(outcomes) => {
+                        let (first, second) = *outcomes;
+                        runtime.warning_output.set(format!(
+                            "Warning: Two runs of the same program had different outcomes.\n\nFirst run:\n{first}\nSecond run:\n{second}"
+                        ));
+                        toasts.push(ToastKind::Error, "The runs differ; see the execution tab");
+                    }
+                }
+            });
+        }
+    };
 
     view! {
         <Dropdown name="Tools" options=tools select_option=select_tool />
@@ -24,10 +24,10 @@ pub fn TransactionButton() -> impl IntoView {
                 Ok(x) => x,
                 Err(..) => return "Execution fails".to_string(),
             };
-            params
-                .transaction(&pruned)
-                .serialize()
-                .to_lower_hex_string()
+            match params.transaction(&pruned) {
+                Ok(tx) => tx.serialize().to_lower_hex_string(),
+                Err(error) => error,
+            }
         })
     };
     view! {
@@ -1,34 +1,960 @@
 use js_sys::Date;
-use leptos::{component, use_context, view, IntoView, SignalWith};
+use leptos::{
+    component, create_memo, create_rw_signal, ev, event_target_value, use_context, view,
+    CollectView, IntoView, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, SignalWithUntracked,
+};
+use simplicityhl::simplicity;
 
-use crate::components::program_window::Runtime;
-use crate::components::string_box::{ErrorBox, NeutralBox, SuccessBox};
+use crate::components::app::ActiveProgramView;
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::{Program, RunRecord, Runtime};
+use crate::components::settings::Settings;
+use crate::components::string_box::{ErrorBox, NeutralBox, SuccessBox, WarningBox};
+use crate::download;
+use crate::jet;
+use crate::util::{code_frame, encoding, source};
 
 #[component]
 pub fn ExecutionTab() -> impl IntoView {
     let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let toggle_wrap = move |_| settings.set_wrap_debug_lines(!settings.wrap_debug_lines());
+    let wrap_label = move || match settings.wrap_debug_lines() {
+        true => "Wrap lines",
+        false => "Scroll lines",
+    };
     let success_string = move || {
-        runtime.error_output.with(|error| match error.is_empty() {
-            true => format!("{}: Success.", get_local_datetime()),
-            false => String::new(),
+        runtime.error_output.with(|error| match error {
+            None => format!("{}: Success.", get_local_datetime()),
+            Some(..) => String::new(),
         })
     };
     let failure_string = move || {
-        runtime.error_output.with(|error| match error.is_empty() {
-            true => String::new(),
-            false => format!("{}:\n{error}", get_local_datetime()),
+        runtime.error_output.with(|error| match error {
+            None => String::new(),
+            Some(error) => format!("{}:\n{error}", get_local_datetime()),
         })
     };
 
+    // Flash the outcome like the run button, since desktops do not vibrate
+    let flash_success = move || runtime.run_succeeded.get() == Some(true);
+    let flash_failure = move || runtime.run_succeeded.get() == Some(false);
+
+    let show_bits = create_rw_signal(false);
+    let toggle_bits = move |_| show_bits.update(|show| *show = !*show);
+    let bits_label = move || match show_bits.get() {
+        true => "Raw bits",
+        false => "Decoded values",
+    };
+    let debug_filter = create_rw_signal(String::new());
+    // Only the view is filtered, so the full output stays available
+    let debug_output = Signal::derive(move || {
+        let output = match show_bits.get() {
+            true => runtime.debug_output_bits.get(),
+            false => runtime.debug_output.get(),
+        };
+        debug_filter.with(|filter| {
+            let filter = filter.trim().to_lowercase();
+            match filter.is_empty() {
+                true => output,
+                false => output
+                    .lines()
+                    .filter(|line| line.to_lowercase().contains(&filter))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            }
+        })
+    });
+
+    let toggle_trace = move |_| runtime.trace_enabled.update(|enabled| *enabled = !*enabled);
+    let trace_label = move || match runtime.trace_enabled.get() {
+        true => "Tracing on",
+        false => "Tracing off",
+    };
+    let export_trace = move |_| {
+        runtime.trace.with(|trace| {
+            if let Some(trace) = trace {
+                let json = trace.to_json();
+                let _ = download::download_file("trace.json", "application/json", json.as_bytes());
+            }
+        });
+    };
+    let no_trace = move || runtime.trace.with(Option::is_none);
+    let toggle_auto_compile = move |_| settings.set_auto_compile(!settings.auto_compile());
+    let auto_compile_label = move || match settings.auto_compile() {
+        true => "Auto-compile on",
+        false => "Auto-compile off",
+    };
+
+    let toggle_fresh_env = move |_| settings.set_fresh_environment(!settings.fresh_environment());
+    let env_label = move || match settings.fresh_environment() {
+        true => "Fresh environment",
+        false => "Current environment",
+    };
+
+    let update_step_limit = move |event: ev::Event| {
+        let input = event_target_value(&event);
+        let limit = match input.trim() {
+            "" => None,
+            input => match input.parse::<usize>() {
+                Ok(limit) if 0 < limit => Some(limit),
+                _ => return,
+            },
+        };
+        settings.set_step_limit(limit);
+    };
+    let update_timeout = move |event: ev::Event| {
+        let input = event_target_value(&event);
+        let seconds = match input.trim() {
+            "" => None,
+            input => match input.parse::<u32>() {
+                Ok(seconds) if 0 < seconds => Some(seconds),
+                _ => return,
+            },
+        };
+        settings.set_run_timeout(seconds);
+    };
+
     view! {
         <div class="tab-content">
-            <SuccessBox success=success_string />
-            <ErrorBox error=failure_string />
-            <NeutralBox neutral=runtime.debug_output />
+            <div class="button-row is-small">
+                <button class="flat-button bordered" type="button" on:click=toggle_trace>
+                    <i class="fas fa-shoe-prints"></i>
+                    {trace_label}
+                </button>
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    on:click=export_trace
+                    disabled=no_trace
+                >
+                    <i class="fas fa-file-export"></i>
+                    "Export trace"
+                </button>
+                <button class="flat-button bordered" type="button" on:click=toggle_auto_compile>
+                    <i class="fas fa-hammer"></i>
+                    {auto_compile_label}
+                </button>
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    title="Whether runs use the edited transaction environment or the default one"
+                    on:click=toggle_fresh_env
+                >
+                    <i class="fas fa-leaf"></i>
+                    {env_label}
+                </button>
+                <button class="flat-button bordered" type="button" on:click=toggle_wrap>
+                    <i class="fas fa-align-left"></i>
+                    {wrap_label}
+                </button>
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    title="Show the values of dbg! as SimplicityHL values or as their bit encoding"
+                    on:click=toggle_bits
+                >
+                    <i class="fas fa-code"></i>
+                    {bits_label}
+                </button>
+                <label class="step-limit">
+                    "Step limit "
+                    <input
+                        class="input"
+                        type="number"
+                        min=1
+                        placeholder="None"
+                        value=settings.step_limit().map(|limit| limit.to_string())
+                        on:change=update_step_limit
+                    />
+                </label>
+                <label class="step-limit">
+                    "Timeout (s) "
+                    <input
+                        class="input"
+                        type="number"
+                        min=1
+                        placeholder="None"
+                        value=settings.run_timeout().map(|seconds| seconds.to_string())
+                        on:change=update_timeout
+                    />
+                </label>
+            </div>
+            <BreakpointList />
+            <RunningBox />
+            <PausedBox />
+            <StepLimitBox />
+            <InterruptedBox />
+            <FrameViewer />
+            <div
+                class="run-outcome"
+                class:flash-success=flash_success
+                class:flash-failure=flash_failure
+            >
+                <SuccessBox success=success_string />
+                <WarningBox warning=runtime.warning_output />
+                <ErrorCategory />
+                <FailureBox failure=failure_string />
+            </div>
+            <div class="debug-output" class:wrap-lines=move || settings.wrap_debug_lines()>
+                <input
+                    class="input debug-filter"
+                    type="search"
+                    placeholder="Filter debug output"
+                    spellcheck="false"
+                    class:hidden=move || runtime.debug_output.with(String::is_empty)
+                    prop:value=debug_filter
+                    on:input=move |event| debug_filter.set(event_target_value(&event))
+                />
+                <NeutralBox neutral=debug_output />
+            </div>
+            <ResourceUsage />
+            <EncodedProgramPanel />
+            <IntrospectionPanel />
+            <JetProfile />
+            <RunHistory />
         </div>
     }
 }
 
+/// Breakpoints of the program, with a field for the input value at which each one pauses.
+///
+/// Breakpoints are toggled with F9 in the editor or added by line number.
+#[component]
+fn BreakpointList() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let new_line = create_rw_signal(String::new());
+    let add_breakpoint = move |_| {
+        let Ok(line) = new_line.get_untracked().trim().parse::<usize>() else {
+            return;
+        };
+        let exists = runtime
+            .breakpoints
+            .with_untracked(|breakpoints| breakpoints.iter().any(|b| b.line == line));
+        if 0 < line && !exists {
+            runtime.toggle_breakpoint(line);
+        }
+        new_line.set(String::new());
+    };
+    let rows = move || {
+        runtime
+            .breakpoints
+            .get()
+            .into_iter()
+            .map(|breakpoint| {
+                let line = breakpoint.line;
+                let update_condition = move |event: ev::Event| {
+                    let condition = event_target_value(&event);
+                    runtime.breakpoints.update(|breakpoints| {
+                        if let Some(b) = breakpoints.iter_mut().find(|b| b.line == line) {
+                            b.condition = condition;
+                        }
+                    });
+                };
+                view! {
+                    <div class="breakpoint-row">
+                        <span class="breakpoint-line">{format!("Line {line}")}</span>
+                        <input
+                            class="input breakpoint-condition"
+                            type="text"
+                            placeholder="Pause at any input"
+                            title="Pause only if the input of the call is this value"
+                            value=breakpoint.condition
+                            on:change=update_condition
+                        />
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            title="Remove breakpoint"
+                            on:click=move |_| runtime.toggle_breakpoint(line)
+                        >
+                            <i class="fas fa-times"></i>
+                        </button>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="breakpoints">
+            <div class="breakpoint-add">
+                <h3 class="tab-title">"Breakpoints"</h3>
+                <input
+                    class="input"
+                    type="number"
+                    min=1
+                    placeholder="Line"
+                    prop:value=new_line
+                    on:input=move |event| new_line.set(event_target_value(&event))
+                />
+                <button class="flat-button bordered" type="button" on:click=add_breakpoint>
+                    <i class="fas fa-plus"></i>
+                    "Add"
+                </button>
+            </div>
+            {rows}
+        </div>
+    }
+}
+
+/// State of a paused execution, with a button to continue it.
+#[component]
+fn PausedBox() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.paused_at.get().map(|pause| {
+            view! {
+                <div class="neutral-box paused-box">
+                    <pre>
+                        {format!(
+                            "Paused at line {} before `{}`\nInput: {}",
+                            pause.line, pause.call, pause.input,
+                        )}
+                    </pre>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        on:click=move |_| runtime.continue_run()
+                    >
+                        <i class="fas fa-forward"></i>
+                        "Continue"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
+
+/// Notice that a run stopped at the step limit, with a button to continue it.
+#[component]
+fn StepLimitBox() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.step_limit_reached.get().map(|steps| {
+            view! {
+                <div class="neutral-box paused-box">
+                    <pre>
+                        {format!(
+                            "Step limit reached after {steps} steps\n\
+                            The program may be very long or stuck in a large loop."
+                        )}
+                    </pre>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        on:click=move |_| runtime.continue_run()
+                    >
+                        <i class="fas fa-forward"></i>
+                        "Continue"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
+
+/// Label of the kind of error of the last run, colored by its category.
+#[component]
+fn ErrorCategory() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.error_output.with(|error| {
+            error.as_ref().map(|error| {
+                view! {
+                    <span class=format!("error-category {}", error.category())>
+                        {error.label()}
+                    </span>
+                }
+            })
+        })
+    }
+}
+
+/// Error of the last run, with a code frame of the program if the error has a location.
+///
+/// Runtime errors have a location if the failing call was found in the program.
+#[component]
+fn FailureBox(#[prop(into)] failure: Signal<String>) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let active_view = use_context::<ActiveProgramView>().expect("ActiveProgramView should exist");
+    let frame = move || {
+        failure.with(|failure| {
+            program.text.with_untracked(|text| {
+                code_frame::code_frame(text, failure).or_else(|| {
+                    let span = runtime.failed_call.get()?;
+                    let message = format!("Line {}", source::line_of(text, span.start));
+                    code_frame::span_frame(text, span, failure.clone(), message)
+                })
+            })
+        })
+    };
+    let go_to_line = move |line: usize| {
+        active_view.0.set("Run");
+        program.go_to_line(line);
+    };
+
+    move || match frame() {
+        None => view! { <ErrorBox error=failure /> }.into_view(),
+        Some(frame) => {
+            let width = frame
+                .lines
+                .last()
+                .map_or(1, |line| line.number.to_string().len());
+            let error_line = frame
+                .lines
+                .iter()
+                .find(|line| line.underline.is_some())
+                .map(|line| line.number);
+            let lines = frame
+                .lines
+                .into_iter()
+                .map(|line| {
+                    let chars: Vec<char> = line.content.chars().collect();
+                    let (before, marked, after) = match line.underline.clone() {
+                        Some(underline) => {
+                            let end = underline.end.min(chars.len());
+                            let start = underline.start.min(end);
+                            (&chars[..start], &chars[start..end], &chars[end..])
+                        }
+                        None => (&chars[..], &[][..], &[][..]),
+                    };
+                    view! {
+                        <div class="code-frame-line" class:error-line=line.underline.is_some()>
+                            <span class="code-frame-number">
+                                {format!("{:>width$} | ", line.number)}
+                            </span>
+                            {before.iter().collect::<String>()}
+                            <span class="code-frame-underline">{marked.iter().collect::<String>()}</span>
+                            {after.iter().collect::<String>()}
+                        </div>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class="error-box">
+                    <pre>{frame.preamble}</pre>
+                    <pre class="code-frame">{lines}</pre>
+                    <pre class="code-frame-message">{frame.message}</pre>
+                    {error_line.map(|line| view! {
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            on:click=move |_| go_to_line(line)
+                        >
+                            <i class="fas fa-arrow-right"></i>
+                            {format!("Go to line {line}")}
+                        </button>
+                    })}
+                </div>
+            }
+            .into_view()
+        }
+    }
+}
+
+/// Notice that a run is in progress, with buttons to stop it or to cancel it.
+#[component]
+fn RunningBox() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.running.get().then(|| {
+            view! {
+                <div class="neutral-box paused-box">
+                    <pre>"Running…"</pre>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        on:click=move |_| runtime.stop()
+                    >
+                        <i class="fas fa-stop"></i>
+                        "Stop"
+                    </button>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        title="Discard the run instead of pausing it"
+                        on:click=move |_| runtime.cancel()
+                    >
+                        <i class="fas fa-times"></i>
+                        "Cancel"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
+
+/// Notice that a run timed out or was stopped, with a button to continue it.
+///
+/// The debug output shows what the run printed until then.
+#[component]
+fn InterruptedBox() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.interrupted.get().map(|reason| {
+            view! {
+                <div class="neutral-box paused-box">
+                    <pre>{format!("{reason}\nThe debug output below is partial.")}</pre>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        on:click=move |_| runtime.continue_run()
+                    >
+                        <i class="fas fa-forward"></i>
+                        "Continue"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
+
+/// Maximum number of bits that are shown of a single frame.
+const MAX_FRAME_BITS: usize = 512;
+
+/// Frame of the Bit Machine as a row of bits.
+///
+/// The cursor of the active read frame is at its start,
+/// because the next combinator reads the whole frame.
+/// The cursor of the latest write frame is at its end.
+#[component]
+fn FrameBits(bits: Vec<bool>, cursor: Option<usize>) -> impl IntoView {
+    let hidden = bits.len().saturating_sub(MAX_FRAME_BITS);
+    let cells = bits
+        .iter()
+        .take(MAX_FRAME_BITS)
+        .enumerate()
+        .map(|(index, bit)| {
+            view! {
+                <span class="frame-bit" class:frame-cursor=cursor == Some(index)>
+                    {if *bit { "1" } else { "0" }}
+                </span>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="frame-bits">
+            {cells}
+            {(cursor == Some(bits.len())).then(|| view! { <span class="frame-bit frame-cursor"></span> })}
+            {(bits.is_empty() && cursor.is_none()).then(|| view! { <span class="frame-empty">"empty"</span> })}
+            {(0 < hidden).then(|| view! { <span class="frame-more">{format!("… {hidden} more bits")}</span> })}
+        </div>
+    }
+}
+
+/// Read and write frames of the paused execution, with a button to execute a single step.
+#[component]
+fn FrameViewer() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    move || {
+        runtime.frames.get().map(|frames| {
+            let read_count = frames.read.len();
+            let write_count = frames.write.len();
+            let read = frames
+                .read
+                .into_iter()
+                .enumerate()
+                .rev()
+                .map(|(index, bits)| {
+                    let cursor = (index + 1 == read_count).then_some(0);
+                    view! { <FrameBits bits=bits cursor=cursor /> }
+                })
+                .collect_view();
+            let write = frames
+                .write
+                .into_iter()
+                .enumerate()
+                .rev()
+                .map(|(index, bits)| {
+                    let cursor = (index + 1 == write_count).then_some(bits.len());
+                    view! { <FrameBits bits=bits cursor=cursor /> }
+                })
+                .collect_view();
+
+            view! {
+                <div class="frame-viewer">
+                    <div class="frame-viewer-header">
+                        <h3 class="tab-title">"Frames"</h3>
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            title="Go back to the start of the execution"
+                            on:click=move |_| runtime.restart()
+                        >
+                            <i class="fas fa-undo"></i>
+                            "Restart"
+                        </button>
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            title="Go back to the state before the last step"
+                            disabled=move || !runtime.can_step_back.get()
+                            on:click=move |_| runtime.step_back()
+                        >
+                            <i class="fas fa-step-backward"></i>
+                            "Step back"
+                        </button>
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            title="Execute the next combinator"
+                            on:click=move |_| runtime.step_once()
+                        >
+                            <i class="fas fa-step-forward"></i>
+                            "Step"
+                        </button>
+                        <button
+                            class="flat-button bordered"
+                            type="button"
+                            title="Run until the end or the next breakpoint"
+                            on:click=move |_| runtime.continue_run()
+                        >
+                            <i class="fas fa-forward"></i>
+                            "Continue"
+                        </button>
+                    </div>
+                    <div class="frame-stack">
+                        <div class="frame-stack-label">"Read frames (active first)"</div>
+                        {read}
+                    </div>
+                    <div class="frame-stack">
+                        <div class="frame-stack-label">"Write frames (latest first)"</div>
+                        {write}
+                    </div>
+                </div>
+            }
+        })
+    }
+}
+
+/// Cost and memory of the program of the last run, as consensus bounds them.
+///
+/// The bounds apply to failed runs as well, since nodes check them before they run a program.
+#[component]
+fn ResourceUsage() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let rows = move || {
+        runtime.resources.get().map(|resources| {
+            let cost = format!(
+                "{} mWU of at most {} mWU",
+                resources.cost,
+                simplicity::Cost::CONSENSUS_MAX
+            );
+            view! {
+                <table>
+                    <tr>
+                        <td>"Cost"</td>
+                        <td class="resource-usage-value">{cost}</td>
+                    </tr>
+                    <tr>
+                        <td>"Cells"</td>
+                        <td class="resource-usage-value">{resources.max_cells}</td>
+                    </tr>
+                    <tr>
+                        <td>"Frames"</td>
+                        <td class="resource-usage-value">{resources.max_frames}</td>
+                    </tr>
+                </table>
+            }
+        })
+    };
+
+    view! {
+        <Show when=move || runtime.resources.with(Option::is_some)>
+            <div class="resource-usage">
+                <h3 class="tab-title">"Resources"</h3>
+                <p class="tab-description">
+                    "Bounds on the cost and memory of the Bit Machine, which nodes check before they run the program. "
+                    "The witness stack of the spending input must weigh at least as much as the cost."
+                </p>
+                {rows}
+            </div>
+        </Show>
+    }
+}
+
+/// Serialized program and witness data after a successful run,
+/// to be put into the witness stack of a spending transaction.
+#[component]
+fn EncodedProgramPanel() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let program = use_context::<Program>().expect("program should exist in context");
+    let encoded = create_memo(move |_| match runtime.run_succeeded.get() {
+        Some(true) => Some(
+            program
+                .satisfied()
+                .map(|satisfied| encoding::encode_satisfied(&satisfied)),
+        ),
+        _ => None,
+    });
+    let contents = move || {
+        encoded.get().map(|encoded| match encoded {
+            Ok(encoded) => {
+                let witness = match encoded.witness.is_empty() {
+                    true => view! {
+                        <p class="tab-description">"The program has no witness data."</p>
+                    }
+                    .into_view(),
+                    false => view! {
+                        <EncodingRow
+                            label="Witness"
+                            length=encoded.witness.len()
+                            hex=encoded.witness_hex()
+                            base64=encoded.witness_base64()
+                        />
+                    }
+                    .into_view(),
+                };
+                view! {
+                    <EncodingRow
+                        label="Program"
+                        length=encoded.program.len()
+                        hex=encoded.program_hex()
+                        base64=encoded.program_base64()
+                    />
+                    {witness}
+                }
+                .into_view()
+            }
+            Err(error) => view! {
+                <p class="tab-description">{format!("The program could not be encoded: {error}")}</p>
+            }
+            .into_view(),
+        })
+    };
+
+    view! {
+        <Show when=move || encoded.with(Option::is_some)>
+            <div class="encoded-program">
+                <h3 class="tab-title">"Encoded program"</h3>
+                <p class="tab-description">
+                    "The program and its witness data as they appear in the witness stack of the spending transaction."
+                </p>
+                {contents}
+            </div>
+        </Show>
+    }
+}
+
+/// Bytes of one part of the encoded program as hex and as base64, each with a copy button.
+#[component]
+fn EncodingRow(label: &'static str, length: usize, hex: String, base64: String) -> impl IntoView {
+    let encodings = [("hex", hex), ("base64", base64)].map(|(format, text)| {
+        let content = {
+            let text = text.clone();
+            Signal::derive(move || text.clone())
+        };
+        view! {
+            <div class="encoded-program-header">
+                <span class="encoded-program-format">{format}</span>
+                <CopyToClipboard content=content class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <pre class="encoded-program-bytes">{text}</pre>
+        }
+    });
+
+    view! {
+        <div class="encoded-program-part">
+            <div class="analysis-item-label">{label}" ("{length}" bytes):"</div>
+            {encodings.into_iter().collect_view()}
+        </div>
+    }
+}
+
+/// Table of how often each jet was called during the last run.
+#[component]
+fn JetProfile() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let rows = move || {
+        runtime.jet_calls.with(|jet_calls| {
+            let max_count = jet_calls.first().map_or(1, |(_, count)| *count);
+            jet_calls
+                .iter()
+                .map(|(jet, count)| {
+                    let width = format!("width: {}%", count * 100 / max_count);
+                    view! {
+                        <tr>
+                            <td class="jet-profile-name">{jet.to_string()}</td>
+                            <td class="jet-profile-count">{*count}</td>
+                            <td class="jet-profile-bar-cell">
+                                <div class="jet-profile-bar" style=width></div>
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let has_calls = move || runtime.jet_calls.with(|jet_calls| !jet_calls.is_empty());
+
+    view! {
+        <Show when=has_calls>
+            <div class="jet-profile">
+                <h3 class="tab-title">"Jet calls"</h3>
+                <table>
+                    <tr>
+                        <th>"Jet"</th>
+                        <th>"Calls"</th>
+                        <th></th>
+                    </tr>
+                    {rows}
+                </table>
+            </div>
+        </Show>
+    }
+}
+
+/// Table of what the jets read from the transaction environment during the last run,
+/// such as the values of inputs and outputs, the lock time and the sequences.
+#[component]
+fn IntrospectionPanel() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let rows = move || {
+        runtime.introspections.with(|introspections| {
+            introspections
+                .iter()
+                .map(|reading| {
+                    let call = format!("jet::{}({})", reading.jet, reading.arguments);
+                    let result = match &reading.result {
+                        Some(result) => {
+                            view! { <td class="introspection-result">{result.clone()}</td> }
+                        }
+                        None => view! { <td class="introspection-failed">"failed"</td> },
+                    };
+                    view! {
+                        <tr>
+                            <td class="introspection-call" title=jet::description(reading.jet)>
+                                {call}
+                            </td>
+                            {result}
+                            <td class="introspection-count">{reading.count}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let has_readings = move || {
+        runtime
+            .introspections
+            .with(|introspections| !introspections.is_empty())
+    };
+
+    view! {
+        <Show when=has_readings>
+            <div class="introspection">
+                <h3 class="tab-title">"Transaction introspection"</h3>
+                <p class="tab-description">
+                    "Values that the program read from the transaction environment, in the order in which it first read them."
+                </p>
+                <table>
+                    <tr>
+                        <th>"Call"</th>
+                        <th>"Result"</th>
+                        <th>"Calls"</th>
+                    </tr>
+                    {rows}
+                </table>
+            </div>
+        </Show>
+    }
+}
+
+/// List of the past runs of this session.
+///
+/// Clicking a run shows the output that it produced.
+#[component]
+fn RunHistory() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let selected = create_rw_signal(Option::<usize>::None);
+    let rows = move || {
+        runtime.history.with(|history| {
+            history
+                .iter()
+                .map(|record| {
+                    let id = record.id;
+                    let status = match record.success {
+                        true => view! { <i class="fas fa-check"></i> },
+                        false => view! { <i class="fas fa-times"></i> },
+                    };
+                    let cmr = record
+                        .cmr
+                        .map(|cmr| cmr.to_string()[..16].to_string() + "…")
+                        .unwrap_or_default();
+                    let toggle = move |_| {
+                        selected.update(|selected| {
+                            *selected = match *selected == Some(id) {
+                                true => None,
+                                false => Some(id),
+                            }
+                        });
+                    };
+                    view! {
+                        <tr
+                            class="run-history-row"
+                            class:failure=!record.success
+                            class:selected=move || selected.get() == Some(id)
+                            on:click=toggle
+                        >
+                            <td>{status}</td>
+                            <td>{record.timestamp.clone()}</td>
+                            <td>{format!("{:.1} ms", record.elapsed_ms)}</td>
+                            <td class="run-history-cmr">{cmr}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let selected_record = move || -> Option<RunRecord> {
+        let id = selected.get()?;
+        runtime
+            .history
+            .with(|history| history.iter().find(|record| record.id == id).cloned())
+    };
+    let has_history = move || runtime.history.with(|history| !history.is_empty());
+
+    view! {
+        <Show when=has_history>
+            <div class="run-history">
+                <h3 class="tab-title">"Run history"</h3>
+                <table>
+                    <tr>
+                        <th></th>
+                        <th>"Time"</th>
+                        <th>"Elapsed"</th>
+                        <th>"CMR"</th>
+                    </tr>
+                    {rows}
+                </table>
+                {move || selected_record().map(|record| {
+                    let output = match record.success {
+                        true => record.debug_output,
+                        false => format!("{}\n{}", record.error_output, record.debug_output),
+                    };
+                    view! {
+                        <div class="neutral-box">
+                            <pre>{output.trim().to_string()}</pre>
+                        </div>
+                    }
+                })}
+            </div>
+        </Show>
+    }
+}
+
 fn get_local_datetime() -> String {
     let date = Date::new_0();
     date.to_iso_string().as_string().unwrap()
@@ -0,0 +1,104 @@
+use leptos::{
+    component, create_rw_signal, use_context, view, CollectView, IntoView, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWithUntracked,
+};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::components::run_window::TxEnv;
+use crate::components::string_box::ErrorBox;
+use crate::pipeline;
+
+/// Runs of the program with each input of the transaction as the spent input.
+///
+/// Covenants often have to hold for every input,
+/// which a single run against the current input does not show.
+#[component]
+pub fn InputRuns() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let error = create_rw_signal(String::new());
+    let outcomes = create_rw_signal(Option::<Vec<Result<(), String>>>::None);
+
+    let run_all = move |_| {
+        program.flush_text();
+        let text = program.text.get_untracked();
+        let batch = tx_env.params.with_untracked(|params| {
+            pipeline::each_input(params)
+                .into_iter()
+                .map(|params| (text.clone(), params))
+                .collect()
+        });
+        error.update(String::clear);
+        outcomes.set(None);
+        runtime.run_batch(batch, move |runs| match runs {
+            Ok(runs) => outcomes.set(Some(runs.into_iter().map(|run| run.result).collect())),
+            Err(reason) => error.set(reason),
+        });
+    };
+
+    let outcomes_view = move || {
+        outcomes.get().map(|outcomes| {
+            let passed = outcomes.iter().filter(|outcome| outcome.is_ok()).count();
+            let summary = format!("{passed} of {} inputs pass", outcomes.len());
+            let rows = outcomes
+                .into_iter()
+                .enumerate()
+                .map(|(index, outcome)| {
+                    let cell = match outcome {
+                        Ok(()) => view! { <td class="witness-set-pass">"pass"</td> },
+                        Err(error) => {
+                            view! { <td class="witness-set-fail" title=error>"fail"</td> }
+                        }
+                    };
+                    view! {
+                        <tr>
+                            <td>{index}</td>
+                            {cell}
+                        </tr>
+                    }
+                })
+                .collect_view();
+            view! {
+                <p class="tab-description">{summary}</p>
+                <table class="witness-set-matrix">
+                    <tr>
+                        <th>"Spent input"</th>
+                        <th>"Outcome"</th>
+                    </tr>
+                    {rows}
+                </table>
+            }
+        })
+    };
+
+    view! {
+        <div class="input-runs">
+            <h3 class="tab-title">"Every input"</h3>
+            <p class="tab-description">
+                "Run the program once for each input of the transaction, "
+                "with that input as the spent input."
+            </p>
+            <div class="network-preset">
+                <button
+                    class="button"
+                    type="button"
+                    disabled=move || runtime.running.get()
+                    on:click=run_all
+                >
+                    "Run for every input"
+                </button>
+                <button
+                    class="button"
+                    class:hidden=move || !runtime.running.get()
+                    type="button"
+                    on:click=move |_| runtime.cancel()
+                >
+                    "Cancel"
+                </button>
+            </div>
+            <ErrorBox error=error />
+            {outcomes_view}
+        </div>
+    }
+}
@@ -1,30 +1,78 @@
 mod execution_tab;
 mod hash_store_tab;
+mod input_runs;
 mod key_store_tab;
+mod param_tab;
+mod random_witness;
 mod transaction_tab;
+mod value_tab;
+mod witness_sets;
+mod witness_tab;
 
-use crate::components::app::ActiveRunTab;
-use leptos::{component, use_context, view, IntoView};
+use crate::components::app::{ActiveRunTab, EmbedMode};
+use leptos::{component, create_memo, use_context, view, IntoView, Signal, SignalWith};
 
 use self::execution_tab::ExecutionTab;
 use self::hash_store_tab::HashStoreTab;
 use self::key_store_tab::KeyStoreTab;
-use self::transaction_tab::TransactionTab;
-use crate::components::navbar::{Navbar, Tab};
+use self::param_tab::ParamTab;
+use self::transaction_tab::{EnvStatus, TransactionTab};
+use self::value_tab::ValueTab;
+use self::witness_tab::WitnessTab;
+use crate::components::navbar::{Button, Navbar, Tab};
+use crate::components::program_window::{Program, Runtime};
+use crate::components::settings::Settings;
+use crate::util::source::ModuleKind;
+use crate::util::witness;
 
 pub use self::hash_store_tab::HashCount;
 pub use self::key_store_tab::{KeyCount, SignedData};
-pub use self::transaction_tab::TxEnv;
+pub use self::random_witness::WitnessSeed;
+pub use self::transaction_tab::{SavedTxEnvs, TxEnv};
+pub use self::witness_sets::WitnessSets;
 
 #[component]
 pub fn RunWindow() -> impl IntoView {
     let active_run_tab =
         use_context::<ActiveRunTab>().expect("active run tab should exist in context");
+    let embedded = use_context::<EmbedMode>().expect("embed mode should exist in context");
+
+    if embedded.0 {
+        return view! { <ExecutionTab /> }.into_view();
+    }
+
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let execution_error = create_memo(move |_| {
+        runtime.error_output.with(|error| match error.is_none() {
+            true => None,
+            false => Some("The last run failed".to_string()),
+        })
+    });
+    let witness_error = create_memo(move |_| {
+        program
+            .text
+            .with(|text| witness::module_error(text, ModuleKind::Witness))
+    });
+    let param_error = create_memo(move |_| {
+        program
+            .text
+            .with(|text| witness::module_error(text, ModuleKind::Param))
+    });
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let modules_hidden = Signal::derive(move || !settings.show_module_tabs());
+
     view! {
         <Navbar default_tab="Execution" active_tab=active_run_tab.0>
-            <Tab name="Execution">
+            <Tab name="Execution" error=execution_error>
                 <ExecutionTab />
             </Tab>
+            <Tab name="Witness" error=witness_error hidden=modules_hidden>
+                <WitnessTab />
+            </Tab>
+            <Tab name="Parameters" error=param_error hidden=modules_hidden>
+                <ParamTab />
+            </Tab>
             <Tab name="Transaction">
                 <TransactionTab />
             </Tab>
@@ -34,6 +82,24 @@ pub fn RunWindow() -> impl IntoView {
             <Tab name="Hash Store">
                 <HashStoreTab />
             </Tab>
+            <Tab name="Values">
+                <ValueTab />
+            </Tab>
+            <Button>
+                <button
+                    class="tab module-tabs-toggle"
+                    type="button"
+                    title="Show or hide the witness and parameter tabs (Alt+W)"
+                    on:click=move |_| settings.toggle_module_tabs()
+                >
+                    <i class=move || match settings.show_module_tabs() {
+                        true => "fas fa-eye-slash",
+                        false => "fas fa-eye",
+                    }></i>
+                </button>
+            </Button>
         </Navbar>
+        <EnvStatus />
     }
+    .into_view()
 }
@@ -0,0 +1,18 @@
+use leptos::{component, view, IntoView};
+
+use super::witness_tab::DeclarationForm;
+use crate::util::source::ModuleKind;
+
+#[component]
+pub fn ParamTab() -> impl IntoView {
+    view! {
+        <div class="tab-content witness-tab">
+            <p class="tab-description">
+                "Each parameter of the program has its own field. "
+                "Changes are written into the param module of the program, "
+                "which provides the arguments for compiling it."
+            </p>
+            <DeclarationForm module=ModuleKind::Param />
+        </div>
+    }
+}
@@ -0,0 +1,221 @@
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, use_context, view, CollectView, IntoView,
+    RwSignal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWithUntracked,
+};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::components::run_window::witness_tab::declared_types;
+use crate::components::run_window::TxEnv;
+use crate::components::state::LocalStorage;
+use crate::components::string_box::ErrorBox;
+use crate::util::source::{self, ModuleKind};
+use crate::util::witness;
+
+/// First seed of the runs with random witness values.
+///
+/// Run `i` uses the seed plus `i`, so each failing run can be reproduced from its seed.
+#[derive(Copy, Clone, Debug)]
+pub struct WitnessSeed(pub RwSignal<u64>);
+
+impl WitnessSeed {
+    pub fn new(seed: u64) -> Self {
+        Self(create_rw_signal(seed))
+    }
+}
+
+impl Default for WitnessSeed {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+const DEFAULT_RUNS: u64 = 32;
+
+/// Assign random values that are generated from `seed` to the witnesses of the program `text`.
+fn with_random_witness(text: &str, seed: u64) -> Result<String, String> {
+    let declared: Vec<_> = declared_types(text, ModuleKind::Witness)?
+        .into_iter()
+        .map(|(name, ty, _)| (name, ty))
+        .collect();
+    if declared.is_empty() {
+        return Err("The program uses no witness values".to_string());
+    }
+    let values = witness::random_values(&declared, seed)?;
+    let updated =
+        declared
+            .iter()
+            .zip(values)
+            .fold(text.to_string(), |text, ((_, ty), (name, value))| {
+                source::set_declaration(&text, ModuleKind::Witness, &name, ty, &value)
+            });
+    Ok(updated)
+}
+
+/// Runs of the program with random witness values, each from its own seed.
+///
+/// The seeds of failing runs are listed, so their witness values can be written into the program.
+#[component]
+pub fn RandomWitness() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let seed = use_context::<WitnessSeed>().expect("witness seed should exist in context");
+    let runs = create_rw_signal(DEFAULT_RUNS);
+    let error = create_rw_signal(String::new());
+    // Number of runs and the seeds of the failing runs with their errors
+    let outcome = create_rw_signal(Option::<(u64, Vec<(u64, String)>)>::None);
+
+    let update_seed = move |event: ev::Event| {
+        if let Ok(value) = event_target_value(&event).trim().parse::<u64>() {
+            seed.0.set(value);
+            seed.store_in_storage();
+        }
+    };
+    let update_runs = move |event: ev::Event| {
+        if let Ok(value) = event_target_value(&event).trim().parse::<u64>() {
+            runs.set(value.max(1));
+        }
+    };
+    let run_all = move |_| {
+        program.flush_text();
+        let text = program.text.get_untracked();
+        let params = tx_env.params.get_untracked();
+        let first = seed.0.get_untracked();
+        let count = runs.get_untracked();
+        let seeds: Vec<u64> = (0..count).map(|i| first.wrapping_add(i)).collect();
+        let texts: Result<Vec<_>, _> = seeds
+            .iter()
+            .map(|run_seed| with_random_witness(&text, *run_seed))
+            .collect();
+        let texts = match texts {
+            Ok(texts) => texts,
+            Err(message) => {
+                error.set(message);
+                outcome.set(None);
+                return;
+            }
+        };
+        error.update(String::clear);
+        outcome.set(None);
+        let batch = texts
+            .into_iter()
+            .map(|text| (text, params.clone()))
+            .collect();
+        runtime.run_batch(batch, move |outcomes| match outcomes {
+            Ok(outcomes) => {
+                let failures = seeds
+                    .iter()
+                    .zip(outcomes)
+                    .filter_map(|(run_seed, run)| run.result.err().map(|error| (*run_seed, error)))
+                    .collect();
+                outcome.set(Some((count, failures)));
+            }
+            Err(reason) => error.set(reason),
+        });
+    };
+    let apply = move |run_seed: u64| {
+        program.flush_text();
+        let result = program
+            .text
+            .with_untracked(|text| with_random_witness(text, run_seed));
+        match result {
+            Ok(text) => {
+                program.text.set(text);
+                error.update(String::clear);
+            }
+            Err(message) => error.set(message),
+        }
+    };
+
+    let outcome_view = move || {
+        outcome.get().map(|(count, failures)| {
+            let summary = match failures.len() {
+                0 => format!("All {count} runs passed"),
+                failed => format!("{failed} of {count} runs failed"),
+            };
+            let rows = failures
+                .into_iter()
+                .map(|(run_seed, message)| {
+                    view! {
+                        <tr>
+                            <td>{run_seed}</td>
+                            <td class="witness-set-fail" title=message>"fail"</td>
+                            <td>
+                                <button
+                                    class="action-button"
+                                    type="button"
+                                    on:click=move |_| apply(run_seed)
+                                >
+                                    "Use witness"
+                                </button>
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect_view();
+            view! {
+                <p class="tab-description">{summary}</p>
+                <table class="witness-set-matrix">
+                    {rows}
+                </table>
+            }
+        })
+    };
+
+    view! {
+        <div class="random-witness">
+            <h3 class="tab-title">"Random witnesses"</h3>
+            <p class="tab-description">
+                "Run the program with random witness values. "
+                "Run i uses the seed plus i, so the same seed always generates the same values."
+            </p>
+            <div class="network-preset">
+                <label>
+                    "Seed "
+                    <input
+                        class="input"
+                        type="number"
+                        min=0
+                        prop:value=move || seed.0.get().to_string()
+                        on:change=update_seed
+                    />
+                </label>
+                <label>
+                    "Runs "
+                    <input
+                        class="input"
+                        type="number"
+                        min=1
+                        value=DEFAULT_RUNS
+                        on:change=update_runs
+                    />
+                </label>
+                <button
+                    class="button"
+                    type="button"
+                    disabled=move || runtime.running.get()
+                    on:click=run_all
+                >
+                    "Run"
+                </button>
+                <button
+                    class="button"
+                    class:hidden=move || !runtime.running.get()
+                    type="button"
+                    on:click=move |_| runtime.cancel()
+                >
+                    "Cancel"
+                </button>
+                <button
+                    class="button"
+                    type="button"
+                    on:click=move |_| apply(seed.0.get_untracked())
+                >
+                    "Use witness of seed"
+                </button>
+            </div>
+            <ErrorBox error=error />
+            {outcome_view}
+        </div>
+    }
+}
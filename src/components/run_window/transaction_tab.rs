@@ -3,14 +3,21 @@ use std::sync::Arc;
 
 use leptos::{
     component, create_rw_signal, ev, event_target_value, use_context, view, with, Children,
-    IntoView, RwSignal, Signal, SignalGetUntracked, SignalSet, SignalUpdate,
+    CollectView, IntoView, RwSignal, Signal, SignalGet, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith, SignalWithUntracked,
 };
 use simplicity::jet::elements::ElementsEnv;
 use simplicityhl::{elements, simplicity};
 
+use crate::components::app::ActiveRunTab;
+use crate::components::dropdown::Dropdown;
 use crate::components::program_window::Program;
+use crate::components::run_window::input_runs::InputRuns;
+use crate::components::settings::Settings;
+use crate::components::state::LocalStorage;
 use crate::components::string_box::ErrorBox;
-use crate::transaction::TxParams;
+use crate::network::Network;
+use crate::transaction::{self, TxOutput, TxParams};
 
 #[derive(Copy, Clone, Debug)]
 pub struct TxEnv {
@@ -21,9 +28,9 @@ pub struct TxEnv {
 impl TxEnv {
     pub fn new(program: Program, params: TxParams) -> Self {
         let params = create_rw_signal(params);
-        let lazy_cmr = program.lazy_cmr;
+        let lazy_compiled = program.lazy_compiled;
         let lazy_env = Signal::derive(move || {
-            with!(|params, lazy_cmr| match lazy_cmr {
+            with!(|params, lazy_compiled| match &lazy_compiled.cmr {
                 Ok(cmr) => params.tx_env(*cmr),
                 Err(..) => params.tx_env(simplicity::Cmr::unit()),
             })
@@ -32,6 +39,25 @@ impl TxEnv {
     }
 }
 
+/// Maximum number of outputs that can be added to the transaction.
+const MAX_EXTRA_OUTPUTS: usize = 16;
+
+/// Transaction environments that the user saved under a name, in order of saving.
+#[derive(Copy, Clone, Debug)]
+pub struct SavedTxEnvs(pub RwSignal<Vec<(String, TxParams)>>);
+
+impl SavedTxEnvs {
+    pub fn new(envs: Vec<(String, TxParams)>) -> Self {
+        Self(create_rw_signal(envs))
+    }
+}
+
+impl Default for SavedTxEnvs {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
 #[component]
 pub fn TransactionTab() -> impl IntoView {
     let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
@@ -42,6 +68,21 @@ pub fn TransactionTab() -> impl IntoView {
     let fee_parse_error = create_rw_signal(String::new());
     let lock_time_parse_error = create_rw_signal(String::new());
     let sequence_parse_error = create_rw_signal(String::new());
+    let input_count_parse_error = create_rw_signal(String::new());
+    let input_index_parse_error = create_rw_signal(String::new());
+    let raw_transaction_parse_error = create_rw_signal(String::new());
+    let output_count_parse_error = create_rw_signal(String::new());
+
+    // Loading a saved environment changes all fields at once,
+    // so the fields are rendered again with their new values.
+    let revision = create_rw_signal(0usize);
+    let reset = move |error: RwSignal<String>, reset: fn(&mut TxParams, TxParams)| {
+        Box::new(move || {
+            tx_env.params.update(|x| reset(x, TxParams::default()));
+            error.update(String::clear);
+            revision.update(|revision| *revision += 1);
+        }) as Box<dyn Fn()>
+    };
 
     let update_txid = move |e: ev::Event| match elements::Txid::from_str(&event_target_value(&e)) {
         Ok(txid) => {
@@ -66,7 +107,8 @@ pub fn TransactionTab() -> impl IntoView {
     };
     let update_recipient_address = move |e: ev::Event| {
         let s = event_target_value(&e);
-        match elements::Address::parse_with_params(&s, &elements::AddressParams::LIQUID_TESTNET) {
+        let address_params = tx_env.params.with_untracked(|x| x.network.address_params());
+        match elements::Address::parse_with_params(&s, address_params) {
             Ok(address) => {
                 tx_env
                     .params
@@ -95,6 +137,13 @@ pub fn TransactionTab() -> impl IntoView {
         }
         Err(error) => lock_time_parse_error.set(error.to_string()),
     };
+    let networks = Network::ALL.map(Network::name);
+    let select_network = move |name: &'static str| {
+        if let Some(network) = Network::from_name(name) {
+            tx_env.params.update(|x| x.network = network);
+        }
+    };
+    let network_name = move || tx_env.params.with(|x| x.network.name());
     let update_sequence = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
         Ok(sequence) => {
             let sequence = elements::Sequence::from_consensus(sequence);
@@ -104,83 +153,506 @@ pub fn TransactionTab() -> impl IntoView {
         Err(error) => sequence_parse_error.set(error.to_string()),
     };
 
+    let update_input_count = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(0) => input_count_parse_error.set("There must be at least one input".to_string()),
+        Ok(input_count) => {
+            tx_env.params.update(|x| x.input_count = input_count);
+            input_count_parse_error.update(String::clear);
+        }
+        Err(error) => input_count_parse_error.set(error.to_string()),
+    };
+    let update_input_index = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(index) if tx_env.params.with_untracked(|x| x.inputs() <= index) => {
+            input_index_parse_error
+                .set("The index must be less than the number of inputs".to_string());
+        }
+        Ok(input_index) => {
+            tx_env.params.update(|x| x.input_index = input_index);
+            input_index_parse_error.update(String::clear);
+        }
+        Err(error) => input_index_parse_error.set(error.to_string()),
+    };
+    let update_raw_transaction = move |e: ev::Event| {
+        let hex = event_target_value(&e);
+        if hex.trim().is_empty() {
+            tx_env.params.update(|x| x.raw_transaction = None);
+            raw_transaction_parse_error.update(String::clear);
+            return;
+        }
+        match transaction::parse_transaction_hex(&hex) {
+            Ok(tx) => {
+                tx_env
+                    .params
+                    .update(|x| x.raw_transaction = Some(Arc::new(tx)));
+                raw_transaction_parse_error.update(String::clear);
+            }
+            Err(error) => raw_transaction_parse_error.set(error),
+        }
+    };
+    let update_output_count = move |e: ev::Event| match event_target_value(&e).parse::<usize>() {
+        Ok(count) if MAX_EXTRA_OUTPUTS < count => output_count_parse_error.set(format!(
+            "There can be at most {MAX_EXTRA_OUTPUTS} extra outputs"
+        )),
+        Ok(count) => {
+            tx_env.params.update(|x| {
+                x.extra_outputs.resize(
+                    count,
+                    TxOutput {
+                        value: 0,
+                        asset: None,
+                    },
+                );
+            });
+            output_count_parse_error.update(String::clear);
+            revision.update(|revision| *revision += 1);
+        }
+        Err(error) => output_count_parse_error.set(error.to_string()),
+    };
+    let has_raw_transaction = move || tx_env.params.with(|x| x.raw_transaction.is_some());
+    // The index may be out of bounds after the number of inputs was lowered
+    let input_index_error = Signal::derive(move || {
+        let parse_error = input_index_parse_error.get();
+        match parse_error.is_empty() {
+            true => tx_env
+                .params
+                .with(TxParams::spent_input_index)
+                .err()
+                .unwrap_or_default(),
+            false => parse_error,
+        }
+    });
+    let sighash_all = move || {
+        tx_env
+            .lazy_env
+            .with(|env| env.c_tx_env().sighash_all().to_string())
+    };
+
+    let current_input = move || {
+        tx_env.lazy_env.with(|env| {
+            env.tx()
+                .input
+                .get(env.ix() as usize) // safety: 32-bit machine or higher
+                .map(|input| (input.previous_output, input.sequence))
+        })
+    };
+    let current_outpoint = move || match current_input() {
+        Some((outpoint, _)) => format!("{}:{}", outpoint.txid, outpoint.vout),
+        None => "(no input)".to_string(),
+    };
+    let current_sequence = move || match current_input() {
+        Some((_, sequence)) => format!("{} (0x{:08x})", sequence, sequence.to_consensus_u32()),
+        None => "(no input)".to_string(),
+    };
+
     view! {
         <div class="tab-content transaction-tab">
             <p class="tab-description">
                 "Only a limited number of fields are available. "
                 "More customization will follow in future updates."
             </p>
-            <Section name="UTXO">
-                <Item name="txid" error=txid_parse_error>
-                    <input
-                        class="input"
-                        type="text"
-                        on:input=update_txid
-                        value=tx_env.params.get_untracked().txid.to_string()
-                    />
-                </Item>
-                <Item name="vout" error=vout_parse_error>
-                    <input
-                        class="input"
-                        type="number"
-                        min=0
-                        on:input=update_vout
-                        value=tx_env.params.get_untracked().vout
-                    />
-                </Item>
-                <Item name="value (sats)" error=value_in_parse_error>
-                    <input
-                        class="input"
-                        type="number"
-                        min=0
-                        on:input=update_value_in
-                        value=tx_env.params.get_untracked().value_in
-                    />
-                </Item>
-            </Section>
-            <Section name="Transaction">
-                <Item name="recipient address" error=recipient_address_parse_error>
-                    <input
-                        class="input"
-                        type="text"
-                        on:input=update_recipient_address
-                        value=tx_env.params.get_untracked().recipient_address.as_ref().map(ToString::to_string).unwrap_or_default()
-                        placeholder="(Send back to faucet)"
-                    />
-                </Item>
-                <Item name="fee (sats)" error=fee_parse_error>
-                    <input
-                        class="input"
-                        type="number"
-                        on:input=update_fee
-                        min=0
-                        value=10000
-                        value=tx_env.params.get_untracked().fee
-                    />
-                </Item>
-                <Item name="nLockTime" error=lock_time_parse_error>
-                    <input
-                        class="input"
-                        type="number"
-                        on:input=update_lock_time
-                        min=0
-                        value=tx_env.params.get_untracked().lock_time.to_string()
-                    />
-                </Item>
-                <Item name="nSequence" error=sequence_parse_error>
-                    <input
-                        class="input"
-                        type="number"
-                        on:input=update_sequence
-                        min=0
-                        value=tx_env.params.get_untracked().sequence.to_string()
-                    />
+            <Section name="Environment">
+                <Item name="network" error=Signal::derive(String::new)>
+                    <div class="network-preset">
+                        <span class="network-preset-name">{network_name}</span>
+                        <Dropdown name="Presets" options=networks select_option=select_network />
+                    </div>
                 </Item>
+                <SavedEnvironments revision=revision />
             </Section>
+            {move || {
+                revision.track();
+                view! {
+                    <Section name="UTXO">
+                        <Item
+                            name="txid"
+                            error=txid_parse_error
+                            reset=reset(txid_parse_error, |x, d| x.txid = d.txid)
+                        >
+                            <input
+                                class="input"
+                                type="text"
+                                on:input=update_txid
+                                value=tx_env.params.get_untracked().txid.to_string()
+                            />
+                        </Item>
+                        <Item
+                            name="vout"
+                            error=vout_parse_error
+                            reset=reset(vout_parse_error, |x, d| x.vout = d.vout)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                min=0
+                                on:input=update_vout
+                                value=tx_env.params.get_untracked().vout
+                            />
+                        </Item>
+                        <Item
+                            name="value (sats)"
+                            error=value_in_parse_error
+                            reset=reset(value_in_parse_error, |x, d| x.value_in = d.value_in)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                min=0
+                                on:input=update_value_in
+                                value=tx_env.params.get_untracked().value_in
+                            />
+                        </Item>
+                    </Section>
+                    <Section name="Transaction">
+                        <Item
+                            name="recipient address"
+                            error=recipient_address_parse_error
+                            reset=reset(recipient_address_parse_error, |x, d| {
+                                x.recipient_address = d.recipient_address;
+                            })
+                        >
+                            <input
+                                class="input"
+                                type="text"
+                                on:input=update_recipient_address
+                                value=tx_env.params.get_untracked().recipient_address.as_ref().map(ToString::to_string).unwrap_or_default()
+                                placeholder="(Send back to faucet)"
+                            />
+                        </Item>
+                        <Item
+                            name="fee (sats)"
+                            error=fee_parse_error
+                            reset=reset(fee_parse_error, |x, d| x.fee = d.fee)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:input=update_fee
+                                min=0
+                                value=10000
+                                value=tx_env.params.get_untracked().fee
+                            />
+                        </Item>
+                        <Item
+                            name="nLockTime"
+                            error=lock_time_parse_error
+                            reset=reset(lock_time_parse_error, |x, d| x.lock_time = d.lock_time)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:input=update_lock_time
+                                min=0
+                                value=tx_env.params.get_untracked().lock_time.to_string()
+                            />
+                        </Item>
+                        <Item
+                            name="nSequence"
+                            error=sequence_parse_error
+                            reset=reset(sequence_parse_error, |x, d| x.sequence = d.sequence)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:input=update_sequence
+                                min=0
+                                value=tx_env.params.get_untracked().sequence.to_string()
+                            />
+                        </Item>
+                    </Section>
+                    <Section name="Outputs">
+                        <Item
+                            name="extra outputs"
+                            error=output_count_parse_error
+                            reset=reset(output_count_parse_error, |x, d| {
+                                x.extra_outputs = d.extra_outputs;
+                            })
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:change=update_output_count
+                                min=0
+                                max=MAX_EXTRA_OUTPUTS
+                                value=tx_env.params.with_untracked(|x| x.extra_outputs.len())
+                            />
+                        </Item>
+                        {(0..tx_env.params.with_untracked(|x| x.extra_outputs.len()))
+                            .map(|index| view! { <OutputItem index=index /> })
+                            .collect_view()}
+                        <p class="tab-description">
+                            "Extra outputs pay the recipient and come before the fee output. "
+                            "Outputs without an asset id use the bitcoin asset of the network."
+                        </p>
+                    </Section>
+                    <Section name="Raw transaction">
+                        <Item
+                            name="transaction hex"
+                            error=raw_transaction_parse_error
+                            reset=reset(raw_transaction_parse_error, |x, d| {
+                                x.raw_transaction = d.raw_transaction;
+                            })
+                        >
+                            <textarea
+                                class="input raw-transaction"
+                                rows="4"
+                                spellcheck="false"
+                                placeholder="(Build the transaction from the fields above)"
+                                on:change=update_raw_transaction
+                            >
+                                {tx_env
+                                    .params
+                                    .get_untracked()
+                                    .raw_transaction
+                                    .as_deref()
+                                    .map(transaction::transaction_hex)
+                                    .unwrap_or_default()}
+                            </textarea>
+                        </Item>
+                        <p class="tab-description" class:hidden=move || !has_raw_transaction()>
+                            "The pasted transaction replaces the fields of the transaction "
+                            "and the number of inputs. "
+                            "Each input spends a UTXO of the program with the value above."
+                        </p>
+                    </Section>
+                    <Section name="Spending input">
+                        <Item
+                            name="number of inputs"
+                            error=input_count_parse_error
+                            reset=reset(input_count_parse_error, |x, d| x.input_count = d.input_count)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:input=update_input_count
+                                min=1
+                                value=tx_env.params.get_untracked().input_count
+                            />
+                        </Item>
+                        <Item
+                            name="spent input"
+                            error=input_index_error
+                            reset=reset(input_index_parse_error, |x, d| x.input_index = d.input_index)
+                        >
+                            <input
+                                class="input"
+                                type="number"
+                                on:input=update_input_index
+                                min=0
+                                value=tx_env.params.get_untracked().input_index
+                            />
+                        </Item>
+                        <Item name="outpoint" error=Signal::derive(String::new)>
+                            <span class="transaction-display-value">{current_outpoint}</span>
+                        </Item>
+                        <Item name="sequence" error=Signal::derive(String::new)>
+                            <span class="transaction-display-value">{current_sequence}</span>
+                        </Item>
+                        <Item name="sig_all_hash" error=Signal::derive(String::new)>
+                            <span class="transaction-display-value">{sighash_all}</span>
+                        </Item>
+                    </Section>
+                }
+            }}
+            <InputRuns />
+            <p class="tab-description">
+                "Simplicity has no sighash flags: "
+                "jet::sig_all_hash commits to the entire transaction and to the spent input. "
+                "Programs that sign less can build their own message from the other transaction jets."
+            </p>
         </div>
     }
 }
 
+/// One line that shows the network and spent input of the environment that runs use.
+///
+/// Clicking the line opens the transaction tab.
+#[component]
+pub fn EnvStatus() -> impl IntoView {
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let active_run_tab =
+        use_context::<ActiveRunTab>().expect("active run tab should exist in context");
+    let status = move || {
+        // Runs with the fresh environment ignore the edited parameters
+        let fresh = settings.fresh_environment();
+        let describe = |params: &TxParams| {
+            let raw = match params.raw_transaction.is_some() {
+                true => ", pasted transaction",
+                false => "",
+            };
+            format!(
+                "env: {}, input {} of {}{raw}",
+                params.network.name(),
+                params.input_index,
+                params.inputs(),
+            )
+        };
+        match fresh {
+            true => format!("{} (default)", describe(&TxParams::default())),
+            false => tx_env.params.with(describe),
+        }
+    };
+
+    view! {
+        <button
+            class="env-status"
+            type="button"
+            title="Open the transaction environment"
+            on:click=move |_| active_run_tab.0.set("Transaction")
+        >
+            <i class="fas fa-globe"></i>
+            " "
+            {status}
+        </button>
+    }
+}
+
+/// Save the current environment under a name and load saved environments.
+#[component]
+fn SavedEnvironments(revision: RwSignal<usize>) -> impl IntoView {
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let saved = use_context::<SavedTxEnvs>().expect("saved environments should exist in context");
+    let name = create_rw_signal(String::new());
+    let error = create_rw_signal(String::new());
+
+    let save = move |_| {
+        let name = name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            error.set("Enter a name for the environment".to_string());
+            return;
+        }
+        error.update(String::clear);
+        let params = tx_env.params.get_untracked();
+        saved.0.update(
+            |envs| match envs.iter_mut().find(|(saved, _)| *saved == name) {
+                Some((_, saved_params)) => *saved_params = params,
+                None => envs.push((name, params)),
+            },
+        );
+        saved.store_in_storage();
+    };
+    let load = move |index: usize| {
+        let Some((_, params)) = saved.0.with_untracked(|envs| envs.get(index).cloned()) else {
+            return;
+        };
+        tx_env.params.set(params);
+        revision.update(|revision| *revision += 1);
+    };
+    let delete = move |index: usize| {
+        saved.0.update(|envs| {
+            if index < envs.len() {
+                envs.remove(index);
+            }
+        });
+        saved.store_in_storage();
+    };
+    let options = move || {
+        saved.0.with(|envs| {
+            envs.iter()
+                .enumerate()
+                .map(|(index, (name, _))| {
+                    view! {
+                        <div class="saved-env-option">
+                            <button class="action-button" on:click=move |_| load(index)>
+                                {name.clone()}
+                            </button>
+                            <button
+                                class="action-button saved-env-delete"
+                                title="Delete"
+                                on:click=move |_| delete(index)
+                            >
+                                <i class="fas fa-trash"></i>
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let no_saved = move || saved.0.with(Vec::is_empty);
+
+    view! {
+        <Item name="saved environments" error=error>
+            <div class="network-preset">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Name"
+                    on:input=move |event| name.set(event_target_value(&event))
+                />
+                <button class="button" type="button" on:click=save>"Save"</button>
+                <div class="dropdown" class:hidden=no_saved>
+                    <button class="button dropdown-button">"Load"</button>
+                    <div class="dropdown-content">{options}</div>
+                </div>
+            </div>
+        </Item>
+    }
+}
+
+/// Value and asset of the extra output at `index`.
+#[component]
+fn OutputItem(index: usize) -> impl IntoView {
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let value_parse_error = create_rw_signal(String::new());
+    let asset_parse_error = create_rw_signal(String::new());
+    let output = tx_env
+        .params
+        .with_untracked(|x| x.extra_outputs.get(index).copied());
+    let update_output = move |f: &dyn Fn(&mut TxOutput)| {
+        tx_env.params.update(|x| {
+            if let Some(output) = x.extra_outputs.get_mut(index) {
+                f(output);
+            }
+        });
+    };
+
+    let update_value = move |e: ev::Event| match event_target_value(&e).parse::<u64>() {
+        Ok(value) => {
+            update_output(&|output| output.value = value);
+            value_parse_error.update(String::clear);
+        }
+        Err(error) => value_parse_error.set(error.to_string()),
+    };
+    let update_asset = move |e: ev::Event| {
+        let s = event_target_value(&e);
+        if s.trim().is_empty() {
+            update_output(&|output| output.asset = None);
+            asset_parse_error.update(String::clear);
+            return;
+        }
+        match elements::AssetId::from_str(s.trim()) {
+            Ok(asset) => {
+                update_output(&|output| output.asset = Some(asset));
+                asset_parse_error.update(String::clear);
+            }
+            Err(error) => asset_parse_error.set(format!("Invalid asset id: {error}")),
+        }
+    };
+
+    view! {
+        <Item name=format!("output {} value (sats)", index + 1) error=value_parse_error>
+            <input
+                class="input"
+                type="number"
+                min=0
+                on:input=update_value
+                value=output.map(|output| output.value)
+            />
+        </Item>
+        <Item name=format!("output {} asset", index + 1) error=asset_parse_error>
+            <input
+                class="input"
+                type="text"
+                spellcheck="false"
+                on:input=update_asset
+                value=output.and_then(|output| output.asset).map(|asset| asset.to_string())
+                placeholder="(Bitcoin)"
+            />
+        </Item>
+    }
+}
+
 #[component]
 fn Section(#[prop(into)] name: String, children: Children) -> impl IntoView {
     view! {
@@ -193,10 +665,14 @@ fn Section(#[prop(into)] name: String, children: Children) -> impl IntoView {
     }
 }
 
+/// Labeled field of the environment.
+///
+/// Fields that can be `reset` have a button that sets them to their default value.
 #[component]
 fn Item(
     #[prop(into)] name: String,
     #[prop(into)] error: Signal<String>,
+    #[prop(optional)] reset: Option<Box<dyn Fn()>>,
     children: Children,
 ) -> impl IntoView {
     view! {
@@ -205,6 +681,16 @@ fn Item(
                 {name}
             </div>
             {children()}
+            {reset.map(|reset| view! {
+                <button
+                    class="action-button reset-field"
+                    type="button"
+                    title="Reset to default"
+                    on:click=move |_| reset()
+                >
+                    <i class="fas fa-undo"></i>
+                </button>
+            })}
         </div>
         <ErrorBox error=error />
     }
@@ -0,0 +1,106 @@
+use leptos::{
+    component, create_rw_signal, event_target_value, view, CollectView, IntoView, Signal,
+    SignalGet, SignalGetUntracked, SignalSet,
+};
+
+use crate::components::string_box::ErrorBox;
+use crate::util::value::{self, ValueInfo};
+
+/// Scratchpad for constructing a value and viewing its Simplicity encoding.
+#[component]
+pub fn ValueTab() -> impl IntoView {
+    let ty = create_rw_signal("(u8, bool)".to_string());
+    let value = create_rw_signal("(5, true)".to_string());
+    let hex = create_rw_signal("0x0580".to_string());
+    let from_hex = create_rw_signal(false);
+    let info = move || match from_hex.get() {
+        false => value::inspect_value(&ty.get(), &value.get()),
+        true => value::decode_hex(&ty.get(), &hex.get()),
+    };
+    let input = move || match from_hex.get() {
+        false => value,
+        true => hex,
+    };
+    let error = move || info().err().unwrap_or_default();
+
+    view! {
+        <div class="tab-content value-tab">
+            <p class="tab-description">
+                "Write a SimplicityHL value and its type to see how Simplicity represents it, \
+                or decode a value from the hex of its compact bits."
+            </p>
+            <div class="button-row is-small">
+                <button
+                    class="flat-button"
+                    class:bordered=from_hex
+                    type="button"
+                    on:click=move |_| from_hex.set(false)
+                >
+                    "From value"
+                </button>
+                <button
+                    class="flat-button"
+                    class:bordered=move || !from_hex.get()
+                    type="button"
+                    on:click=move |_| from_hex.set(true)
+                >
+                    "From hex"
+                </button>
+            </div>
+            <div class="value-input-row">
+                <label class="display-row-label">"Type"</label>
+                <input
+                    class="input"
+                    type="text"
+                    spellcheck="false"
+                    value=ty.get_untracked()
+                    on:input=move |event| ty.set(event_target_value(&event))
+                />
+            </div>
+            <div class="value-input-row">
+                <label class="display-row-label">
+                    {move || match from_hex.get() {
+                        false => "Value",
+                        true => "Hex",
+                    }}
+                </label>
+                <input
+                    class="input"
+                    type="text"
+                    spellcheck="false"
+                    prop:value=move || input().get()
+                    on:input=move |event| input().set(event_target_value(&event))
+                />
+            </div>
+            <ErrorBox error=Signal::derive(error) />
+            {move || info().ok().map(|info| view! { <ValueDetails info /> })}
+        </div>
+    }
+}
+
+#[component]
+fn ValueDetails(info: ValueInfo) -> impl IntoView {
+    let rows = [
+        ("SimplicityHL value", info.value),
+        ("SimplicityHL type", info.ty),
+        ("Simplicity value", info.simplicity_value),
+        ("Simplicity type", info.simplicity_type),
+        ("Compact bits", info.compact_bits),
+        ("Padded bits", info.padded_bits),
+        ("Hex", info.hex),
+    ];
+
+    view! {
+        <table class="value-details">
+            {rows
+                .into_iter()
+                .map(|(name, content)| view! {
+                    <tr>
+                        <th>{name}</th>
+                        <td>{content}</td>
+                    </tr>
+                })
+                .collect_view()}
+        </table>
+    }
+}
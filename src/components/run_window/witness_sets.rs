@@ -0,0 +1,230 @@
+use leptos::{
+    component, create_rw_signal, event_target_value, use_context, view, CollectView, IntoView,
+    RwSignal, Show, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked,
+};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::components::run_window::{SavedTxEnvs, TxEnv};
+use crate::components::state::LocalStorage;
+use crate::components::string_box::ErrorBox;
+use crate::util::source::{self, ModuleKind};
+
+/// Witness modules that the user saved under a name, to run the program against each of them.
+#[derive(Copy, Clone, Debug)]
+pub struct WitnessSets(pub RwSignal<Vec<(String, String)>>);
+
+impl WitnessSets {
+    pub fn new(sets: Vec<(String, String)>) -> Self {
+        Self(create_rw_signal(sets))
+    }
+}
+
+impl Default for WitnessSets {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+/// Outcomes of running the program with each witness set in each environment.
+#[derive(Clone, Debug)]
+struct Matrix {
+    /// Names of the environments, one per column.
+    columns: Vec<String>,
+    /// Names of the witness sets with the outcome in each environment, one per row.
+    rows: Vec<(String, Vec<Result<(), String>>)>,
+}
+
+/// Saved witness sets with a matrix of the runs of the program against each of them.
+///
+/// The columns of the matrix are the current transaction environment
+/// and the saved environments.
+#[component]
+pub fn WitnessSetMatrix() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let sets = use_context::<WitnessSets>().expect("witness sets should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let saved_envs =
+        use_context::<SavedTxEnvs>().expect("saved environments should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let name = create_rw_signal(String::new());
+    let error = create_rw_signal(String::new());
+    let matrix = create_rw_signal(Option::<Matrix>::None);
+
+    let save = move |_| {
+        let name = name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            error.set("Enter a name for the witness set".to_string());
+            return;
+        }
+        program.flush_text();
+        let Some(module) = program.text.with_untracked(|text| {
+            source::module_span(text, ModuleKind::Witness).map(|span| text[span].to_string())
+        }) else {
+            error.set("The program has no witness module".to_string());
+            return;
+        };
+        error.update(String::clear);
+        sets.0.update(
+            |sets| match sets.iter_mut().find(|(saved, _)| *saved == name) {
+                Some((_, saved_module)) => *saved_module = module,
+                None => sets.push((name, module)),
+            },
+        );
+        sets.store_in_storage();
+    };
+    let load = move |index: usize| {
+        let Some((_, module)) = sets.0.with_untracked(|sets| sets.get(index).cloned()) else {
+            return;
+        };
+        program.text.update(|text| {
+            *text = source::replace_module(text, ModuleKind::Witness, &module);
+        });
+    };
+    let delete = move |index: usize| {
+        sets.0.update(|sets| {
+            if index < sets.len() {
+                sets.remove(index);
+            }
+        });
+        sets.store_in_storage();
+        matrix.set(None);
+    };
+    let run_all = move |_| {
+        program.flush_text();
+        let text = program.text.get_untracked();
+        let mut envs = vec![("Current".to_string(), tx_env.params.get_untracked())];
+        envs.extend(saved_envs.0.get_untracked());
+        let (names, batch): (Vec<_>, Vec<_>) = sets.0.with_untracked(|sets| {
+            let batch = sets
+                .iter()
+                .flat_map(|(_, module)| {
+                    let text = source::replace_module(&text, ModuleKind::Witness, module);
+                    envs.iter()
+                        .map(move |(_, params)| (text.clone(), params.clone()))
+                })
+                .collect();
+            (sets.iter().map(|(name, _)| name.clone()).collect(), batch)
+        });
+        let columns: Vec<String> = envs.into_iter().map(|(name, _)| name).collect();
+        error.update(String::clear);
+        matrix.set(None);
+        runtime.run_batch(batch, move |outcomes| match outcomes {
+            Ok(outcomes) => {
+                let outcomes: Vec<_> = outcomes.into_iter().map(|run| run.result).collect();
+                let rows = names
+                    .into_iter()
+                    .zip(outcomes.chunks(columns.len()))
+                    .map(|(name, outcomes)| (name, outcomes.to_vec()))
+                    .collect();
+                matrix.set(Some(Matrix { columns, rows }));
+            }
+            Err(reason) => error.set(reason),
+        });
+    };
+
+    let set_rows = move || {
+        sets.0.with(|sets| {
+            sets.iter()
+                .enumerate()
+                .map(|(index, (name, _))| {
+                    view! {
+                        <div class="saved-env-option">
+                            <button class="action-button" type="button" on:click=move |_| load(index)>
+                                {name.clone()}
+                            </button>
+                            <button
+                                class="action-button saved-env-delete"
+                                type="button"
+                                title="Delete"
+                                on:click=move |_| delete(index)
+                            >
+                                <i class="fas fa-trash"></i>
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let matrix_view = move || {
+        matrix.get().map(|matrix| {
+            let header = matrix
+                .columns
+                .iter()
+                .map(|column| view! { <th>{column.clone()}</th> })
+                .collect_view();
+            let rows = matrix
+                .rows
+                .into_iter()
+                .map(|(name, outcomes)| {
+                    let cells = outcomes
+                        .into_iter()
+                        .map(|outcome| match outcome {
+                            Ok(()) => view! { <td class="witness-set-pass">"pass"</td> },
+                            Err(error) => {
+                                view! { <td class="witness-set-fail" title=error>"fail"</td> }
+                            }
+                        })
+                        .collect_view();
+                    view! {
+                        <tr>
+                            <td>{name}</td>
+                            {cells}
+                        </tr>
+                    }
+                })
+                .collect_view();
+            view! {
+                <table class="witness-set-matrix">
+                    <tr>
+                        <th>"Witness set"</th>
+                        {header}
+                    </tr>
+                    {rows}
+                </table>
+            }
+        })
+    };
+    let no_sets = move || sets.0.with(Vec::is_empty);
+
+    view! {
+        <div class="witness-sets">
+            <h3 class="tab-title">"Witness sets"</h3>
+            <p class="tab-description">
+                "Save the witness module under a name to run the program against all saved sets, "
+                "in the current and in each saved transaction environment."
+            </p>
+            <div class="network-preset">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Name"
+                    on:input=move |event| name.set(event_target_value(&event))
+                />
+                <button class="button" type="button" on:click=save>"Save witness set"</button>
+                <button
+                    class="button"
+                    type="button"
+                    on:click=run_all
+                    disabled=move || no_sets() || runtime.running.get()
+                >
+                    "Run all"
+                </button>
+                <button
+                    class="button"
+                    class:hidden=move || !runtime.running.get()
+                    type="button"
+                    on:click=move |_| runtime.cancel()
+                >
+                    "Cancel"
+                </button>
+            </div>
+            <ErrorBox error=error />
+            {set_rows}
+            <Show when=move || !no_sets()>
+                {matrix_view}
+            </Show>
+        </div>
+    }
+}
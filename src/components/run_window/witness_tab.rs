@@ -0,0 +1,277 @@
+use leptos::wasm_bindgen::JsValue;
+use leptos::{
+    component, create_memo, create_rw_signal, ev, event_target_checked, event_target_value,
+    spawn_local, use_context, view, CollectView, IntoView, Show, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked,
+};
+use simplicityhl::parse::ParseFromStr;
+
+use crate::components::program_window::Program;
+use crate::components::run_window::random_witness::RandomWitness;
+use crate::components::run_window::witness_sets::WitnessSetMatrix;
+use crate::components::string_box::ErrorBox;
+use crate::components::toast::{ToastKind, Toasts};
+use crate::util::source::{self, ModuleKind};
+use crate::util::witness::{self, InputKind};
+
+/// Return the names and types of the witnesses or parameters that the program uses,
+/// sorted by name.
+pub fn declared_types(
+    text: &str,
+    kind: ModuleKind,
+) -> Result<Vec<(String, String, InputKind)>, String> {
+    let parsed = simplicityhl::parse::Program::parse_from_str(text).map_err(|e| e.to_string())?;
+    let program = simplicityhl::ast::Program::analyze(&parsed).map_err(|e| e.to_string())?;
+    let describe = |(name, ty): (&simplicityhl::str::WitnessName, &simplicityhl::ResolvedType)| {
+        (name.to_string(), ty.to_string(), InputKind::of(ty))
+    };
+    let mut types: Vec<_> = match kind {
+        ModuleKind::Witness => program.witness_types().iter().map(describe).collect(),
+        ModuleKind::Param => program.parameters().iter().map(describe).collect(),
+    };
+    types.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(types)
+}
+
+/// Return the value expression that is currently assigned to the witness or parameter `name`.
+fn current_value(text: &str, kind: ModuleKind, name: &str) -> String {
+    source::declarations(text, kind)
+        .into_iter()
+        .find(|declaration| declaration.name == name)
+        .and_then(|declaration| {
+            witness::declared_value(&text[declaration.span]).map(str::to_string)
+        })
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn WitnessTab() -> impl IntoView {
+    view! {
+        <div class="tab-content witness-tab">
+            <p class="tab-description">
+                "Each witness that the program uses has its own field. "
+                "Changes are written into the witness module of the program, "
+                "and notes are written as comments above their witnesses."
+            </p>
+            <DeclarationForm module=ModuleKind::Witness />
+            <PasteWitness />
+            <WitnessSetMatrix />
+            <RandomWitness />
+        </div>
+    }
+}
+
+/// Read the text of the clipboard, or return `None` if the browser does not allow it.
+async fn read_clipboard() -> Option<String> {
+    let clipboard = web_sys::window()?.navigator().clipboard();
+    let js: &JsValue = clipboard.as_ref();
+    if js.is_undefined() || js.is_null() {
+        return None;
+    }
+    let text = wasm_bindgen_futures::JsFuture::from(clipboard.read_text())
+        .await
+        .ok()?;
+    text.as_string()
+}
+
+/// Button that reads witness values from the clipboard into the witness module.
+///
+/// If the browser does not allow reading the clipboard,
+/// then the user can paste the witness values into a text field instead.
+#[component]
+fn PasteWitness() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let error = create_rw_signal(String::new());
+    let manual = create_rw_signal(false);
+    let manual_text = create_rw_signal(String::new());
+
+    let apply = move |pasted: &str| {
+        program.flush_text();
+        let result = program.text.with_untracked(|text| {
+            let declared: Vec<_> = declared_types(text, ModuleKind::Witness)?
+                .into_iter()
+                .map(|(name, ty, _)| (name, ty))
+                .collect();
+            let values = witness::parse_pasted(pasted, &declared)?;
+            let mut updated = text.clone();
+            for (name, value) in &values {
+                let (_, ty) = declared
+                    .iter()
+                    .find(|(declared, _)| declared == name)
+                    .expect("pasted witnesses are declared");
+                updated = source::set_declaration(&updated, ModuleKind::Witness, name, ty, value);
+            }
+            Ok::<_, String>((updated, values.len()))
+        });
+        match result {
+            Ok((text, count)) => {
+                program.text.set(text);
+                error.update(String::clear);
+                manual.set(false);
+                manual_text.update(String::clear);
+                toasts.push(ToastKind::Success, format!("Pasted {count} witness values"));
+            }
+            Err(message) => error.set(message),
+        }
+    };
+    let paste = move |_| {
+        spawn_local(async move {
+            match read_clipboard().await {
+                Some(pasted) => apply(&pasted),
+                // Permission was denied or the clipboard API is unavailable
+                None => manual.set(true),
+            }
+        });
+    };
+
+    view! {
+        <div class="paste-witness">
+            <button class="button" type="button" on:click=paste>
+                <i class="fas fa-paste"></i>
+                " Paste witness"
+            </button>
+            <Show when=move || manual.get()>
+                <p class="tab-description">
+                    "The browser did not allow reading the clipboard. "
+                    "Paste the witness module or its JSON here:"
+                </p>
+                <textarea
+                    class="input paste-witness-text"
+                    rows="5"
+                    spellcheck="false"
+                    prop:value=manual_text
+                    on:input=move |event| manual_text.set(event_target_value(&event))
+                ></textarea>
+                <button
+                    class="button"
+                    type="button"
+                    on:click=move |_| apply(&manual_text.get_untracked())
+                >
+                    "Apply"
+                </button>
+            </Show>
+            <ErrorBox error=error />
+        </div>
+    }
+}
+
+/// One input field for each witness or parameter of the program.
+#[component]
+pub fn DeclarationForm(module: ModuleKind) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    // Only rerender the form if the set of declarations changes,
+    // so that typing into a field keeps its focus.
+    let types = create_memo(move |_| program.text.with(|text| declared_types(text, module)));
+    let values = match module {
+        ModuleKind::Witness => "witness values",
+        ModuleKind::Param => "parameters",
+    };
+
+    move || match types.get() {
+        Err(..) => view! {
+            <p class="tab-description">
+                {format!("Fix the errors in the program to edit its {values}.")}
+            </p>
+        }
+        .into_view(),
+        Ok(types) if types.is_empty() => view! {
+            <p class="tab-description">{format!("The program uses no {values}.")}</p>
+        }
+        .into_view(),
+        Ok(types) => types
+            .into_iter()
+            .map(|(name, ty, kind)| view! { <DeclarationInput module name ty kind /> })
+            .collect_view(),
+    }
+}
+
+#[component]
+fn DeclarationInput(
+    module: ModuleKind,
+    name: String,
+    ty: String,
+    kind: InputKind,
+) -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let value = program
+        .text
+        .with_untracked(|text| current_value(text, module, &name));
+    let note = program
+        .text
+        .with_untracked(|text| source::declaration_note(text, module, &name))
+        .unwrap_or_default();
+    let set_note = {
+        let name = name.clone();
+        move |e: ev::Event| {
+            let note = event_target_value(&e);
+            program.text.update(|text| {
+                *text = source::set_declaration_note(text, module, &name, &note);
+            });
+        }
+    };
+    let label = format!("{name}: {ty}");
+    let error = create_rw_signal(Option::<String>::None);
+    let set_value = {
+        let name = name.clone();
+        move |expression: String| {
+            if let Err(message) = witness::check_value(&expression, &ty) {
+                error.set(Some(message));
+                return;
+            }
+            error.set(None);
+            program.text.update(|text| {
+                *text = source::set_declaration(text, module, &name, &ty, &expression);
+            });
+        }
+    };
+
+    let input = match kind {
+        InputKind::Boolean => {
+            let set_value = set_value.clone();
+            let update = move |e: ev::Event| set_value(event_target_checked(&e).to_string());
+            view! {
+                <input type="checkbox" checked=value == "true" on:change=update />
+            }
+        }
+        _ => {
+            let update = move |e: ev::Event| {
+                let input = event_target_value(&e);
+                if !input.trim().is_empty() {
+                    set_value(kind.to_expression(&input));
+                }
+            };
+            let input_type = match kind {
+                InputKind::Integer => "number",
+                _ => "text",
+            };
+            view! {
+                <input
+                    class="input"
+                    type=input_type
+                    min=0
+                    spellcheck="false"
+                    placeholder=kind.placeholder()
+                    value=value
+                    on:input=update
+                />
+            }
+        }
+    };
+
+    view! {
+        <div class="witness-input-row">
+            <label class="display-row-label">{label}</label>
+            {input}
+            <input
+                class="input witness-note"
+                type="text"
+                placeholder="Note"
+                title="Note that is kept as a comment above the declaration"
+                value=note
+                on:change=set_note
+            />
+        </div>
+        {move || error.get().map(|message| view! { <div class="witness-input-error">{message}</div> })}
+    }
+}
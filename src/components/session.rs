@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_rw_signal, ev, spawn_local, store_value, use_context, view, IntoView,
+    SignalGetUntracked, SignalSet, SignalUpdate, StoredValue,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::components::program_window::{Program, ProgramTabs, SavedPrograms};
+use crate::components::run_window::{
+    HashCount, KeyCount, SavedTxEnvs, TxEnv, WitnessSeed, WitnessSets,
+};
+use crate::components::settings::Settings;
+use crate::components::state::LocalStorage;
+use crate::components::string_box::ErrorBox;
+use crate::download;
+use crate::transaction::TxParams;
+use crate::util::SigningKeys;
+
+/// Version of the format of exported sessions.
+const SESSION_VERSION: u32 = 1;
+
+/// Entire state of the IDE, as the entries that it keeps in the browser's storage.
+#[derive(Debug, Serialize, Deserialize)]
+struct Session {
+    version: u32,
+    storage: BTreeMap<String, String>,
+}
+
+impl Session {
+    /// Add the entries of `object` to the session.
+    fn insert<T: LocalStorage>(&mut self, object: &T) {
+        let entries = T::keys().map(str::to_string).zip(object.to_values());
+        self.storage.extend(entries);
+    }
+
+    /// Read an object from the entries of the session.
+    ///
+    /// Return `None` if the session does not contain the object.
+    fn get<T: LocalStorage>(&self) -> Option<T> {
+        T::from_values(T::keys().filter_map(|key| self.storage.get(key).cloned()))
+    }
+}
+
+/// Stores of the IDE that make up a session.
+///
+/// The release notes that were last seen and whether the tour was completed
+/// belong to the browser rather than to the session, so they are left out.
+#[derive(Copy, Clone)]
+struct Stores {
+    program: Program,
+    program_tabs: ProgramTabs,
+    saved_programs: SavedPrograms,
+    settings: Settings,
+    tx_env: TxEnv,
+    saved_tx_envs: SavedTxEnvs,
+    witness_sets: WitnessSets,
+    witness_seed: WitnessSeed,
+    /// Signing keys, whose seed also derives the hashed data.
+    signing_keys: StoredValue<SigningKeys>,
+    key_count: KeyCount,
+    hash_count: HashCount,
+}
+
+impl Stores {
+    fn from_context() -> Self {
+        Self {
+            program: use_context::<Program>().expect("program should exist in context"),
+            program_tabs: use_context::<ProgramTabs>()
+                .expect("program tabs should exist in context"),
+            saved_programs: use_context::<SavedPrograms>()
+                .expect("saved programs should exist in context"),
+            settings: use_context::<Settings>().expect("settings should exist in context"),
+            tx_env: use_context::<TxEnv>()
+                .expect("transaction environment should exist in context"),
+            saved_tx_envs: use_context::<SavedTxEnvs>()
+                .expect("saved transaction environments should exist in context"),
+            witness_sets: use_context::<WitnessSets>()
+                .expect("witness sets should exist in context"),
+            witness_seed: use_context::<WitnessSeed>()
+                .expect("witness seed should exist in context"),
+            signing_keys: store_value(
+                use_context::<SigningKeys>().expect("signing keys should exist in context"),
+            ),
+            key_count: use_context::<KeyCount>().expect("key count should exist in context"),
+            hash_count: use_context::<HashCount>().expect("hash count should exist in context"),
+        }
+    }
+
+    /// Serialize all saved programs, the settings, the current program, the program tabs,
+    /// the transaction environments, the witness sets, the seed of random witnesses
+    /// and the key and hash stores as JSON.
+    fn export(self) -> String {
+        let mut session = Session {
+            version: SESSION_VERSION,
+            storage: BTreeMap::new(),
+        };
+        // Include edits that are not yet applied to the program text
+        self.program_tabs.sync(self.program);
+        session.insert(&self.program);
+        session.insert(&self.program_tabs);
+        session.insert(&self.saved_programs);
+        session.insert(&self.settings);
+        session.insert(&self.tx_env.params.get_untracked());
+        session.insert(&self.saved_tx_envs);
+        session.insert(&self.witness_sets);
+        session.insert(&self.witness_seed);
+        self.signing_keys.with_value(|keys| session.insert(keys));
+        session.insert(&self.key_count);
+        session.insert(&self.hash_count);
+        serde_json::to_string_pretty(&session).expect("strings can be serialized")
+    }
+
+    /// Replace the state of the IDE with the session in the JSON `text`.
+    ///
+    /// Parts of the state that the session does not contain stay as they are.
+    /// Everything that is replaced is stored in the browser's storage.
+    ///
+    /// The keys and hashes are derived from their seed when the IDE starts,
+    /// so an imported seed takes effect when the page is reloaded.
+    fn import(self, text: &str) -> Result<(), String> {
+        let session = serde_json::from_str::<Session>(text)
+            .map_err(|error| format!("The file is not an exported session: {error}"))?;
+        if session.version != SESSION_VERSION {
+            return Err(format!(
+                "Sessions of version {} cannot be imported",
+                session.version
+            ));
+        }
+
+        if let Some(imported) = session.get::<ProgramTabs>() {
+            self.program_tabs.tabs.set(imported.tabs.get_untracked());
+            self.program_tabs
+                .active
+                .set(imported.active.get_untracked());
+        }
+        // Set the text directly, so the imported program is compiled only once
+        if let Some(text) = Program::keys().find_map(|key| session.storage.get(key)) {
+            self.program.text.set(text.clone());
+        }
+        self.program_tabs.store(self.program);
+        if let Some(imported) = session.get::<SavedPrograms>() {
+            self.saved_programs.0.set(imported.0.get_untracked());
+            self.saved_programs.store_in_storage();
+        }
+        if let Some(imported) = session.get::<Settings>() {
+            self.settings.0.set(imported.0.get_untracked());
+            self.settings.store_in_storage();
+        }
+        if let Some(params) = session.get::<TxParams>() {
+            params.store_in_storage();
+            self.tx_env.params.set(params);
+        }
+        if let Some(imported) = session.get::<SavedTxEnvs>() {
+            self.saved_tx_envs.0.set(imported.0.get_untracked());
+            self.saved_tx_envs.store_in_storage();
+        }
+        if let Some(imported) = session.get::<WitnessSets>() {
+            self.witness_sets.0.set(imported.0.get_untracked());
+            self.witness_sets.store_in_storage();
+        }
+        if let Some(imported) = session.get::<WitnessSeed>() {
+            self.witness_seed.0.set(imported.0.get_untracked());
+            self.witness_seed.store_in_storage();
+        }
+        if let Some(imported) = session.get::<SigningKeys>() {
+            imported.store_in_storage();
+        }
+        if let Some(imported) = session.get::<KeyCount>() {
+            self.key_count.0.set(imported.0.get_untracked());
+            self.key_count.store_in_storage();
+        }
+        if let Some(imported) = session.get::<HashCount>() {
+            self.hash_count.0.set(imported.0.get_untracked());
+            self.hash_count.store_in_storage();
+        }
+        Ok(())
+    }
+}
+
+/// Ask the user whether the current state may be replaced by an imported session.
+fn confirm_import() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .confirm_with_message(
+                    "Importing a session replaces the program, the program tabs, the saved programs, the settings, the transaction environments, the witness sets and the key and hash stores. New keys and hashes appear after the page is reloaded. Continue?",
+                )
+                .ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Buttons to export the entire state of the IDE to a file and to import it again.
+#[component]
+pub fn SessionControls() -> impl IntoView {
+    let stores = Stores::from_context();
+    let error = create_rw_signal(String::new());
+
+    let export = move |_| {
+        let json = stores.export();
+        match download::download_file(
+            "simplicity-session.json",
+            "application/json",
+            json.as_bytes(),
+        ) {
+            Ok(()) => error.update(String::clear),
+            Err(..) => error.set("The browser could not download the session".to_string()),
+        }
+    };
+    let import = move |event: ev::Event| {
+        let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Allow the same file to be chosen again
+        input.set_value("");
+        if !confirm_import() {
+            return;
+        }
+        spawn_local(async move {
+            let Ok(text) = wasm_bindgen_futures::JsFuture::from(file.text()).await else {
+                error.set(format!("The file `{}` could not be read", file.name()));
+                return;
+            };
+            let text = text.as_string().unwrap_or_default();
+            match stores.import(&text) {
+                Ok(()) => error.update(String::clear),
+                Err(message) => error.set(message),
+            }
+        });
+    };
+
+    view! {
+        <div class="session-controls">
+            <button class="button" type="button" on:click=export>
+                <i class="fas fa-file-export"></i>
+                " Export session"
+            </button>
+            <label class="button">
+                <i class="fas fa-file-import"></i>
+                " Import session"
+                <input class="hidden" type="file" accept="application/json,.json" on:change=import />
+            </label>
+        </div>
+        <ErrorBox error=error />
+    }
+}
@@ -0,0 +1,403 @@
+use leptos::{
+    component, create_rw_signal, ev, event_target_checked, event_target_value, use_context, view,
+    IntoView, RwSignal, Show, SignalGet, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::components::modal::Modal;
+use crate::components::session::SessionControls;
+use crate::components::state::LocalStorage;
+
+/// Preferences of the user, which are stored together in one entry of the browser's storage.
+///
+/// Missing fields take their default value,
+/// so preferences that were stored by an older version of the IDE still load.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)] // Each preference is an independent switch
+pub struct Preferences {
+    /// Font size of the program editor in pixels.
+    pub font_size: u32,
+    /// Number of spaces that Tab inserts in the program editor.
+    pub tab_width: usize,
+    /// Whether the device vibrates when a run succeeds or fails.
+    pub haptics: bool,
+    /// Whether the program is saved in the browser shortly after each edit.
+    pub autosave: bool,
+    /// Whether runs use the default transaction environment instead of the edited one.
+    pub fresh_environment: bool,
+    /// Whether new programs start with empty witness and parameter modules.
+    pub default_modules: bool,
+    /// Whether the editor colors names by what the parser knows about them.
+    pub semantic_highlighting: bool,
+    /// Whether the toolbar has a button that links to the documentation.
+    pub help_button: bool,
+    /// Whether programs are formatted before they are stored or shared.
+    pub format_on_save: bool,
+    /// Whether programs are compiled automatically when they are read.
+    ///
+    /// If disabled, programs are only compiled on explicit user actions.
+    pub auto_compile: bool,
+    /// Whether programs are compiled with debug symbols, so that `dbg!` expressions show their values.
+    ///
+    /// Debug symbols change the CMR of the program.
+    pub debug_symbols: bool,
+    /// Whether whitespace and invisible characters of the program are shown next to it.
+    pub show_whitespace: bool,
+    /// Whether the analysis is docked as a panel beside the editor
+    /// instead of being shown in its own view.
+    pub dock_analysis: bool,
+    /// Whether the witness and parameter tabs are shown.
+    pub show_module_tabs: bool,
+    /// Whether long lines of debug output are wrapped instead of scrolled horizontally.
+    pub wrap_debug_lines: bool,
+    /// Maximum number of steps that a run executes before it stops, if any.
+    ///
+    /// Stopped runs can be continued for the same number of steps.
+    pub step_limit: Option<usize>,
+    /// Wall-clock time in seconds after which a run stops, if any.
+    ///
+    /// Stopped runs keep their debug output and can be continued.
+    pub run_timeout: Option<u32>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            font_size: Settings::DEFAULT_FONT_SIZE,
+            tab_width: Settings::DEFAULT_TAB_WIDTH,
+            haptics: true,
+            autosave: true,
+            fresh_environment: false,
+            default_modules: true,
+            semantic_highlighting: false,
+            help_button: true,
+            format_on_save: false,
+            auto_compile: true,
+            debug_symbols: false,
+            show_whitespace: false,
+            dock_analysis: false,
+            show_module_tabs: true,
+            wrap_debug_lines: false,
+            step_limit: Some(Settings::DEFAULT_STEP_LIMIT),
+            run_timeout: Some(Settings::DEFAULT_TIMEOUT_SECONDS),
+        }
+    }
+}
+
+/// Settings of the IDE, which are stored whenever they change.
+#[derive(Copy, Clone, Debug)]
+pub struct Settings(pub RwSignal<Preferences>);
+
+impl Settings {
+    pub const DEFAULT_FONT_SIZE: u32 = 12;
+    pub const MIN_FONT_SIZE: u32 = 8;
+    pub const MAX_FONT_SIZE: u32 = 32;
+    pub const DEFAULT_TAB_WIDTH: usize = 4;
+    pub const TAB_WIDTHS: [usize; 3] = [2, 4, 8];
+    pub const DEFAULT_STEP_LIMIT: usize = 10_000_000;
+    pub const DEFAULT_TIMEOUT_SECONDS: u32 = 60;
+
+    pub fn new(mut preferences: Preferences) -> Self {
+        Self::make_valid(&mut preferences);
+        Self(create_rw_signal(preferences))
+    }
+
+    /// Keep the preferences within their valid ranges.
+    fn make_valid(preferences: &mut Preferences) {
+        preferences.font_size = preferences
+            .font_size
+            .clamp(Self::MIN_FONT_SIZE, Self::MAX_FONT_SIZE);
+        if !Self::TAB_WIDTHS.contains(&preferences.tab_width) {
+            preferences.tab_width = Self::DEFAULT_TAB_WIDTH;
+        }
+        // A run that stops before its first step makes no progress
+        if preferences.step_limit == Some(0) {
+            preferences.step_limit = Some(Self::DEFAULT_STEP_LIMIT);
+        }
+        if preferences.run_timeout == Some(0) {
+            preferences.run_timeout = Some(Self::DEFAULT_TIMEOUT_SECONDS);
+        }
+    }
+
+    /// Change the preferences and store them.
+    fn update(self, f: impl FnOnce(&mut Preferences)) {
+        self.0.update(|preferences| {
+            f(preferences);
+            Self::make_valid(preferences);
+        });
+        self.store_in_storage();
+    }
+
+    pub fn font_size(self) -> u32 {
+        self.0.with(|preferences| preferences.font_size)
+    }
+
+    pub fn set_font_size(self, size: u32) {
+        self.update(|preferences| preferences.font_size = size);
+    }
+
+    pub fn tab_width(self) -> usize {
+        self.0.with(|preferences| preferences.tab_width)
+    }
+
+    pub fn set_tab_width(self, width: usize) {
+        self.update(|preferences| preferences.tab_width = width);
+    }
+
+    pub fn haptics(self) -> bool {
+        self.0.with(|preferences| preferences.haptics)
+    }
+
+    pub fn set_haptics(self, haptics: bool) {
+        self.update(|preferences| preferences.haptics = haptics);
+    }
+
+    pub fn autosave(self) -> bool {
+        self.0.with(|preferences| preferences.autosave)
+    }
+
+    pub fn set_autosave(self, autosave: bool) {
+        self.update(|preferences| preferences.autosave = autosave);
+    }
+
+    pub fn fresh_environment(self) -> bool {
+        self.0.with(|preferences| preferences.fresh_environment)
+    }
+
+    pub fn set_fresh_environment(self, fresh: bool) {
+        self.update(|preferences| preferences.fresh_environment = fresh);
+    }
+
+    pub fn default_modules(self) -> bool {
+        self.0.with(|preferences| preferences.default_modules)
+    }
+
+    pub fn set_default_modules(self, insert: bool) {
+        self.update(|preferences| preferences.default_modules = insert);
+    }
+
+    pub fn semantic_highlighting(self) -> bool {
+        self.0.with(|preferences| preferences.semantic_highlighting)
+    }
+
+    pub fn set_semantic_highlighting(self, semantic: bool) {
+        self.update(|preferences| preferences.semantic_highlighting = semantic);
+    }
+
+    pub fn help_button(self) -> bool {
+        self.0.with(|preferences| preferences.help_button)
+    }
+
+    pub fn set_help_button(self, show: bool) {
+        self.update(|preferences| preferences.help_button = show);
+    }
+
+    pub fn format_on_save(self) -> bool {
+        self.0.with(|preferences| preferences.format_on_save)
+    }
+
+    pub fn set_format_on_save(self, format: bool) {
+        self.update(|preferences| preferences.format_on_save = format);
+    }
+
+    pub fn auto_compile(self) -> bool {
+        self.0.with(|preferences| preferences.auto_compile)
+    }
+
+    pub fn set_auto_compile(self, enabled: bool) {
+        self.update(|preferences| preferences.auto_compile = enabled);
+    }
+
+    pub fn debug_symbols(self) -> bool {
+        self.0.with(|preferences| preferences.debug_symbols)
+    }
+
+    pub fn set_debug_symbols(self, include: bool) {
+        self.update(|preferences| preferences.debug_symbols = include);
+    }
+
+    pub fn show_whitespace(self) -> bool {
+        self.0.with(|preferences| preferences.show_whitespace)
+    }
+
+    pub fn set_show_whitespace(self, show: bool) {
+        self.update(|preferences| preferences.show_whitespace = show);
+    }
+
+    pub fn dock_analysis(self) -> bool {
+        self.0.with(|preferences| preferences.dock_analysis)
+    }
+
+    /// Dock or undock the analysis.
+    pub fn toggle_dock_analysis(self) {
+        self.update(|preferences| preferences.dock_analysis = !preferences.dock_analysis);
+    }
+
+    pub fn show_module_tabs(self) -> bool {
+        self.0.with(|preferences| preferences.show_module_tabs)
+    }
+
+    /// Show or hide the witness and parameter tabs.
+    pub fn toggle_module_tabs(self) {
+        self.update(|preferences| preferences.show_module_tabs = !preferences.show_module_tabs);
+    }
+
+    pub fn wrap_debug_lines(self) -> bool {
+        self.0.with(|preferences| preferences.wrap_debug_lines)
+    }
+
+    pub fn set_wrap_debug_lines(self, wrap: bool) {
+        self.update(|preferences| preferences.wrap_debug_lines = wrap);
+    }
+
+    /// Return the step limit of runs without tracking it, for runs that are about to start.
+    pub fn step_limit(self) -> Option<usize> {
+        self.0.with_untracked(|preferences| preferences.step_limit)
+    }
+
+    pub fn set_step_limit(self, limit: Option<usize>) {
+        self.update(|preferences| preferences.step_limit = limit);
+    }
+
+    /// Return the timeout of runs without tracking it, for runs that are about to start.
+    pub fn run_timeout(self) -> Option<u32> {
+        self.0.with_untracked(|preferences| preferences.run_timeout)
+    }
+
+    pub fn set_run_timeout(self, seconds: Option<u32>) {
+        self.update(|preferences| preferences.run_timeout = seconds);
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new(Preferences::default())
+    }
+}
+
+/// Button that opens the settings panel.
+#[component]
+pub fn SettingsButton() -> impl IntoView {
+    let open = create_rw_signal(false);
+
+    view! {
+        <button class="button" type="button" on:click=move |_| open.set(true)>
+            <i class="fas fa-cog"></i>
+            " Settings"
+        </button>
+        <Show when=move || open.get()>
+            <Modal title="Settings" class="settings-modal" close=move || open.set(false)>
+                <SettingsPanel />
+            </Modal>
+        </Show>
+    }
+}
+
+/// Form with all settings, which apply as soon as they are changed.
+#[component]
+fn SettingsPanel() -> impl IntoView {
+    let settings = use_context::<Settings>().expect("settings should exist in context");
+    let update_font_size = move |event: ev::Event| {
+        if let Ok(size) = event_target_value(&event).parse::<u32>() {
+            settings.set_font_size(size);
+        }
+    };
+    let update_tab_width = move |event: ev::Event| {
+        if let Ok(width) = event_target_value(&event).parse::<usize>() {
+            settings.set_tab_width(width);
+        }
+    };
+    let tab_width_options = Settings::TAB_WIDTHS
+        .into_iter()
+        .map(|width| view! { <option value=width.to_string()>{width}" spaces"</option> })
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="settings-panel">
+            <label>
+                "Editor font size (px)"
+                <input
+                    class="input"
+                    type="number"
+                    min=Settings::MIN_FONT_SIZE
+                    max=Settings::MAX_FONT_SIZE
+                    prop:value=move || settings.font_size().to_string()
+                    on:change=update_font_size
+                />
+            </label>
+            <label>
+                "Tab width"
+                <select
+                    prop:value=move || settings.tab_width().to_string()
+                    on:change=update_tab_width
+                >
+                    {tab_width_options}
+                </select>
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.autosave()
+                    on:change=move |event| settings.set_autosave(event_target_checked(&event))
+                />
+                "Save the program automatically after each edit"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.haptics()
+                    on:change=move |event| settings.set_haptics(event_target_checked(&event))
+                />
+                "Vibrate when a run succeeds or fails"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.fresh_environment()
+                    on:change=move |event| {
+                        settings.set_fresh_environment(event_target_checked(&event));
+                    }
+                />
+                "Run in the default transaction environment, ignoring its edits"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.default_modules()
+                    on:change=move |event| settings.set_default_modules(event_target_checked(&event))
+                />
+                "Start new programs with empty witness and parameter modules"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.semantic_highlighting()
+                    on:change=move |event| {
+                        settings.set_semantic_highlighting(event_target_checked(&event));
+                    }
+                />
+                "Color witnesses, parameters, functions and types as the parser sees them"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.help_button()
+                    on:change=move |event| settings.set_help_button(event_target_checked(&event))
+                />
+                "Show the help button in the toolbar"
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.format_on_save()
+                    on:change=move |event| settings.set_format_on_save(event_target_checked(&event))
+                />
+                "Format programs before saving or sharing them, if they parse"
+            </label>
+            <h3>"Session"</h3>
+            <p>"Export all programs, settings and transaction environments to a file, to back them up or to move them to another browser."</p>
+            <SessionControls />
+        </div>
+    }
+}
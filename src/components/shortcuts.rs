@@ -0,0 +1,75 @@
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_rw_signal, ev, view, window_event_listener, CollectView, IntoView, Show,
+    SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+};
+
+use crate::components::modal::Modal;
+
+/// Keyboard shortcuts of the IDE, as pairs of keys and descriptions.
+const SHORTCUTS: [(&str, &str); 12] = [
+    ("Ctrl+R / Cmd+R", "Run the program"),
+    ("Ctrl+Enter / Cmd+Enter", "Run the program (in the editor)"),
+    ("Alt+W", "Show or hide the witness and parameter tabs"),
+    ("Alt+A", "Dock or undock the analysis beside the editor"),
+    (
+        "Ctrl+Shift+Enter / Cmd+Shift+Enter",
+        "Run until the line of the cursor (in the editor)",
+    ),
+    (
+        "F9",
+        "Add or remove a breakpoint on the line of the cursor (in the editor)",
+    ),
+    ("Ctrl+/ / Cmd+/", "Comment or uncomment the selected lines"),
+    (
+        "F12",
+        "Go to the declaration of the witness or parameter under the cursor",
+    ),
+    ("Tab", "Indent by the tab width of the settings"),
+    ("Shift+Tab", "Remove one level of indentation"),
+    ("?", "Show this list of shortcuts (outside of text fields)"),
+    ("Escape", "Close this list"),
+];
+
+/// Check whether the event was sent to a field that accepts text input.
+fn is_text_input(event: &ev::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+}
+
+/// Overlay with the list of keyboard shortcuts, which is opened with `?`.
+#[component]
+pub fn ShortcutsOverlay() -> impl IntoView {
+    let open = create_rw_signal(false);
+    let _listener = window_event_listener(ev::keydown, move |event| {
+        if event.key() == "?" && !is_text_input(&event) {
+            event.prevent_default();
+            open.update(|open| *open = !*open);
+        } else if event.key() == "Escape" && open.get_untracked() {
+            open.set(false);
+        }
+    });
+    let rows = || {
+        SHORTCUTS
+            .iter()
+            .map(|(keys, description)| {
+                view! {
+                    <tr>
+                        <td><kbd>{*keys}</kbd></td>
+                        <td>{*description}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <Modal title="Keyboard shortcuts" class="shortcuts-modal" close=move || open.set(false)>
+                <table class="shortcuts-table">{rows()}</table>
+            </Modal>
+        </Show>
+    }
+}
@@ -1,9 +1,18 @@
-use leptos::{use_context, SignalGetUntracked, SignalWithUntracked};
+use leptos::{
+    create_effect, spawn_local, store_value, use_context, SignalGetUntracked, SignalWith,
+    SignalWithUntracked,
+};
 use simplicityhl::num::U256;
 use web_sys::window;
 
-use crate::components::program_window::Program;
-use crate::components::run_window::{HashCount, KeyCount, TxEnv};
+use crate::components::program_window::{Program, ProgramTabs, SavedPrograms};
+use crate::components::run_window::{
+    HashCount, KeyCount, SavedTxEnvs, TxEnv, WitnessSeed, WitnessSets,
+};
+use crate::components::settings::{Preferences, Settings};
+use crate::components::toast::{ToastKind, Toasts};
+use crate::components::tour::TourCompleted;
+use crate::components::whats_new::LastSeenRelease;
 use crate::transaction::TxParams;
 use crate::util::{Counter26, HashedData, SigningKeys};
 
@@ -46,13 +55,24 @@ pub trait LocalStorage: Sized {
             let _result = storage.set_item(key, value.as_str());
         }
     }
+
+    /// Remove the object from the browser's local storage.
+    fn remove_from_storage() {
+        let Some(storage) = local_storage() else {
+            return;
+        };
+        for key in Self::keys() {
+            let _result = storage.remove_item(key);
+        }
+    }
 }
 
 /// Store the app's entire state in the browser's local storage.
 pub fn update_local_storage() {
-    use_context::<Program>()
-        .expect("program should exist in context")
-        .store_in_storage();
+    let program = use_context::<Program>().expect("program should exist in context");
+    use_context::<ProgramTabs>()
+        .expect("program tabs should exist in context")
+        .store(program);
     use_context::<TxEnv>()
         .expect("transaction environment should exist in context")
         .params
@@ -69,6 +89,35 @@ pub fn update_local_storage() {
     leptos::logging::log!("Update storage");
 }
 
+/// Time after the last edit until the program is saved, in milliseconds.
+const AUTOSAVE_DELAY_MS: u32 = 1000;
+
+/// Save the program in the browser's local storage shortly after each edit.
+///
+/// Consecutive edits are debounced, so the program is saved once the user stops typing.
+/// The other tabs are saved along with the program, and a toast tells the user.
+/// Nothing is saved while automatic saving is turned off in the settings.
+pub fn autosave(program: Program, program_tabs: ProgramTabs, settings: Settings, toasts: Toasts) {
+    let generation = store_value(0_usize);
+    create_effect(move |previous: Option<()>| {
+        program.text.with(|_| ());
+        let enabled = settings.autosave();
+        // The loaded program is already in storage
+        if previous.is_none() || !enabled {
+            return;
+        }
+        generation.update_value(|generation| *generation += 1);
+        let current = generation.get_value();
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(AUTOSAVE_DELAY_MS).await;
+            if generation.get_value() == current {
+                program_tabs.store(program);
+                toasts.push(ToastKind::Info, "Program saved");
+            }
+        });
+    });
+}
+
 impl LocalStorage for Program {
     fn keys() -> impl Iterator<Item = &'static str> {
         ["program"].into_iter()
@@ -79,7 +128,215 @@ impl LocalStorage for Program {
     }
 
     fn to_values(&self) -> impl Iterator<Item = String> {
-        [self.text.get_untracked()].into_iter()
+        [self.text_to_store()].into_iter()
+    }
+}
+
+impl LocalStorage for SavedPrograms {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["saved_programs"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        let programs: Vec<(String, String)> = serde_json::from_str(&values.next()?).ok()?;
+        Some(Self::new(programs))
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        let json = self.0.with_untracked(|programs| {
+            serde_json::to_string(programs).expect("strings can be serialized")
+        });
+        [json].into_iter()
+    }
+}
+
+impl LocalStorage for ProgramTabs {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["program_tabs", "active_program_tab"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        let tabs: Vec<(String, String)> = serde_json::from_str(&values.next()?).ok()?;
+        let active = values.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        Some(Self::new(tabs, active))
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        let json = self
+            .tabs
+            .with_untracked(|tabs| serde_json::to_string(tabs).expect("strings can be serialized"));
+        [json, self.active.get_untracked().to_string()].into_iter()
+    }
+}
+
+impl LocalStorage for WitnessSets {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["witness_sets"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        let sets: Vec<(String, String)> = serde_json::from_str(&values.next()?).ok()?;
+        Some(Self::new(sets))
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        let json = self
+            .0
+            .with_untracked(|sets| serde_json::to_string(sets).expect("strings can be serialized"));
+        [json].into_iter()
+    }
+}
+
+impl LocalStorage for WitnessSeed {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["witness_seed"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        values.next()?.parse::<u64>().ok().map(Self::new)
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        [self.0.get_untracked().to_string()].into_iter()
+    }
+}
+
+/// Keys under which older versions of the IDE stored single settings,
+/// before the settings were kept together.
+const LEGACY_SETTING_KEYS: [&str; 9] = [
+    "editor_font_size",
+    "auto_compile",
+    "debug_symbols",
+    "show_whitespace",
+    "dock_analysis",
+    "show_module_tabs",
+    "wrap_debug_lines",
+    "step_limit",
+    "run_timeout",
+];
+
+/// Load the settings from the browser's local storage.
+///
+/// Settings that older versions of the IDE stored under their own keys are moved into
+/// the settings, and their keys are removed, so they are only migrated once.
+pub fn load_settings() -> Settings {
+    let stored = Settings::load_from_storage();
+    let Some(storage) = local_storage() else {
+        return stored.unwrap_or_default();
+    };
+    let legacy = |key: &str| storage.get_item(key).ok().flatten();
+    if LEGACY_SETTING_KEYS.iter().all(|key| legacy(key).is_none()) {
+        return stored.unwrap_or_default();
+    }
+    // The font size was the first setting to move into the stored settings,
+    // so its old key is outdated once they exist
+    let font_size_is_current = stored.is_none();
+    let mut preferences = stored
+        .map(|settings| settings.0.get_untracked())
+        .unwrap_or_default();
+    migrate_preferences(&mut preferences, |key| {
+        match key == "editor_font_size" && !font_size_is_current {
+            true => None,
+            false => legacy(key),
+        }
+    });
+    let settings = Settings::new(preferences);
+    settings.store_in_storage();
+    for key in LEGACY_SETTING_KEYS {
+        let _result = storage.remove_item(key);
+    }
+    settings
+}
+
+/// Take the settings that are stored under the `legacy` keys into the `preferences`.
+///
+/// Values that do not parse keep the preference as it is.
+fn migrate_preferences(preferences: &mut Preferences, legacy: impl Fn(&str) -> Option<String>) {
+    let flag = |key: &str| legacy(key).and_then(|value| value.parse::<bool>().ok());
+    if let Some(size) = legacy("editor_font_size").and_then(|value| value.parse().ok()) {
+        preferences.font_size = size;
+    }
+    if let Some(enabled) = flag("auto_compile") {
+        preferences.auto_compile = enabled;
+    }
+    if let Some(include) = flag("debug_symbols") {
+        preferences.debug_symbols = include;
+    }
+    if let Some(show) = flag("show_whitespace") {
+        preferences.show_whitespace = show;
+    }
+    if let Some(docked) = flag("dock_analysis") {
+        preferences.dock_analysis = docked;
+    }
+    if let Some(show) = flag("show_module_tabs") {
+        preferences.show_module_tabs = show;
+    }
+    if let Some(wrap) = flag("wrap_debug_lines") {
+        preferences.wrap_debug_lines = wrap;
+    }
+    if let Some(value) = legacy("step_limit") {
+        migrate_limit(&mut preferences.step_limit, &value);
+    }
+    if let Some(value) = legacy("run_timeout") {
+        migrate_limit(&mut preferences.run_timeout, &value);
+    }
+}
+
+/// Take the stored `value` into the `limit`, where an empty value means that there is no limit.
+fn migrate_limit<T: std::str::FromStr>(limit: &mut Option<T>, value: &str) {
+    if value.is_empty() {
+        *limit = None;
+    } else if let Ok(parsed) = value.parse() {
+        *limit = Some(parsed);
+    }
+}
+
+impl LocalStorage for Settings {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["settings"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        let preferences = serde_json::from_str::<Preferences>(&values.next()?).ok()?;
+        Some(Self::new(preferences))
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        let json = self.0.with_untracked(|preferences| {
+            serde_json::to_string(preferences).expect("preferences should serialize")
+        });
+        [json].into_iter()
+    }
+}
+
+impl LocalStorage for LastSeenRelease {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["last_seen_release"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        values.next().and_then(|s| s.parse::<u32>().ok()).map(Self)
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        [self.0.to_string()].into_iter()
+    }
+}
+
+impl LocalStorage for TourCompleted {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["tour_completed"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        values
+            .next()
+            .and_then(|s| s.parse::<bool>().ok())
+            .map(Self::new)
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        [self.0.get_untracked().to_string()].into_iter()
     }
 }
 
@@ -153,6 +410,31 @@ impl LocalStorage for HashCount {
     }
 }
 
+impl LocalStorage for SavedTxEnvs {
+    fn keys() -> impl Iterator<Item = &'static str> {
+        ["saved_tx_envs"].into_iter()
+    }
+
+    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        // Each environment is stored as the values of its transaction parameters
+        let stored: Vec<(String, Vec<String>)> = serde_json::from_str(&values.next()?).ok()?;
+        let envs = stored
+            .into_iter()
+            .filter_map(|(name, values)| Some((name, TxParams::from_values(values.into_iter())?)))
+            .collect();
+        Some(Self::new(envs))
+    }
+
+    fn to_values(&self) -> impl Iterator<Item = String> {
+        let stored: Vec<(String, Vec<String>)> = self.0.with_untracked(|envs| {
+            envs.iter()
+                .map(|(name, params)| (name.clone(), params.to_values().collect()))
+                .collect()
+        });
+        [serde_json::to_string(&stored).expect("strings can be serialized")].into_iter()
+    }
+}
+
 impl LocalStorage for TxParams {
     fn keys() -> impl Iterator<Item = &'static str> {
         [
@@ -163,43 +445,20 @@ impl LocalStorage for TxParams {
             "fee",
             "lock_time",
             "sequence",
+            "network",
+            "input_count",
+            "input_index",
+            "raw_transaction",
+            "extra_outputs",
         ]
         .into_iter()
     }
 
-    fn from_values(mut values: impl Iterator<Item = String>) -> Option<Self> {
-        let txid = values.next().and_then(|s| s.parse().ok())?;
-        let vout = values.next().and_then(|s| s.parse().ok())?;
-        let value_in = values.next().and_then(|s| s.parse().ok())?;
-        let recipient_address = values.next().and_then(|s| s.parse().ok());
-        let fee = values.next().and_then(|s| s.parse().ok())?;
-        let lock_time = values.next().and_then(|s| s.parse().ok())?;
-        let sequence = values.next().and_then(|s| s.parse().ok())?;
-
-        Some(Self {
-            txid,
-            vout,
-            value_in,
-            recipient_address,
-            fee,
-            lock_time,
-            sequence,
-        })
+    fn from_values(values: impl Iterator<Item = String>) -> Option<Self> {
+        Self::from_strings(values)
     }
 
     fn to_values(&self) -> impl Iterator<Item = String> {
-        [
-            self.txid.to_string(),
-            self.vout.to_string(),
-            self.value_in.to_string(),
-            self.recipient_address
-                .as_ref()
-                .map(ToString::to_string)
-                .unwrap_or_default(),
-            self.fee.to_string(),
-            self.lock_time.to_string(),
-            self.sequence.to_string(),
-        ]
-        .into_iter()
+        self.to_strings().into_iter()
     }
 }
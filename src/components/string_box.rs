@@ -14,6 +14,13 @@ pub fn NeutralBox(#[prop(into)] neutral: Signal<String>) -> impl IntoView {
     }
 }
 
+#[component]
+pub fn WarningBox(#[prop(into)] warning: Signal<String>) -> impl IntoView {
+    view! {
+        <StringBox string=warning box_class="warning-box" />
+    }
+}
+
 #[component]
 pub fn ErrorBox(#[prop(into)] error: Signal<String>) -> impl IntoView {
     view! {
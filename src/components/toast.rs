@@ -0,0 +1,112 @@
+use leptos::{
+    component, create_rw_signal, spawn_local, store_value, use_context, view, For, IntoView,
+    RwSignal, SignalGet, SignalUpdate, StoredValue,
+};
+
+/// Time for which a toast is shown, in milliseconds.
+const TOAST_DURATION_MS: u32 = 3000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    fn class(self) -> &'static str {
+        match self {
+            Self::Info => "toast",
+            Self::Success => "toast toast-success",
+            Self::Error => "toast toast-error",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "fas fa-info-circle",
+            Self::Success => "fas fa-check",
+            Self::Error => "fas fa-times",
+        }
+    }
+}
+
+/// Transient notification.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Toast {
+    id: usize,
+    kind: ToastKind,
+    message: String,
+}
+
+/// Queue of the toasts that are currently shown.
+///
+/// Any component can push a toast, which disappears after a few seconds.
+#[derive(Copy, Clone, Debug)]
+pub struct Toasts {
+    queue: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<usize>,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            queue: create_rw_signal(Vec::new()),
+            next_id: store_value(0),
+        }
+    }
+}
+
+impl Toasts {
+    /// Show a toast with the given message.
+    ///
+    /// A toast that is already shown with the same message is replaced,
+    /// so repeated notices do not pile up.
+    pub fn push(self, kind: ToastKind, message: impl Into<String>) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id.wrapping_add(1));
+        let toast = Toast {
+            id,
+            kind,
+            message: message.into(),
+        };
+        self.queue.update(|queue| {
+            queue.retain(|shown| shown.kind != toast.kind || shown.message != toast.message);
+            queue.push(toast);
+        });
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(TOAST_DURATION_MS).await;
+            self.dismiss(id);
+        });
+    }
+
+    fn dismiss(self, id: usize) {
+        self.queue
+            .update(|queue| queue.retain(|toast| toast.id != id));
+    }
+}
+
+/// Stack of the toasts that are currently shown.
+#[component]
+pub fn ToastList() -> impl IntoView {
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+
+    view! {
+        <div class="toast-list" role="status" aria-live="polite">
+            <For
+                each=move || toasts.queue.get()
+                key=|toast| toast.id
+                children=move |toast| {
+                    let id = toast.id;
+                    view! {
+                        <div class=toast.kind.class() on:click=move |_| toasts.dismiss(id)>
+                            <i class=toast.kind.icon()></i>
+                            " "
+                            {toast.message}
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
@@ -0,0 +1,122 @@
+use leptos::{
+    component, create_effect, create_rw_signal, view, IntoView, RwSignal, Show, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate,
+};
+
+use crate::components::state::LocalStorage;
+
+/// Whether the user has finished or dismissed the tour of the IDE.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TourCompleted(pub RwSignal<bool>);
+
+impl TourCompleted {
+    pub fn new(completed: bool) -> Self {
+        Self(create_rw_signal(completed))
+    }
+}
+
+/// Step of the tour that explains the element that matches a CSS selector.
+struct TourStep {
+    selector: &'static str,
+    title: &'static str,
+    text: &'static str,
+}
+
+const TOUR_STEPS: [TourStep; 4] = [
+    TourStep {
+        selector: ".program-input-field",
+        title: "Editor",
+        text: "Write your SimplicityHL program here. \
+            Witness values go into `mod witness` and parameters into `mod param`.",
+    },
+    TourStep {
+        selector: ".run-button",
+        title: "Run",
+        text: "Run the program in the transaction environment. \
+            The result appears in the execution tab below. Ctrl+Enter runs from the editor.",
+    },
+    TourStep {
+        selector: "[data-tab='Witness']",
+        title: "Witness",
+        text: "Edit the witness values of the program, with one field per witness.",
+    },
+    TourStep {
+        selector: "[data-tour='analyze']",
+        title: "Analysis",
+        text: "Inspect the size, cost and DAG of the compiled program.",
+    },
+];
+
+const HIGHLIGHT_CLASS: &str = "tour-highlight";
+
+/// Add or remove the highlight of the element of the given step.
+fn set_highlight(step: usize, highlighted: bool) {
+    let Some(element) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector(TOUR_STEPS[step].selector).ok())
+        .flatten()
+    else {
+        return;
+    };
+    let classes = element.class_list();
+    let _result = match highlighted {
+        true => classes.add_1(HIGHLIGHT_CLASS),
+        false => classes.remove_1(HIGHLIGHT_CLASS),
+    };
+}
+
+/// Step-by-step tour of the IDE that is shown on the first visit.
+#[component]
+pub fn Tour() -> impl IntoView {
+    let completed = TourCompleted::load_from_storage().unwrap_or_default();
+    let step = create_rw_signal(0_usize);
+    let finish = move || {
+        set_highlight(step.get_untracked(), false);
+        completed.0.set(true);
+        completed.store_in_storage();
+    };
+    create_effect(move |previous: Option<usize>| {
+        if let Some(previous) = previous {
+            set_highlight(previous, false);
+        }
+        let current = step.get();
+        if !completed.0.get() {
+            set_highlight(current, true);
+        }
+        current
+    });
+    let next = move |_| match step.get_untracked() + 1 < TOUR_STEPS.len() {
+        true => step.update(|step| *step += 1),
+        false => finish(),
+    };
+    let back = move |_| step.update(|step| *step = step.saturating_sub(1));
+    let is_last = move || step.get() + 1 == TOUR_STEPS.len();
+
+    view! {
+        <Show when=move || !completed.0.get()>
+            <div class="tour-card">
+                <div class="tour-progress">
+                    {move || format!("{} / {}", step.get() + 1, TOUR_STEPS.len())}
+                </div>
+                <h3 class="tour-title">{move || TOUR_STEPS[step.get()].title}</h3>
+                <p>{move || TOUR_STEPS[step.get()].text}</p>
+                <div class="button-row is-small">
+                    <button class="flat-button bordered" type="button" on:click=move |_| finish()>
+                        "Skip"
+                    </button>
+                    <button
+                        class="flat-button bordered"
+                        type="button"
+                        on:click=back
+                        disabled=move || step.get() == 0
+                    >
+                        "Back"
+                    </button>
+                    <button class="flat-button bordered" type="button" on:click=next>
+                        {move || if is_last() { "Done" } else { "Next" }}
+                    </button>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,73 @@
+use leptos::{
+    component, create_rw_signal, view, CollectView, IntoView, Show, SignalGet, SignalSet,
+};
+
+use crate::components::modal::Modal;
+use crate::components::state::LocalStorage;
+
+/// Number of the newest release whose changes the user has seen.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LastSeenRelease(pub u32);
+
+/// New features of one release of the IDE.
+struct Release {
+    number: u32,
+    changes: &'static [&'static str],
+}
+
+/// Releases with noteworthy changes, newest first.
+///
+/// Add a release with a higher number to show its changes to returning users.
+const RELEASES: [Release; 2] = [
+    Release {
+        number: 2,
+        changes: &[
+            "Warnings about unused witnesses, constant assertions and invisible characters",
+            "Compare the CMRs and trees of two programs side by side",
+            "Explore the compiled program as a collapsible node tree",
+            "Stop long runs at a configurable step limit",
+            "Save transaction environments under a name",
+            "Show the output of dbg! as raw bits",
+        ],
+    },
+    Release {
+        number: 1,
+        changes: &[
+            "Guided tour of the IDE",
+            "Keyboard shortcut overview: press ?",
+            "Dock the analysis next to the editor",
+            "Fields for parameters in the Parameters tab",
+        ],
+    },
+];
+
+/// Popup that lists the changes since the last visit.
+///
+/// First-time visitors get the tour instead, so they do not see the popup.
+#[component]
+pub fn WhatsNew() -> impl IntoView {
+    let newest = LastSeenRelease(RELEASES[0].number);
+    let last_seen = LastSeenRelease::load_from_storage();
+    newest.store_in_storage();
+    let Some(last_seen) = last_seen else {
+        return view! {}.into_view();
+    };
+    let changes = move || {
+        RELEASES
+            .iter()
+            .filter(|release| last_seen.0 < release.number)
+            .flat_map(|release| release.changes.iter())
+            .map(|change| view! { <li>{*change}</li> })
+            .collect_view()
+    };
+    let open = create_rw_signal(last_seen != newest);
+
+    view! {
+        <Show when=move || open.get()>
+            <Modal title="What's new" class="whats-new-modal" close=move || open.set(false)>
+                <ul class="whats-new-list">{changes}</ul>
+            </Modal>
+        </Show>
+    }
+    .into_view()
+}
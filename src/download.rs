@@ -0,0 +1,23 @@
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{window, Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Let the browser download `content` as a file with the given name and MIME type.
+pub fn download_file(file_name: &str, mime_type: &str, content: &[u8]) -> Result<(), JsValue> {
+    let document = window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("document should exist"))?;
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(content));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let anchor = document
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    Url::revoke_object_url(&url)
+}
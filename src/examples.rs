@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::network::Network;
 use crate::transaction::TxParams;
 use elements::hashes::{sha256, Hash};
 use elements::secp256k1_zkp as secp256k1;
@@ -58,6 +59,11 @@ impl Example {
             fee: 1_000,
             lock_time: elements::LockTime::from_consensus(self.lock_time),
             sequence: elements::Sequence::from_consensus(self.sequence),
+            network: Network::default(),
+            input_count: 1,
+            input_index: 0,
+            raw_transaction: None,
+            extra_outputs: vec![],
         }
     }
 }
@@ -83,6 +89,9 @@ fn p2pk_witness(
     )]))
 }
 
+/// Name of the example that the IDE starts with when there is no stored program.
+pub const STARTER: &str = "✍️️ P2PK";
+
 const P2PK: Example = Example {
     description: r#"Pay to public key.
 
@@ -624,6 +633,22 @@ const EXAMPLES: [(&str, Example); 7] = [
     ("🧨 Hash loop", HASH_LOOP),
 ];
 
+/// Parts of compile error messages and the names of examples
+/// that show the correct usage of the construct that failed.
+const ERROR_EXAMPLES: [(&str, &str); 8] = [
+    ("for a for-while loop", "🧨 Hash loop"),
+    ("as for-while bit width", "🧨 Hash loop"),
+    ("Match arm", "⚡ HTLC"),
+    (
+        "Witness expressions are not allowed outside the `main` function",
+        "⚡ HTLC",
+    ),
+    ("Main function takes no input parameters", "✍️️ P2PK"),
+    ("Main function produces no output", "✍️️ P2PK"),
+    ("is missing an argument", "✍️️ P2PK"),
+    ("was declared with type", "✍️ P2PKH"),
+];
+
 /// Iterate over the example names.
 pub fn keys() -> impl ExactSizeIterator<Item = &'static str> {
     EXAMPLES.into_iter().map(|(name, _)| name)
@@ -639,11 +664,25 @@ pub fn get(name: &str) -> Option<Example> {
         })
 }
 
+/// Get the name of an example that shows how to avoid the compile `error`, if there is one.
+pub fn for_error(error: &str) -> Option<&'static str> {
+    ERROR_EXAMPLES
+        .into_iter()
+        .find(|(pattern, _)| error.contains(pattern))
+        .map(|(_, name)| name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
 
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn starter_exists() {
+        assert!(get(STARTER).is_some());
+    }
+
     #[test]
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn name_primary_key() {
@@ -652,4 +691,20 @@ mod tests {
             keys().collect::<HashSet<&'static str>>().len()
         );
     }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn error_examples_exist() {
+        for (_, name) in ERROR_EXAMPLES {
+            assert!(get(name).is_some(), "{name} should be an example");
+        }
+        let error = simplicityhl::CompiledProgram::new(
+            "fn main() -> u8 { 0 }",
+            simplicityhl::Arguments::default(),
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(Some("✍️️ P2PK"), for_error(&error));
+        assert_eq!(None, for_error("Grammar error"));
+    }
 }
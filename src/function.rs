@@ -1,19 +1,24 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
 use either::Either;
+use serde::Serialize;
+use simplicity::bit_machine::LimitError;
+use simplicity::jet::Elements;
 use simplicity::node::Inner;
 use simplicity::types::Final;
 use simplicity::Value;
-use simplicityhl::debug::{DebugSymbols, FallibleCall, FallibleCallName};
+use simplicityhl::debug::{DebugSymbols, FallibleCall, FallibleCallName, TrackedCall};
 use simplicityhl::either;
 use simplicityhl::simplicity::jet::elements::ElementsEnv;
+use simplicityhl::value::StructuralValue;
 use simplicityhl::SatisfiedProgram;
 use simplicityhl::{elements, simplicity};
 
 use crate::jet;
-use crate::jet::JetFailed;
-use crate::util::Expression;
+use crate::jet::JetError;
+use crate::util::{value, Expression};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ErrorKind {
@@ -22,6 +27,16 @@ pub enum ErrorKind {
     JetFailed,
     WrongType,
     SimplicityHLCallFailed(FallibleCall),
+    /// The runner executed as many steps as it was allowed to.
+    ///
+    /// The execution can be continued after allowing more steps.
+    StepLimitReached,
+    /// The run was aborted because it executed more steps than it was allowed to.
+    ///
+    /// Unlike reaching the step limit, the execution cannot be continued.
+    ExecutionAborted {
+        steps: usize,
+    },
 }
 
 impl fmt::Display for ErrorKind {
@@ -48,6 +63,12 @@ impl fmt::Display for ErrorKind {
                 }
                 write!(f, "`{}`", call.text())
             }
+            ErrorKind::StepLimitReached => f.write_str("Step limit reached"),
+            ErrorKind::ExecutionAborted { steps } => write!(
+                f,
+                "The program was aborted for exceeding {steps} steps. \
+                It may be very long or stuck in a large loop."
+            ),
         }
     }
 }
@@ -63,6 +84,100 @@ enum Task {
     ResetActiveSimplicityHLCall,
 }
 
+/// Maximum number of steps that are recorded in an execution trace.
+///
+/// Longer traces are truncated to keep the browser responsive.
+const MAX_TRACE_STEPS: usize = 100_000;
+
+/// Execution of a single Simplicity combinator.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TraceStep {
+    /// Name of the combinator, including the jet or word for leaves.
+    pub node: String,
+    /// CMR of the executed expression.
+    pub cmr: String,
+    /// Value on which the combinator was called.
+    pub input: String,
+    /// Output of jets, witnesses and words.
+    ///
+    /// The output of other combinators is computed by subsequent steps.
+    pub output: Option<String>,
+    /// Number of tasks that were waiting for the combinator to finish.
+    pub depth: usize,
+}
+
+/// Sequence of executed combinators.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct ExecutionTrace {
+    /// Executed steps, in order of execution.
+    pub steps: Vec<TraceStep>,
+    /// Whether steps were omitted because the trace grew too long.
+    pub truncated: bool,
+    /// Error that stopped the execution, if any.
+    pub error: Option<String>,
+}
+
+impl ExecutionTrace {
+    fn push(&mut self, step: TraceStep) {
+        match self.steps.len() < MAX_TRACE_STEPS {
+            true => self.steps.push(step),
+            false => self.truncated = true,
+        }
+    }
+
+    fn set_output(&mut self, output: &Value) {
+        if self.truncated {
+            return;
+        }
+        if let Some(step) = self.steps.last_mut() {
+            step.output = Some(output.to_string());
+        }
+    }
+
+    /// Serialize the trace as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("trace consists of strings and numbers")
+    }
+}
+
+/// State of a runner after it ran for some steps.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Progress {
+    /// The runner is about to execute a call at which it should pause.
+    Paused,
+    /// The program ran to completion.
+    Finished,
+    /// The runner executed the maximum number of steps.
+    Unfinished,
+}
+
+/// Frames of a runner, as the bits that the Bit Machine would hold in its frames.
+///
+/// The runner keeps values instead of bits.
+/// Each value on its input stack corresponds to a read frame,
+/// and each value on its output stack corresponds to a written frame,
+/// in the padded encoding of the Bit Machine.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frames {
+    /// Read frames, from the bottom to the active frame.
+    pub read: Vec<Vec<bool>>,
+    /// Write frames, from the bottom to the most recently written frame.
+    pub write: Vec<Vec<bool>>,
+}
+
+/// What a jet read from the transaction environment during a run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Introspection {
+    /// Jet that read the environment.
+    pub jet: Elements,
+    /// Arguments of the call in `SimplicityHL` syntax, such as the index of an input.
+    pub arguments: String,
+    /// Value that the jet returned in `SimplicityHL` syntax, or `None` if the jet failed.
+    pub result: Option<String>,
+    /// Number of times that the jet was called with these arguments.
+    pub count: usize,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Runner {
     /// Stack of tasks to run.
@@ -77,171 +192,490 @@ pub struct Runner {
     active_simplicityhl_call: Option<FallibleCall>,
     /// Output from simplicityhl dbg! expressions, in order of occurrence.
     debug_output: Vec<String>,
+    /// Output from simplicityhl dbg! expressions as raw bits, in order of occurrence.
+    debug_output_bits: Vec<String>,
+    /// Number of witness nodes that were executed.
+    executed_witnesses: usize,
+    /// Trace of executed combinators, if tracing is enabled.
+    trace: Option<ExecutionTrace>,
+    /// Number of times that each jet was called.
+    jet_calls: HashMap<Elements, usize>,
+    /// Readings of the transaction environment, in order of their first occurrence.
+    introspections: Vec<Introspection>,
+    /// Number of tasks that were executed.
+    executed_steps: usize,
+    /// Number of tasks after which the execution stops, if any.
+    step_limit: Option<usize>,
+    /// Resources that the program may use, according to its static bounds.
+    resources: ResourceSummary,
 }
 
-impl Runner {
-    pub fn for_program(program: &SatisfiedProgram) -> Self {
+/// Resources of the Bit Machine that the program uses.
+///
+/// Consensus bounds the cost and memory of a program before it is run,
+/// so these bounds are what a node charges, independently of how far a run gets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResourceSummary {
+    /// Bound on the CPU cost, which the witness stack of the spending input must pay for.
+    pub cost: simplicity::Cost,
+    /// Bound on the number of cells, including the input and output of the program.
+    pub max_cells: usize,
+    /// Bound on the number of frames, including the input and output frames.
+    pub max_frames: usize,
+}
+
+impl ResourceSummary {
+    /// Compute the resource bounds of the program.
+    fn of_program(program: &Expression) -> Self {
+        let arrow = program.arrow();
+        let bounds = program.bounds();
         Self {
+            cost: bounds.cost,
+            max_cells: arrow
+                .source
+                .bit_width()
+                .saturating_add(arrow.target.bit_width())
+                .saturating_add(bounds.extra_cells),
+            max_frames: bounds.extra_frames.saturating_add(2),
+        }
+    }
+}
+
+/// Check that the program can be executed within the resource limits of Simplicity.
+///
+/// Simplicity programs always terminate, but loops are unrolled during compilation,
+/// so loops with large bounds make programs that need too much memory or time.
+fn check_limits(program: &Expression) -> Result<(), String> {
+    // The Bit Machine checks its memory limits when it is set up for the program
+    simplicity::BitMachine::for_program(program).map_err(|error| match error {
+        LimitError::MaxCellsExceeded { got, max, .. } => format!(
+            "The program is too large: it needs up to {got} bits of memory, but the Bit Machine has at most {max}. Use smaller types or smaller loop bounds."
+        ),
+        LimitError::MaxFramesExceeded { got, max, .. } => format!(
+            "The program is nested too deeply: it needs up to {got} frames, but the Bit Machine has at most {max}. Use fewer nested loops and function calls."
+        ),
+        error => format!("The program exceeds the limits of the Bit Machine: {error}"),
+    })?;
+    let cost = program.bounds().cost;
+    if !cost.is_consensus_valid() {
+        return Err(format!(
+            "The program does not terminate within the limits of consensus: its cost of {cost}mWU exceeds the maximum of {}mWU. Use smaller loop bounds.",
+            simplicity::Cost::CONSENSUS_MAX
+        ));
+    }
+    Ok(())
+}
+
+impl Runner {
+    /// Prepare to run the program.
+    ///
+    /// Return an error if the program exceeds the resource limits of Simplicity.
+    pub fn for_program(program: &SatisfiedProgram) -> Result<Self, String> {
+        check_limits(program.redeem())?;
+        let resources = ResourceSummary::of_program(program.redeem());
+        Ok(Self {
             tasks: vec![Task::Execute(program.redeem().clone())],
             input: vec![Value::unit()],
             output: vec![],
             debug_symbols: program.debug_symbols().clone(),
             active_simplicityhl_call: None,
             debug_output: vec![],
+            debug_output_bits: vec![],
+            executed_witnesses: 0,
+            trace: None,
+            jet_calls: HashMap::new(),
+            introspections: vec![],
+            executed_steps: 0,
+            step_limit: None,
+            resources,
+        })
+    }
+
+    /// Return the resources that the program uses, whether the run succeeds or fails.
+    pub fn resource_summary(&self) -> ResourceSummary {
+        self.resources
+    }
+
+    /// Stop the execution after the given number of further steps.
+    ///
+    /// `None` removes the limit.
+    pub fn limit_steps(&mut self, steps: Option<usize>) {
+        self.step_limit = steps.map(|steps| self.executed_steps.saturating_add(steps));
+    }
+
+    /// Return the number of steps that were executed so far.
+    pub fn executed_steps(&self) -> usize {
+        self.executed_steps
+    }
+
+    /// Record an execution trace while running the program.
+    pub fn enable_trace(&mut self) {
+        self.trace = Some(ExecutionTrace::default());
+    }
+
+    /// Take the execution trace, if tracing was enabled.
+    pub fn take_trace(&mut self) -> Option<ExecutionTrace> {
+        self.trace.take()
+    }
+
+    /// Return the number of witness nodes that were executed so far.
+    pub fn executed_witnesses(&self) -> usize {
+        self.executed_witnesses
+    }
+
+    /// Return how many times each jet was called so far,
+    /// starting with the most frequently called jet.
+    pub fn jet_calls(&self) -> Vec<(Elements, usize)> {
+        let mut calls = self
+            .jet_calls
+            .iter()
+            .map(|(jet, count)| (*jet, *count))
+            .collect::<Vec<_>>();
+        calls.sort_by(|(jet_a, count_a), (jet_b, count_b)| {
+            count_b.cmp(count_a).then(jet_a.cmp(jet_b))
+        });
+        calls
+    }
+
+    /// Return what the jets that read the transaction environment saw so far.
+    pub fn introspections(&self) -> &[Introspection] {
+        &self.introspections
+    }
+
+    /// Record a call of a jet that reads the transaction environment.
+    ///
+    /// Repeated calls with the same arguments are counted instead of recorded again.
+    fn record_introspection(&mut self, jet: Elements, input: &Value, output: Option<&Value>) {
+        let arguments = jet::format_arguments(jet, input);
+        let result = output.map(|output| jet::format_result(jet, output));
+        match self
+            .introspections
+            .iter_mut()
+            .find(|reading| reading.jet == jet && reading.arguments == arguments)
+        {
+            Some(reading) => reading.count += 1,
+            None => self.introspections.push(Introspection {
+                jet,
+                arguments,
+                result,
+                count: 1,
+            }),
         }
     }
 
-    pub fn debug_output(self) -> Vec<String> {
-        self.debug_output
+    pub fn debug_output(&self) -> &[String] {
+        &self.debug_output
+    }
+
+    /// Return the output from `dbg!` expressions with their values as compact bits.
+    pub fn debug_output_bits(&self) -> &[String] {
+        &self.debug_output_bits
+    }
+
+    /// Check if the program ran to completion.
+    pub fn is_finished(&self) -> bool {
+        self.tasks.is_empty()
     }
 
+    /// Return the tracked `SimplicityHL` call that is about to be executed,
+    /// together with its Simplicity input value.
+    ///
+    /// Return `None` if the next task is not a tracked call.
+    pub fn next_call(&self) -> Option<(&TrackedCall, &Value)> {
+        let Some(Task::Execute(expression)) = self.tasks.last() else {
+            return None;
+        };
+        let Inner::AssertL(_, cmr) = expression.inner() else {
+            return None;
+        };
+        let call = self.debug_symbols.get(cmr)?;
+        let input = self.input.last()?;
+        Some((call, input))
+    }
+
+    /// Return the frames of the machine in its current state.
+    pub fn frames(&self) -> Frames {
+        let bits = |values: &[Value]| {
+            values
+                .iter()
+                .map(|value| value.iter_padded().collect())
+                .collect()
+        };
+        Frames {
+            read: bits(&self.input),
+            write: bits(&self.output),
+        }
+    }
+
+    /// Run the program to completion.
     pub fn run(&mut self, env: &ElementsEnv<Arc<elements::Transaction>>) -> Result<(), ErrorKind> {
-        while let Some(task) = self.tasks.pop() {
-            match task {
-                Task::Execute(expression) => {
-                    let input = self.input.pop().unwrap();
-                    match expression.inner() {
-                        Inner::Iden => self.output.push(input),
-                        Inner::Unit => self.output.push(Value::unit()),
-                        Inner::InjL(t) => {
-                            let ty_r = expression.arrow().target.as_sum().unwrap().1;
-                            self.tasks.push(Task::MakeLeft(Arc::clone(ty_r)));
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(input);
-                        }
-                        Inner::InjR(t) => {
-                            let ty_l = expression.arrow().target.as_sum().unwrap().0;
-                            self.tasks.push(Task::MakeRight(Arc::clone(ty_l)));
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(input);
-                        }
-                        Inner::Take(t) => {
-                            let (a, _) = input.as_product().ok_or(ErrorKind::WrongType)?;
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(a.to_value());
-                        }
-                        Inner::Drop(t) => {
-                            let (_, b) = input.as_product().ok_or(ErrorKind::WrongType)?;
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(b.to_value());
-                        }
-                        Inner::Comp(s, t) => {
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::MoveLeftCompOutput);
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            self.input.push(input);
-                        }
-                        Inner::Pair(s, t) => {
-                            self.tasks.push(Task::MakeProduct);
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            self.input.push(input.shallow_clone());
-                            self.input.push(input);
-                        }
-                        Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..) => {
-                            let (sum_a_b, c) = input.as_product().ok_or(ErrorKind::WrongType)?;
-
-                            if let Inner::AssertL(_, cmr) = expression.inner() {
-                                if let Some(tracked_call) = self.debug_symbols.get(cmr) {
-                                    match tracked_call.map_value(
-                                        &simplicityhl::value::StructuralValue::from(c.to_value()),
-                                    ) {
-                                        Some(Either::Left(fallible_call)) => {
-                                            let replaced = self
-                                                .active_simplicityhl_call
-                                                .replace(fallible_call);
-                                            debug_assert!(replaced.is_none());
-                                            self.tasks.push(Task::ResetActiveSimplicityHLCall);
-                                        }
-                                        Some(Either::Right(debug_value)) => {
-                                            let s = format!(
-                                                "`{}` = `{}`",
-                                                debug_value.text(),
-                                                debug_value.value()
-                                            );
-                                            self.debug_output.push(s);
-                                        }
-                                        None => {}
+        while !self.is_finished() {
+            self.step(env)?;
+        }
+        Ok(())
+    }
+
+    /// Run the program to completion, but abort it once it executed `max_steps` further steps.
+    ///
+    /// Runs that cannot yield to the browser use this, so that no program freezes the page.
+    pub fn run_with_limit(
+        &mut self,
+        env: &ElementsEnv<Arc<elements::Transaction>>,
+        max_steps: usize,
+    ) -> Result<(), ErrorKind> {
+        let limit = self.executed_steps.saturating_add(max_steps);
+        while !self.is_finished() {
+            if limit <= self.executed_steps {
+                return Err(ErrorKind::ExecutionAborted { steps: max_steps });
+            }
+            self.step(env)?;
+        }
+        Ok(())
+    }
+
+    /// Run the program for at most `max_steps` steps,
+    /// until it is about to execute a tracked call that satisfies `pause`,
+    /// given the call and its Simplicity input value.
+    ///
+    /// If `force_first` is set, the first task is executed without checking `pause`,
+    /// so a runner that paused at a call makes progress when it is resumed.
+    /// Running in portions lets the caller yield to the browser in between.
+    pub fn run_steps<F>(
+        &mut self,
+        env: &ElementsEnv<Arc<elements::Transaction>>,
+        max_steps: usize,
+        force_first: bool,
+        mut pause: F,
+    ) -> Result<Progress, ErrorKind>
+    where
+        F: FnMut(&TrackedCall, &Value) -> bool,
+    {
+        for index in 0..max_steps {
+            if self.is_finished() {
+                return Ok(Progress::Finished);
+            }
+            let skip_check = force_first && index == 0;
+            if !skip_check
+                && self
+                    .next_call()
+                    .is_some_and(|(call, input)| pause(call, input))
+            {
+                return Ok(Progress::Paused);
+            }
+            self.step(env)?;
+        }
+        match self.is_finished() {
+            true => Ok(Progress::Finished),
+            false => Ok(Progress::Unfinished),
+        }
+    }
+
+    /// Execute the next task of the program.
+    ///
+    /// Do nothing if the program ran to completion.
+    /// Fail without doing anything if the step limit is reached.
+    pub fn step(&mut self, env: &ElementsEnv<Arc<elements::Transaction>>) -> Result<(), ErrorKind> {
+        if self
+            .step_limit
+            .is_some_and(|limit| limit <= self.executed_steps)
+        {
+            return Err(ErrorKind::StepLimitReached);
+        }
+        self.executed_steps += 1;
+        let result = self.step_inner(env);
+        if let (Some(trace), Err(error)) = (&mut self.trace, &result) {
+            trace.error = Some(error.to_string());
+        }
+        result
+    }
+
+    fn step_inner(
+        &mut self,
+        env: &ElementsEnv<Arc<elements::Transaction>>,
+    ) -> Result<(), ErrorKind> {
+        let Some(task) = self.tasks.pop() else {
+            return Ok(());
+        };
+        match task {
+            Task::Execute(expression) => {
+                let input = self.input.pop().unwrap();
+                if let Some(trace) = &mut self.trace {
+                    trace.push(TraceStep {
+                        node: expression.inner().to_string(),
+                        cmr: expression.cmr().to_string(),
+                        input: input.to_string(),
+                        output: None,
+                        depth: self.tasks.len(),
+                    });
+                }
+                match expression.inner() {
+                    Inner::Iden => self.output.push(input),
+                    Inner::Unit => self.output.push(Value::unit()),
+                    Inner::InjL(t) => {
+                        let ty_r = expression.arrow().target.as_sum().unwrap().1;
+                        self.tasks.push(Task::MakeLeft(Arc::clone(ty_r)));
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.input.push(input);
+                    }
+                    Inner::InjR(t) => {
+                        let ty_l = expression.arrow().target.as_sum().unwrap().0;
+                        self.tasks.push(Task::MakeRight(Arc::clone(ty_l)));
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.input.push(input);
+                    }
+                    Inner::Take(t) => {
+                        let (a, _) = input.as_product().ok_or(ErrorKind::WrongType)?;
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.input.push(a.to_value());
+                    }
+                    Inner::Drop(t) => {
+                        let (_, b) = input.as_product().ok_or(ErrorKind::WrongType)?;
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.input.push(b.to_value());
+                    }
+                    Inner::Comp(s, t) => {
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::MoveLeftCompOutput);
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        self.input.push(input);
+                    }
+                    Inner::Pair(s, t) => {
+                        self.tasks.push(Task::MakeProduct);
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        self.input.push(input.shallow_clone());
+                        self.input.push(input);
+                    }
+                    Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..) => {
+                        let (sum_a_b, c) = input.as_product().ok_or(ErrorKind::WrongType)?;
+
+                        if let Inner::AssertL(_, cmr) = expression.inner() {
+                            if let Some(tracked_call) = self.debug_symbols.get(cmr) {
+                                match tracked_call.map_value(&StructuralValue::from(c.to_value())) {
+                                    Some(Either::Left(fallible_call)) => {
+                                        let replaced =
+                                            self.active_simplicityhl_call.replace(fallible_call);
+                                        debug_assert!(replaced.is_none());
+                                        self.tasks.push(Task::ResetActiveSimplicityHLCall);
+                                    }
+                                    Some(Either::Right(debug_value)) => {
+                                        let s = format!(
+                                            "`{}` = `{}`",
+                                            debug_value.text(),
+                                            debug_value.value()
+                                        );
+                                        self.debug_output.push(s);
+                                        let value =
+                                            Value::from(StructuralValue::from(debug_value.value()));
+                                        self.debug_output_bits.push(format!(
+                                            "`{}` = {}",
+                                            debug_value.text(),
+                                            value::compact_bits(&value)
+                                        ));
                                     }
+                                    None => {}
                                 }
                             }
+                        }
 
-                            if let Some(a) = sum_a_b.as_left() {
-                                match expression.inner() {
-                                    Inner::Case(s, _) | Inner::AssertL(s, _) => {
-                                        self.tasks.push(Task::Execute(Arc::clone(s)));
-                                        self.input.push(Value::product(a.to_value(), c.to_value()));
-                                    }
-                                    Inner::AssertR(_, _) => {
-                                        return Err(self.error(ErrorKind::AssertionFailed))
-                                    }
-                                    _ => unreachable!("Covered by outer match statement"),
+                        if let Some(a) = sum_a_b.as_left() {
+                            match expression.inner() {
+                                Inner::Case(s, _) | Inner::AssertL(s, _) => {
+                                    self.tasks.push(Task::Execute(Arc::clone(s)));
+                                    self.input.push(Value::product(a.to_value(), c.to_value()));
                                 }
-                            } else if let Some(b) = sum_a_b.as_right() {
-                                match expression.inner() {
-                                    Inner::Case(_, t) | Inner::AssertR(_, t) => {
-                                        self.tasks.push(Task::Execute(Arc::clone(t)));
-                                        self.input.push(Value::product(b.to_value(), c.to_value()));
-                                    }
-                                    Inner::AssertL(_, _) => {
-                                        return Err(self.error(ErrorKind::AssertionFailed))
-                                    }
-                                    _ => unreachable!("Covered by outer match statement"),
+                                Inner::AssertR(_, _) => {
+                                    return Err(self.error(ErrorKind::AssertionFailed))
+                                }
+                                _ => unreachable!("Covered by outer match statement"),
+                            }
+                        } else if let Some(b) = sum_a_b.as_right() {
+                            match expression.inner() {
+                                Inner::Case(_, t) | Inner::AssertR(_, t) => {
+                                    self.tasks.push(Task::Execute(Arc::clone(t)));
+                                    self.input.push(Value::product(b.to_value(), c.to_value()));
+                                }
+                                Inner::AssertL(_, _) => {
+                                    return Err(self.error(ErrorKind::AssertionFailed))
                                 }
-                            } else {
-                                return Err(ErrorKind::WrongType);
+                                _ => unreachable!("Covered by outer match statement"),
                             }
+                        } else {
+                            return Err(ErrorKind::WrongType);
                         }
-                        Inner::Disconnect(s, t) => {
-                            self.tasks.push(Task::MakeProduct);
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::MoveLeftDisconnectOutput);
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            let t_cmr = Value::u256(t.cmr().to_byte_array());
-                            self.input.push(Value::product(t_cmr, input));
+                    }
+                    Inner::Disconnect(s, t) => {
+                        self.tasks.push(Task::MakeProduct);
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::MoveLeftDisconnectOutput);
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        let t_cmr = Value::u256(t.cmr().to_byte_array());
+                        self.input.push(Value::product(t_cmr, input));
+                    }
+                    Inner::Witness(value) => {
+                        self.executed_witnesses += 1;
+                        self.push_leaf_output(value.shallow_clone());
+                    }
+                    Inner::Fail(_) => return Err(self.error(ErrorKind::FailNode)),
+                    Inner::Jet(jet) => {
+                        *self.jet_calls.entry(*jet).or_default() += 1;
+                        let output = jet::execute_jet_with_env(jet, &input, env);
+                        if jet::reads_environment(*jet) {
+                            self.record_introspection(*jet, &input, output.as_ref().ok());
+                        }
+                        match output {
+                            Ok(output) => self.push_leaf_output(output),
+                            Err(JetError::Failed) => return Err(self.error(ErrorKind::JetFailed)),
+                            Err(JetError::WrongInput) => {
+                                return Err(self.error(ErrorKind::WrongType))
+                            }
                         }
-                        Inner::Witness(value) => self.output.push(value.shallow_clone()),
-                        Inner::Fail(_) => return Err(self.error(ErrorKind::FailNode)),
-                        Inner::Jet(jet) => match jet::execute_jet_with_env(jet, &input, env) {
-                            Ok(output) => self.output.push(output),
-                            Err(JetFailed) => return Err(self.error(ErrorKind::JetFailed)),
-                        },
-                        Inner::Word(word) => self.output.push(word.as_value().shallow_clone()),
                     }
+                    Inner::Word(word) => self.push_leaf_output(word.as_value().shallow_clone()),
                 }
-                Task::MoveLeftCompOutput => {
-                    let output = self.output.pop().unwrap();
-                    self.input.push(output);
-                }
-                Task::MoveLeftDisconnectOutput => {
-                    let prod_b_c = self.output.pop().unwrap();
-                    let (b, c) = prod_b_c.as_product().unwrap();
-                    self.output.push(b.to_value());
-                    self.input.push(c.to_value());
-                }
-                Task::MakeLeft(ty_r) => {
-                    let val_l = self.output.pop().unwrap();
-                    self.output.push(Value::left(val_l, ty_r));
-                }
-                Task::MakeRight(ty_l) => {
-                    let val_r = self.output.pop().unwrap();
-                    self.output.push(Value::right(ty_l, val_r));
-                }
-                Task::MakeProduct => {
-                    let b = self.output.pop().unwrap();
-                    let a = self.output.pop().unwrap();
-                    self.output.push(Value::product(a, b));
-                }
-                Task::ResetActiveSimplicityHLCall => self.active_simplicityhl_call = None,
             }
+            Task::MoveLeftCompOutput => {
+                let output = self.output.pop().unwrap();
+                self.input.push(output);
+            }
+            Task::MoveLeftDisconnectOutput => {
+                let prod_b_c = self.output.pop().unwrap();
+                let (b, c) = prod_b_c.as_product().unwrap();
+                self.output.push(b.to_value());
+                self.input.push(c.to_value());
+            }
+            Task::MakeLeft(ty_r) => {
+                let val_l = self.output.pop().unwrap();
+                self.output.push(Value::left(val_l, ty_r));
+            }
+            Task::MakeRight(ty_l) => {
+                let val_r = self.output.pop().unwrap();
+                self.output.push(Value::right(ty_l, val_r));
+            }
+            Task::MakeProduct => {
+                let b = self.output.pop().unwrap();
+                let a = self.output.pop().unwrap();
+                self.output.push(Value::product(a, b));
+            }
+            Task::ResetActiveSimplicityHLCall => self.active_simplicityhl_call = None,
         }
 
-        debug_assert!(self.input.is_empty());
-        debug_assert_eq!(self.output.len(), 1);
-        debug_assert!(self.output.pop().unwrap().is_unit());
+        if self.is_finished() {
+            debug_assert!(self.input.is_empty());
+            debug_assert_eq!(self.output.len(), 1);
+            debug_assert!(self.output.pop().unwrap().is_unit());
+        }
         Ok(())
     }
 
+    /// Push the output of a leaf combinator and record it in the trace.
+    fn push_leaf_output(&mut self, output: Value) {
+        if let Some(trace) = &mut self.trace {
+            trace.set_output(&output);
+        }
+        self.output.push(output);
+    }
+
     /// Try to return an error with simplicityhl debug information included.
     /// Otherwise, return the original error.
     fn error(&self, error: ErrorKind) -> ErrorKind {
@@ -255,7 +689,8 @@ impl Runner {
 #[cfg(test)]
 mod tests {
     use simplicityhl::elements::{hashes::Hash, secp256k1_zkp as secp256k1};
-    use simplicityhl::CompiledProgram;
+    use simplicityhl::parse::ParseFromStr;
+    use simplicityhl::{CompiledProgram, WitnessValues};
 
     use super::*;
     use crate::examples;
@@ -289,6 +724,27 @@ mod tests {
         (satisfied, tx_env)
     }
 
+    /// Prepare to run the program `text` with the witness of its witness module,
+    /// in the default transaction environment.
+    fn runner_for(
+        text: &str,
+        include_debug_symbols: bool,
+    ) -> (Runner, ElementsEnv<Arc<elements::Transaction>>) {
+        let compiled = CompiledProgram::new(
+            text,
+            simplicityhl::Arguments::default(),
+            include_debug_symbols,
+        )
+        .expect("program should compile");
+        let witness = WitnessValues::parse_from_str(text).unwrap_or_default();
+        let satisfied = compiled
+            .satisfy(witness)
+            .expect("program should be satisfied");
+        let tx_env = crate::transaction::TxParams::default().tx_env(compiled.commit().cmr());
+        let runner = Runner::for_program(&satisfied).expect("program within limits");
+        (runner, tx_env)
+    }
+
     #[test]
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn run_examples() {
@@ -299,7 +755,7 @@ mod tests {
             println!("{name}");
             let example = examples::get(name).unwrap();
             let (satisfied, tx_env) = satisfied_and_tx_env(example, &signing_keys, &hashed_data);
-            let mut runner = Runner::for_program(&satisfied);
+            let mut runner = Runner::for_program(&satisfied).unwrap();
             if let Err(error) = runner.run(&tx_env) {
                 println!("sighash all = {}", tx_env.c_tx_env().sighash_all());
                 for debug_line in runner.debug_output() {
@@ -323,7 +779,7 @@ mod tests {
             let rust_simplicity_result = simplicity::BitMachine::for_program(satisfied.redeem())
                 .expect("program within limits")
                 .exec(satisfied.redeem(), &tx_env);
-            let webide_result = Runner::for_program(&satisfied).run(&tx_env);
+            let webide_result = Runner::for_program(&satisfied).unwrap().run(&tx_env);
             match (rust_simplicity_result, webide_result) {
                 (Ok(..), Err(error)) => {
                     panic!("rust-simplicity accepted but web IDE rejected: {error}")
@@ -335,4 +791,302 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn count_executed_witnesses() {
+        let text = r#"mod witness {
+    const X: u8 = 0;
+}
+
+fn main() {
+    let a: bool = true;
+    match a {
+        true => {},
+        false => assert!(jet::eq_8(witness::X, 0)),
+    };
+}"#;
+        let (mut runner, tx_env) = runner_for(text, false);
+        runner.run(&tx_env).expect("program should run");
+        assert_eq!(0, runner.executed_witnesses());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn record_trace() {
+        let text = r#"fn main() {
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    assert!(jet::eq_8(sum, 3));
+}"#;
+        let (mut runner, tx_env) = runner_for(text, false);
+        runner.enable_trace();
+        runner.run(&tx_env).expect("program should run");
+        let trace = runner.take_trace().expect("tracing is enabled");
+
+        assert!(!trace.truncated);
+        assert!(trace.error.is_none());
+        let add = trace
+            .steps
+            .iter()
+            .find(|step| step.node == "jet(add_8)")
+            .expect("jet should be traced");
+        assert!(add.output.is_some());
+        assert_eq!(0, trace.steps[0].depth);
+
+        let jet_calls = runner.jet_calls();
+        assert!(jet_calls.contains(&(Elements::Add8, 1)));
+        assert!(jet_calls.contains(&(Elements::Eq8, 1)));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn pause_at_call() {
+        let text = r#"fn main() {
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    assert!(jet::eq_8(sum, 3));
+}"#;
+        let (mut runner, tx_env) = runner_for(text, true);
+
+        let paused = runner
+            .run_steps(&tx_env, usize::MAX, true, |call, _| {
+                call.text().contains("eq_8")
+            })
+            .expect("program should run");
+        assert_eq!(Progress::Paused, paused);
+        let (call, _input) = runner.next_call().expect("runner paused at call");
+        assert!(call.text().contains("jet::eq_8(sum, 3)"));
+        assert_eq!(
+            0,
+            runner
+                .jet_calls()
+                .iter()
+                .filter(|(jet, _)| *jet == Elements::Eq8)
+                .count()
+        );
+
+        let paused = runner
+            .run_steps(&tx_env, usize::MAX, true, |_, _| false)
+            .expect("program should run");
+        assert_eq!(Progress::Finished, paused);
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn pause_at_input_value() {
+        let text = r#"fn check(x: u8) {
+    assert!(jet::eq_8(x, x));
+}
+
+fn main() {
+    check(1);
+    check(2);
+}"#;
+        let (initial, tx_env) = runner_for(text, true);
+        let mut runner = initial.clone();
+
+        let mut inputs = Vec::new();
+        runner
+            .run_steps(&tx_env, usize::MAX, true, |call, input| {
+                if call.text().starts_with("jet::eq_8") {
+                    inputs.push(input.to_string());
+                }
+                false
+            })
+            .expect("program should run");
+        assert_eq!(2, inputs.len());
+        assert_ne!(inputs[0], inputs[1]);
+
+        let mut runner = initial;
+        let paused = runner
+            .run_steps(&tx_env, usize::MAX, true, |_, input| {
+                input.to_string() == inputs[1]
+            })
+            .expect("program should run");
+        assert_eq!(Progress::Paused, paused);
+        let eq_8_calls = |runner: &Runner| {
+            runner
+                .jet_calls()
+                .iter()
+                .find(|(jet, _)| *jet == Elements::Eq8)
+                .map_or(0, |(_, count)| *count)
+        };
+        assert_eq!(1, eq_8_calls(&runner));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn run_in_portions() {
+        let text = r#"fn main() {
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    assert!(jet::eq_8(sum, 3));
+}"#;
+        let (mut runner, tx_env) = runner_for(text, false);
+
+        let mut portions = 0;
+        loop {
+            portions += 1;
+            let progress = runner
+                .run_steps(&tx_env, 3, false, |_, _| false)
+                .expect("program should run");
+            if progress == Progress::Finished {
+                break;
+            }
+            assert_eq!(Progress::Unfinished, progress);
+        }
+        assert!(1 < portions);
+        assert_eq!(portions * 3, runner.executed_steps().next_multiple_of(3));
+        assert!(runner.jet_calls().contains(&(Elements::Eq8, 1)));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn record_introspection() {
+        let text = r#"fn main() {
+    let index: u32 = jet::current_index();
+    assert!(jet::eq_32(index, jet::current_index()));
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    let _: Option<u32> = jet::input_sequence(index);
+}"#;
+        let (mut runner, tx_env) = runner_for(text, false);
+        runner.run(&tx_env).expect("program should run");
+
+        let introspections = runner.introspections();
+        assert_eq!(2, introspections.len());
+        assert_eq!(Elements::CurrentIndex, introspections[0].jet);
+        assert_eq!("", introspections[0].arguments);
+        assert_eq!(Some("0"), introspections[0].result.as_deref());
+        assert_eq!(2, introspections[0].count);
+        assert_eq!(Elements::InputSequence, introspections[1].jet);
+        assert_eq!("0", introspections[1].arguments);
+        assert!(introspections[1].result.is_some());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn stop_at_step_limit() {
+        let text = r#"fn main() {
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    assert!(jet::eq_8(sum, 3));
+}"#;
+        let (initial, tx_env) = runner_for(text, false);
+        let mut runner = initial.clone();
+
+        runner.limit_steps(Some(3));
+        assert_eq!(Err(ErrorKind::StepLimitReached), runner.run(&tx_env));
+        assert_eq!(3, runner.executed_steps());
+        assert!(!runner.is_finished());
+
+        runner.limit_steps(None);
+        runner.run(&tx_env).expect("program should run");
+        assert!(runner.is_finished());
+
+        let mut runner = initial.clone();
+        assert_eq!(
+            Err(ErrorKind::ExecutionAborted { steps: 3 }),
+            runner.run_with_limit(&tx_env, 3)
+        );
+        assert_eq!(3, runner.executed_steps());
+        let mut runner = initial;
+        runner
+            .run_with_limit(&tx_env, usize::MAX)
+            .expect("program should run");
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn debug_output_as_bits() {
+        let text = r#"fn main() {
+    let x: u8 = dbg!(5);
+    assert!(jet::eq_8(x, 5));
+}"#;
+        let (mut runner, tx_env) = runner_for(text, true);
+        runner.run(&tx_env).expect("program should run");
+
+        assert_eq!(["`5` = `5`"], runner.debug_output());
+        assert_eq!(["`5` = 00000101"], runner.debug_output_bits());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn summarize_resources() {
+        let text = "fn main() { let (_, sum): (bool, u32) = jet::add_32(1, 2); assert!(jet::eq_32(sum, 4)); }";
+        let compiled = CompiledProgram::new(text, simplicityhl::Arguments::default(), false)
+            .expect("program should compile");
+        let satisfied = compiled
+            .satisfy(WitnessValues::default())
+            .expect("program should be satisfied");
+        let mut runner = Runner::for_program(&satisfied).expect("program within limits");
+        let env = examples::get(examples::STARTER)
+            .unwrap()
+            .params()
+            .tx_env(compiled.commit().cmr());
+        assert!(runner.run(&env).is_err());
+
+        let summary = runner.resource_summary();
+        let bounds = satisfied.redeem().bounds();
+        assert_eq!(bounds.cost, summary.cost);
+        assert!(summary.cost.is_consensus_valid());
+        assert_eq!(bounds.extra_frames + 2, summary.max_frames);
+        assert!(bounds.extra_cells <= summary.max_cells);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn reject_program_beyond_limits() {
+        let text = r#"mod witness {
+    const SIG: [u8; 64] = 0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000;
+}
+
+fn verify(sig: [u8; 64], unused: (), counter: u16) -> Either<(), [u8; 64]> {
+    let pk: u256 = 0xf9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9;
+    let msg: u256 = 0x0000000000000000000000000000000000000000000000000000000000000000;
+    jet::bip_0340_verify((pk, msg), sig);
+    jet::bip_0340_verify((pk, msg), sig);
+    Right(sig)
+}
+
+fn main() {
+    let out: Either<(), [u8; 64]> = for_while::<verify>(witness::SIG, ());
+}"#;
+        let compiled = CompiledProgram::new(text, simplicityhl::Arguments::default(), false)
+            .expect("program should compile");
+        let witness = WitnessValues::parse_from_str(text).unwrap();
+        let satisfied = compiled
+            .satisfy(witness)
+            .expect("program should be satisfied");
+        let Err(error) = Runner::for_program(&satisfied) else {
+            panic!("program should exceed the limits");
+        };
+        assert!(
+            error.contains("does not terminate within the limits"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn frames_of_values() {
+        let text = r#"fn main() {
+    let (_, sum): (bool, u8) = jet::add_8(1, 2);
+    assert!(jet::eq_8(sum, 3));
+}"#;
+        let (mut runner, tx_env) = runner_for(text, true);
+        assert_eq!(
+            Frames {
+                read: vec![vec![]],
+                write: vec![]
+            },
+            runner.frames()
+        );
+
+        let mut seen_byte = false;
+        while !runner.is_finished() {
+            runner.step(&tx_env).expect("program should run");
+            let frames = runner.frames();
+            seen_byte |= frames.write.iter().any(|bits| bits.len() == 8);
+        }
+        assert!(seen_byte, "the sum should be written as a frame of 8 bits");
+    }
 }
@@ -1,19 +1,31 @@
 use std::sync::Arc;
 
-use simplicity::jet::Jet;
+use std::str::FromStr;
+
+use simplicity::jet::{Core, Elements, Jet};
 use simplicity::node::JetConstructible as _;
 use simplicity::types::Context;
 use simplicity::{BitMachine, ConstructNode, Value};
 use simplicityhl::simplicity;
+use simplicityhl::types::TypeConstructible as _;
+use simplicityhl::value::{StructuralValue, ValueInner};
+use simplicityhl::ResolvedType;
 
-pub struct JetFailed;
+/// Error of executing a jet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JetError {
+    /// The jet failed, like a failed assertion.
+    Failed,
+    /// The input value does not have the source type of the jet.
+    WrongInput,
+}
 
 /// Execute a jet on an input and inside an environment. Return the output.
 pub fn execute_jet_with_env<J: Jet>(
     jet: &J,
     input: &Value,
     env: &J::Environment,
-) -> Result<Value, JetFailed> {
+) -> Result<Value, JetError> {
     let prog = Context::with_context(|ctx| {
         Arc::<ConstructNode<J>>::jet(&ctx, *jet)
             .finalize_unpruned()
@@ -22,16 +34,748 @@ pub fn execute_jet_with_env<J: Jet>(
 
     let mut mac = BitMachine::for_program(&prog).expect("a single jet is within limits");
 
-    mac.input(input).expect("no problem with input");
+    // The input comes from the program, so a malformed program must not crash the IDE
+    mac.input(input).map_err(|_| JetError::WrongInput)?;
 
     // The only execution error possible is a jet failure
-    mac.exec(&prog, env).map_err(|_| JetFailed)
+    mac.exec(&prog, env).map_err(|_| JetError::Failed)
+}
+
+/// Return the signature of a jet in `SimplicityHL` syntax,
+/// such as `fn add_32(u32, u32) -> (bool, u32)`.
+pub fn signature(jet: Elements) -> String {
+    let source = simplicityhl::jet::source_type(jet)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let target = simplicityhl::jet::target_type(jet);
+    format!("fn {jet}({source}) -> {target}")
+}
+
+/// Return a one-line description of a jet, including its signature and its cost.
+pub fn description(jet: Elements) -> String {
+    format!("{} (costs {} mWU)", signature(jet), jet.cost())
+}
+
+/// Documentation of the jets as Rust functions, which keeps each jet in the module of its category.
+const DOCUMENTATION_URL: &str =
+    "https://docs.rs/simplicityhl-as-rust/latest/simplicityhl_as_rust/jet";
+
+/// Names of the jets that have an entry in the documentation, by the module of their category.
+///
+/// Jets that are missing here, such as recently added ones, have no documentation link.
+const DOCUMENTED_JETS: &[(&str, &[&str])] = &[
+    (
+        "multi_bit_logic",
+        &[
+            "all_16",
+            "all_32",
+            "all_64",
+            "all_8",
+            "and_1",
+            "and_16",
+            "and_32",
+            "and_64",
+            "and_8",
+            "ch_1",
+            "ch_16",
+            "ch_32",
+            "ch_64",
+            "ch_8",
+            "complement_1",
+            "complement_16",
+            "complement_32",
+            "complement_64",
+            "complement_8",
+            "eq_1",
+            "eq_16",
+            "eq_256",
+            "eq_32",
+            "eq_64",
+            "eq_8",
+            "full_left_shift_16_1",
+            "full_left_shift_16_2",
+            "full_left_shift_16_4",
+            "full_left_shift_16_8",
+            "full_left_shift_32_1",
+            "full_left_shift_32_16",
+            "full_left_shift_32_2",
+            "full_left_shift_32_4",
+            "full_left_shift_32_8",
+            "full_left_shift_64_1",
+            "full_left_shift_64_16",
+            "full_left_shift_64_2",
+            "full_left_shift_64_32",
+            "full_left_shift_64_4",
+            "full_left_shift_64_8",
+            "full_left_shift_8_1",
+            "full_left_shift_8_2",
+            "full_left_shift_8_4",
+            "full_right_shift_16_1",
+            "full_right_shift_16_2",
+            "full_right_shift_16_4",
+            "full_right_shift_16_8",
+            "full_right_shift_32_1",
+            "full_right_shift_32_16",
+            "full_right_shift_32_2",
+            "full_right_shift_32_4",
+            "full_right_shift_32_8",
+            "full_right_shift_64_1",
+            "full_right_shift_64_16",
+            "full_right_shift_64_2",
+            "full_right_shift_64_32",
+            "full_right_shift_64_4",
+            "full_right_shift_64_8",
+            "full_right_shift_8_1",
+            "full_right_shift_8_2",
+            "full_right_shift_8_4",
+            "high_1",
+            "high_16",
+            "high_32",
+            "high_64",
+            "high_8",
+            "left_extend_16_32",
+            "left_extend_16_64",
+            "left_extend_1_16",
+            "left_extend_1_32",
+            "left_extend_1_64",
+            "left_extend_1_8",
+            "left_extend_32_64",
+            "left_extend_8_16",
+            "left_extend_8_32",
+            "left_extend_8_64",
+            "left_pad_high_16_32",
+            "left_pad_high_16_64",
+            "left_pad_high_1_16",
+            "left_pad_high_1_32",
+            "left_pad_high_1_64",
+            "left_pad_high_1_8",
+            "left_pad_high_32_64",
+            "left_pad_high_8_16",
+            "left_pad_high_8_32",
+            "left_pad_high_8_64",
+            "left_pad_low_16_32",
+            "left_pad_low_16_64",
+            "left_pad_low_1_16",
+            "left_pad_low_1_32",
+            "left_pad_low_1_64",
+            "left_pad_low_1_8",
+            "left_pad_low_32_64",
+            "left_pad_low_8_16",
+            "left_pad_low_8_32",
+            "left_pad_low_8_64",
+            "left_rotate_16",
+            "left_rotate_32",
+            "left_rotate_64",
+            "left_rotate_8",
+            "left_shift_16",
+            "left_shift_32",
+            "left_shift_64",
+            "left_shift_8",
+            "left_shift_with_16",
+            "left_shift_with_32",
+            "left_shift_with_64",
+            "left_shift_with_8",
+            "leftmost_16_1",
+            "leftmost_16_2",
+            "leftmost_16_4",
+            "leftmost_16_8",
+            "leftmost_32_1",
+            "leftmost_32_16",
+            "leftmost_32_2",
+            "leftmost_32_4",
+            "leftmost_32_8",
+            "leftmost_64_1",
+            "leftmost_64_16",
+            "leftmost_64_2",
+            "leftmost_64_32",
+            "leftmost_64_4",
+            "leftmost_64_8",
+            "leftmost_8_1",
+            "leftmost_8_2",
+            "leftmost_8_4",
+            "low_1",
+            "low_16",
+            "low_32",
+            "low_64",
+            "low_8",
+            "maj_1",
+            "maj_16",
+            "maj_32",
+            "maj_64",
+            "maj_8",
+            "or_1",
+            "or_16",
+            "or_32",
+            "or_64",
+            "or_8",
+            "right_extend_16_32",
+            "right_extend_16_64",
+            "right_extend_32_64",
+            "right_extend_8_16",
+            "right_extend_8_32",
+            "right_extend_8_64",
+            "right_pad_high_16_32",
+            "right_pad_high_16_64",
+            "right_pad_high_1_16",
+            "right_pad_high_1_32",
+            "right_pad_high_1_64",
+            "right_pad_high_1_8",
+            "right_pad_high_32_64",
+            "right_pad_high_8_16",
+            "right_pad_high_8_32",
+            "right_pad_high_8_64",
+            "right_pad_low_16_32",
+            "right_pad_low_16_64",
+            "right_pad_low_1_16",
+            "right_pad_low_1_32",
+            "right_pad_low_1_64",
+            "right_pad_low_1_8",
+            "right_pad_low_32_64",
+            "right_pad_low_8_16",
+            "right_pad_low_8_32",
+            "right_pad_low_8_64",
+            "right_rotate_16",
+            "right_rotate_32",
+            "right_rotate_64",
+            "right_rotate_8",
+            "right_shift_16",
+            "right_shift_32",
+            "right_shift_64",
+            "right_shift_8",
+            "right_shift_with_16",
+            "right_shift_with_32",
+            "right_shift_with_64",
+            "right_shift_with_8",
+            "rightmost_16_1",
+            "rightmost_16_2",
+            "rightmost_16_4",
+            "rightmost_16_8",
+            "rightmost_32_1",
+            "rightmost_32_16",
+            "rightmost_32_2",
+            "rightmost_32_4",
+            "rightmost_32_8",
+            "rightmost_64_1",
+            "rightmost_64_16",
+            "rightmost_64_2",
+            "rightmost_64_32",
+            "rightmost_64_4",
+            "rightmost_64_8",
+            "rightmost_8_1",
+            "rightmost_8_2",
+            "rightmost_8_4",
+            "some_1",
+            "some_16",
+            "some_32",
+            "some_64",
+            "some_8",
+            "verify",
+            "xor_1",
+            "xor_16",
+            "xor_32",
+            "xor_64",
+            "xor_8",
+            "xor_xor_1",
+            "xor_xor_16",
+            "xor_xor_32",
+            "xor_xor_64",
+            "xor_xor_8",
+        ],
+    ),
+    (
+        "arithmetic",
+        &[
+            "add_16",
+            "add_32",
+            "add_64",
+            "add_8",
+            "decrement_16",
+            "decrement_32",
+            "decrement_64",
+            "decrement_8",
+            "div_mod_128_64",
+            "div_mod_16",
+            "div_mod_32",
+            "div_mod_64",
+            "div_mod_8",
+            "divide_16",
+            "divide_32",
+            "divide_64",
+            "divide_8",
+            "divides_16",
+            "divides_32",
+            "divides_64",
+            "divides_8",
+            "full_add_16",
+            "full_add_32",
+            "full_add_64",
+            "full_add_8",
+            "full_decrement_16",
+            "full_decrement_32",
+            "full_decrement_64",
+            "full_decrement_8",
+            "full_increment_16",
+            "full_increment_32",
+            "full_increment_64",
+            "full_increment_8",
+            "full_multiply_16",
+            "full_multiply_32",
+            "full_multiply_64",
+            "full_multiply_8",
+            "full_subtract_16",
+            "full_subtract_32",
+            "full_subtract_64",
+            "full_subtract_8",
+            "increment_16",
+            "increment_32",
+            "increment_64",
+            "increment_8",
+            "is_one_16",
+            "is_one_32",
+            "is_one_64",
+            "is_one_8",
+            "is_zero_16",
+            "is_zero_32",
+            "is_zero_64",
+            "is_zero_8",
+            "le_16",
+            "le_32",
+            "le_64",
+            "le_8",
+            "lt_16",
+            "lt_32",
+            "lt_64",
+            "lt_8",
+            "max_16",
+            "max_32",
+            "max_64",
+            "max_8",
+            "median_16",
+            "median_32",
+            "median_64",
+            "median_8",
+            "min_16",
+            "min_32",
+            "min_64",
+            "min_8",
+            "modulo_16",
+            "modulo_32",
+            "modulo_64",
+            "modulo_8",
+            "multiply_16",
+            "multiply_32",
+            "multiply_64",
+            "multiply_8",
+            "negate_16",
+            "negate_32",
+            "negate_64",
+            "negate_8",
+            "one_16",
+            "one_32",
+            "one_64",
+            "one_8",
+            "subtract_16",
+            "subtract_32",
+            "subtract_64",
+            "subtract_8",
+        ],
+    ),
+    (
+        "hash_functions",
+        &[
+            "sha_256_block",
+            "sha_256_ctx_8_add_1",
+            "sha_256_ctx_8_add_128",
+            "sha_256_ctx_8_add_16",
+            "sha_256_ctx_8_add_2",
+            "sha_256_ctx_8_add_256",
+            "sha_256_ctx_8_add_32",
+            "sha_256_ctx_8_add_4",
+            "sha_256_ctx_8_add_512",
+            "sha_256_ctx_8_add_64",
+            "sha_256_ctx_8_add_8",
+            "sha_256_ctx_8_add_buffer_511",
+            "sha_256_ctx_8_finalize",
+            "sha_256_ctx_8_init",
+            "sha_256_iv",
+        ],
+    ),
+    (
+        "elliptic_curve_functions",
+        &[
+            "decompress",
+            "fe_add",
+            "fe_invert",
+            "fe_is_odd",
+            "fe_is_zero",
+            "fe_multiply",
+            "fe_multiply_beta",
+            "fe_negate",
+            "fe_normalize",
+            "fe_square",
+            "fe_square_root",
+            "ge_is_on_curve",
+            "ge_negate",
+            "gej_add",
+            "gej_double",
+            "gej_equiv",
+            "gej_ge_add",
+            "gej_ge_add_ex",
+            "gej_ge_equiv",
+            "gej_infinity",
+            "gej_is_infinity",
+            "gej_is_on_curve",
+            "gej_negate",
+            "gej_normalize",
+            "gej_rescale",
+            "gej_x_equiv",
+            "gej_y_is_odd",
+            "generate",
+            "linear_combination_1",
+            "linear_verify_1",
+            "point_verify_1",
+            "scalar_add",
+            "scalar_invert",
+            "scalar_is_zero",
+            "scalar_multiply",
+            "scalar_multiply_lambda",
+            "scalar_negate",
+            "scalar_normalize",
+            "scalar_square",
+            "scale",
+        ],
+    ),
+    (
+        "digital_signatures",
+        &["bip_0340_verify", "check_sig_verify"],
+    ),
+    ("bitcoin", &["parse_lock", "parse_sequence"]),
+    (
+        "signature_hash_modes",
+        &[
+            "annex_hash",
+            "asset_amount_hash",
+            "build_tapbranch",
+            "build_tapleaf_simplicity",
+            "build_taptweak",
+            "input_amounts_hash",
+            "input_annexes_hash",
+            "input_hash",
+            "input_outpoints_hash",
+            "input_script_sigs_hash",
+            "input_scripts_hash",
+            "input_sequences_hash",
+            "input_utxo_hash",
+            "input_utxos_hash",
+            "inputs_hash",
+            "issuance_asset_amounts_hash",
+            "issuance_blinding_entropy_hash",
+            "issuance_hash",
+            "issuance_range_proofs_hash",
+            "issuance_token_amounts_hash",
+            "issuances_hash",
+            "nonce_hash",
+            "outpoint_hash",
+            "output_amounts_hash",
+            "output_hash",
+            "output_nonces_hash",
+            "output_range_proofs_hash",
+            "output_scripts_hash",
+            "output_surjection_proofs_hash",
+            "outputs_hash",
+            "sig_all_hash",
+            "tap_env_hash",
+            "tapleaf_hash",
+            "tappath_hash",
+            "tx_hash",
+        ],
+    ),
+    (
+        "time_locks",
+        &[
+            "check_lock_distance",
+            "check_lock_duration",
+            "check_lock_height",
+            "check_lock_time",
+            "tx_is_final",
+            "tx_lock_distance",
+            "tx_lock_duration",
+            "tx_lock_height",
+            "tx_lock_time",
+        ],
+    ),
+    (
+        "issuance",
+        &[
+            "calculate_asset",
+            "calculate_confidential_token",
+            "calculate_explicit_token",
+            "calculate_issuance_entropy",
+            "issuance",
+            "issuance_asset",
+            "issuance_entropy",
+            "issuance_token",
+            "lbtc_asset",
+        ],
+    ),
+    (
+        "transaction",
+        &[
+            "current_amount",
+            "current_annex_hash",
+            "current_asset",
+            "current_index",
+            "current_issuance_asset_amount",
+            "current_issuance_asset_proof",
+            "current_issuance_token_amount",
+            "current_issuance_token_proof",
+            "current_new_issuance_contract",
+            "current_pegin",
+            "current_prev_outpoint",
+            "current_reissuance_blinding",
+            "current_reissuance_entropy",
+            "current_script_hash",
+            "current_script_sig_hash",
+            "current_sequence",
+            "input_amount",
+            "input_annex_hash",
+            "input_asset",
+            "input_pegin",
+            "input_prev_outpoint",
+            "input_script_hash",
+            "input_script_sig_hash",
+            "input_sequence",
+            "internal_key",
+            "issuance_asset_amount",
+            "issuance_asset_proof",
+            "issuance_token_amount",
+            "issuance_token_proof",
+            "lock_time",
+            "new_issuance_contract",
+            "num_inputs",
+            "num_outputs",
+            "output_amount",
+            "output_asset",
+            "output_is_fee",
+            "output_nonce",
+            "output_null_datum",
+            "output_range_proof",
+            "output_script_hash",
+            "output_surjection_proof",
+            "reissuance_blinding",
+            "reissuance_entropy",
+            "script_cmr",
+            "tapleaf_version",
+            "tappath",
+            "total_fee",
+            "transaction_id",
+            "version",
+        ],
+    ),
+];
+
+/// Return the link to the entry of a jet in the documentation,
+/// or `None` if the documentation has no entry for the jet.
+pub fn documentation_url(jet: Elements) -> Option<String> {
+    let name = jet.to_string();
+    DOCUMENTED_JETS
+        .iter()
+        .find(|(_, names)| names.contains(&name.as_str()))
+        .map(|(module, _)| format!("{DOCUMENTATION_URL}/{module}/fn.{name}.html"))
+}
+
+/// Maximum number of jets that [`completions`] returns.
+pub const MAX_COMPLETIONS: usize = 10;
+
+/// Return the jets whose names start with `prefix`, in alphabetical order.
+///
+/// At most [`MAX_COMPLETIONS`] jets are returned.
+/// A prefix that already is the full name of the only matching jet has no completions.
+pub fn completions(prefix: &str) -> Vec<Elements> {
+    let mut jets = Elements::ALL
+        .into_iter()
+        .map(|jet| (jet.to_string(), jet))
+        .filter(|(name, _)| name.starts_with(prefix))
+        .collect::<Vec<_>>();
+    if matches!(jets.as_slice(), [(name, _)] if name == prefix) {
+        return vec![];
+    }
+    jets.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    jets.into_iter()
+        .take(MAX_COMPLETIONS)
+        .map(|(_, jet)| jet)
+        .collect()
+}
+
+/// Format the input of a jet as its arguments in `SimplicityHL` syntax,
+/// such as `1, 2` for a call of `add_8`.
+///
+/// An input that does not have the source type of the jet is shown as a Simplicity value.
+pub fn format_arguments(jet: Elements, input: &Value) -> String {
+    let source = ResolvedType::tuple(simplicityhl::jet::source_type(jet).iter().map(|ty| {
+        ty.resolve_builtin()
+            .expect("jet types use only builtin aliases")
+    }));
+    simplicityhl::Value::reconstruct(&StructuralValue::from(input.clone()), &source)
+        .and_then(|value| match value.inner() {
+            ValueInner::Tuple(arguments) => Some(
+                arguments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            _ => None,
+        })
+        .unwrap_or_else(|| input.to_string())
+}
+
+/// Format the output of a jet as a `SimplicityHL` value, such as `(false, 3)` for `add_8`.
+///
+/// An output that does not have the target type of the jet is shown as a Simplicity value.
+pub fn format_result(jet: Elements, output: &Value) -> String {
+    let target = simplicityhl::jet::target_type(jet)
+        .resolve_builtin()
+        .expect("jet types use only builtin aliases");
+    simplicityhl::Value::reconstruct(&StructuralValue::from(output.clone()), &target)
+        .map_or_else(|| output.to_string(), |value| value.to_string())
+}
+
+/// Check whether the C implementation of the jets works on this platform.
+///
+/// If the check fails, then every program that calls a jet will fail to run.
+pub fn c_jets_work() -> bool {
+    simplicity::ffi::c_jets::sanity_checks()
+}
+
+/// Check whether a jet reads the transaction environment.
+///
+/// Core jets and the Elements jets that only compute hashes or assets from their input
+/// are pure: their output depends on nothing but their input.
+pub fn reads_environment(jet: Elements) -> bool {
+    let is_core = Core::from_str(&jet.to_string()).is_ok();
+    let is_pure_helper = matches!(
+        jet,
+        Elements::OutpointHash
+            | Elements::AssetAmountHash
+            | Elements::NonceHash
+            | Elements::AnnexHash
+            | Elements::BuildTapleafSimplicity
+            | Elements::BuildTapbranch
+            | Elements::BuildTaptweak
+            | Elements::CalculateIssuanceEntropy
+            | Elements::CalculateAsset
+            | Elements::CalculateExplicitToken
+            | Elements::CalculateConfidentialToken
+    );
+    !is_core && !is_pure_helper
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn jet_signature() {
+        assert_eq!(
+            "fn add_32(u32, u32) -> (bool, u32)",
+            signature(Elements::Add32)
+        );
+        assert_eq!("fn verify(bool) -> ()", signature(Elements::Verify));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn jet_documentation_url() {
+        assert_eq!(
+            Some(
+                "https://docs.rs/simplicityhl-as-rust/latest/simplicityhl_as_rust/jet/arithmetic/fn.add_32.html"
+                    .to_string()
+            ),
+            documentation_url(Elements::Add32)
+        );
+        assert_eq!(
+            Some(
+                "https://docs.rs/simplicityhl-as-rust/latest/simplicityhl_as_rust/jet/signature_hash_modes/fn.sig_all_hash.html"
+                    .to_string()
+            ),
+            documentation_url(Elements::SigAllHash)
+        );
+        assert_eq!(None, documentation_url(Elements::Swu));
+
+        let mut names = DOCUMENTED_JETS
+            .iter()
+            .flat_map(|(_, names)| names.iter())
+            .collect::<Vec<_>>();
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(count, names.len(), "every jet has at most one entry");
+        for name in names {
+            assert!(Elements::from_str(name).is_ok(), "{name} is not a jet");
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn jet_completions() {
+        assert_eq!(
+            vec![
+                Elements::Add16,
+                Elements::Add32,
+                Elements::Add64,
+                Elements::Add8
+            ],
+            completions("add_")
+        );
+        assert_eq!(MAX_COMPLETIONS, completions("").len());
+        assert!(completions("add_32").is_empty());
+        assert!(completions("no_such_jet").is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn jet_wrong_input() {
+        let env = crate::transaction::TxParams::default().tx_env(simplicity::Cmr::unit());
+        assert_eq!(
+            Err(JetError::WrongInput),
+            execute_jet_with_env(&Elements::Add32, &Value::unit(), &env)
+        );
+        assert_eq!(
+            Err(JetError::Failed),
+            execute_jet_with_env(&Elements::Verify, &Value::u1(0), &env)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn environment_jets() {
+        assert!(!reads_environment(Elements::Add32));
+        assert!(!reads_environment(Elements::Sha256Ctx8Init));
+        assert!(!reads_environment(Elements::CalculateAsset));
+        assert!(reads_environment(Elements::SigAllHash));
+        assert!(reads_environment(Elements::CurrentIndex));
+        assert!(reads_environment(Elements::CheckLockHeight));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn format_jet_call() {
+        let input = Value::product(Value::u8(1), Value::u8(2));
+        let output = Value::product(Value::u1(0), Value::u8(3));
+        assert_eq!("1, 2", format_arguments(Elements::Add8, &input));
+        assert_eq!("(false, 3)", format_result(Elements::Add8, &output));
+        assert_eq!("", format_arguments(Elements::CurrentIndex, &Value::unit()));
+        assert_eq!("0", format_result(Elements::CurrentIndex, &Value::u32(0)));
+        assert_eq!(
+            "(((0,0),(0,0)),((0,1),(1,1)))",
+            format_result(Elements::CurrentIndex, &Value::u8(7))
+        );
+    }
+
     #[wasm_bindgen_test::wasm_bindgen_test]
     #[allow(dead_code)]
     fn wasm_sanity_checks() {
@@ -0,0 +1,18 @@
+//! Headless core of the IDE: compiling, satisfying and running `SimplicityHL` programs.
+//!
+//! None of these modules depend on the user interface,
+//! so they can be reused outside the browser.
+//! Without the default `ui` feature, the crate builds only this library
+//! and leaves out Leptos and the other browser dependencies.
+
+pub mod examples;
+pub mod function;
+pub mod jet;
+pub mod network;
+pub mod pipeline;
+pub mod transaction;
+pub mod util;
+pub mod worker;
+
+#[cfg(test)]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
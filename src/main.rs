@@ -1,14 +1,11 @@
 mod components;
-mod examples;
-mod function;
-mod jet;
-mod transaction;
+mod download;
 mod url_sharing;
-mod util;
 
 use components::App;
 use leptos::{mount_to_body, view};
 use leptos_router::Router;
+use simplicity_webide::{examples, function, jet, network, pipeline, transaction, util, worker};
 
 #[cfg(test)]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use elements::hashes::{sha256, Hash};
+use simplicityhl::elements;
+
+use crate::util;
+
+/// Elements network whose chain parameters are used in the transaction environment.
+///
+/// The network determines the genesis block hash and the asset id of bitcoin,
+/// which are both visible to introspection jets.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Network {
+    LiquidMainnet,
+    #[default]
+    LiquidTestnet,
+    ElementsRegtest,
+}
+
+impl Network {
+    /// All available networks, in the order in which they are displayed.
+    pub const ALL: [Self; 3] = [
+        Self::LiquidMainnet,
+        Self::LiquidTestnet,
+        Self::ElementsRegtest,
+    ];
+
+    /// Human-readable name of the network.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::LiquidMainnet => "Liquid mainnet",
+            Self::LiquidTestnet => "Liquid testnet",
+            Self::ElementsRegtest => "Elements regtest",
+        }
+    }
+
+    /// Find the network with the given human-readable name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|network| network.name() == name)
+    }
+
+    /// Hash of the genesis block.
+    ///
+    /// Regtest chains are created locally and pick their own genesis block,
+    /// so we use the all-zeroes hash as a placeholder.
+    pub fn genesis_hash(self) -> elements::BlockHash {
+        match self {
+            Self::LiquidMainnet => elements::BlockHash::from_byte_array([
+                0x03, 0x60, 0x20, 0x8a, 0x88, 0x96, 0x92, 0x37, 0x2c, 0x8d, 0x68, 0xb0, 0x84, 0xa6,
+                0x2e, 0xfd, 0xf6, 0x0e, 0xa1, 0xa3, 0x59, 0xa0, 0x4c, 0x94, 0xb2, 0x0d, 0x22, 0x36,
+                0x58, 0x27, 0x66, 0x14,
+            ]),
+            Self::LiquidTestnet => util::liquid_testnet_genesis(),
+            Self::ElementsRegtest => elements::BlockHash::all_zeros(),
+        }
+    }
+
+    /// Asset id of (pegged) bitcoin.
+    pub fn bitcoin_asset(self) -> elements::AssetId {
+        match self {
+            Self::LiquidMainnet => elements::AssetId::from_inner(sha256::Midstate([
+                0x6d, 0x52, 0x1c, 0x38, 0xec, 0x1e, 0xa1, 0x57, 0x34, 0xae, 0x22, 0xb7, 0xc4, 0x60,
+                0x64, 0x41, 0x28, 0x29, 0xc0, 0xd0, 0x57, 0x9f, 0x0a, 0x71, 0x3d, 0x1c, 0x04, 0xed,
+                0xe9, 0x79, 0x02, 0x6f,
+            ])),
+            Self::LiquidTestnet => util::liquid_testnet_bitcoin_asset(),
+            Self::ElementsRegtest => elements::AssetId::from_inner(sha256::Midstate([
+                0x25, 0xb2, 0x51, 0x07, 0x0e, 0x29, 0xca, 0x19, 0x04, 0x3c, 0xf3, 0x3c, 0xcd, 0x73,
+                0x24, 0xe2, 0xdd, 0xab, 0x03, 0xec, 0xc4, 0xae, 0x0b, 0x5e, 0x77, 0xc4, 0xfc, 0x0e,
+                0x5c, 0xf6, 0xc9, 0x5a,
+            ])),
+        }
+    }
+
+    /// Parameters for encoding addresses.
+    pub fn address_params(self) -> &'static elements::AddressParams {
+        match self {
+            Self::LiquidMainnet => &elements::AddressParams::LIQUID,
+            Self::LiquidTestnet => &elements::AddressParams::LIQUID_TESTNET,
+            Self::ElementsRegtest => &elements::AddressParams::ELEMENTS,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn name_roundtrip() {
+        for network in Network::ALL {
+            assert_eq!(Some(network), Network::from_name(network.name()));
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn chain_parameters() {
+        assert_eq!(
+            "1466275836220db2944ca059a3a10ef6fd2ea684b0688d2c379296888a206003",
+            Network::LiquidMainnet.genesis_hash().to_string()
+        );
+        assert_eq!(
+            "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d",
+            Network::LiquidMainnet.bitcoin_asset().to_string()
+        );
+        assert_eq!(
+            "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49",
+            Network::LiquidTestnet.bitcoin_asset().to_string()
+        );
+        assert_eq!(
+            "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225",
+            Network::ElementsRegtest.bitcoin_asset().to_string()
+        );
+    }
+}
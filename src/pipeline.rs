@@ -0,0 +1,463 @@
+//! Compile, satisfy and run programs without the user interface.
+//!
+//! The IDE runs programs through these functions,
+//! and so can command-line tools and tests that do not run in a browser.
+
+use std::fmt;
+use std::sync::Arc;
+
+use simplicityhl::error::{RichError, Span};
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::simplicity::jet::elements::ElementsEnv;
+use simplicityhl::{elements, CompiledProgram, SatisfiedProgram, TemplateProgram, WitnessValues};
+
+use crate::function::{ErrorKind, Runner};
+use crate::transaction::TxParams;
+use crate::util::source::{self, ModuleKind};
+use crate::util::witness;
+
+/// Reason why a program failed, from parsing it up to running it.
+///
+/// The error displays as the message that the IDE shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgramError {
+    /// The program has not been compiled yet.
+    NotCompiled,
+    /// The program does not parse.
+    Parse(Box<RichError>),
+    /// The program of the file with the given name does not parse.
+    File { name: String, error: Box<RichError> },
+    /// The program parses, but it does not compile, such as because it is ill-typed.
+    Compile(String),
+    /// The values of the witness or param module are invalid.
+    Witness { message: String, span: Span },
+    /// The witness values do not satisfy the program.
+    Satisfy(String),
+    /// The program exceeds the resources of the Bit Machine, so it cannot run.
+    Resources(String),
+    /// The transaction environment of the run is invalid.
+    Environment(String),
+    /// The program failed while running, with an optional explanation of the failure.
+    Runtime {
+        error: Box<ErrorKind>,
+        explanation: Option<String>,
+    },
+}
+
+impl ProgramError {
+    /// Return the name of the stage at which the program failed,
+    /// which makes up the CSS class of the error.
+    pub const fn category(&self) -> &'static str {
+        match self {
+            Self::NotCompiled => "not-compiled",
+            Self::Parse(..) | Self::File { .. } => "parse",
+            Self::Compile(..) => "compile",
+            Self::Witness { .. } => "witness",
+            Self::Satisfy(..) => "satisfy",
+            Self::Resources(..) => "resources",
+            Self::Environment(..) => "environment",
+            Self::Runtime { .. } => "runtime",
+        }
+    }
+
+    /// Return a short name of the kind of error, for the user.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::NotCompiled => "Not compiled",
+            Self::Parse(..) | Self::File { .. } => "Parse error",
+            Self::Compile(..) => "Compile error",
+            Self::Witness { .. } => "Witness error",
+            Self::Satisfy(..) => "Satisfaction error",
+            Self::Resources(..) => "Resource limit",
+            Self::Environment(..) => "Environment error",
+            Self::Runtime { .. } => "Runtime error",
+        }
+    }
+
+    /// Return the location of the error in the program text, if the error has one.
+    ///
+    /// The location of an error in a file refers to the text of that file.
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Self::Parse(error) | Self::File { error, .. } => Some(error.span()),
+            Self::Witness { span, .. } => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Return the description of the error without the code that it refers to.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Parse(error) | Self::File { error, .. } => error.error().to_string(),
+            Self::Witness { message, .. } => message.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotCompiled => f.write_str("The program has not been compiled yet"),
+            Self::Parse(error) => write!(f, "{error}"),
+            Self::File { name, error } => {
+                write!(f, "The file `{name}` is not a valid program:\n{error}")
+            }
+            Self::Compile(message)
+            | Self::Witness { message, .. }
+            | Self::Satisfy(message)
+            | Self::Resources(message)
+            | Self::Environment(message) => f.write_str(message),
+            Self::Runtime {
+                error,
+                explanation: Some(explanation),
+            } => write!(f, "{error}\n\n{explanation}"),
+            Self::Runtime {
+                error,
+                explanation: None,
+            } => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+impl From<ErrorKind> for ProgramError {
+    fn from(error: ErrorKind) -> Self {
+        Self::Runtime {
+            error: Box::new(error),
+            explanation: None,
+        }
+    }
+}
+
+/// Compile the program `text`.
+///
+/// A missing param module is treated as empty,
+/// so the module can be commented out temporarily.
+pub fn compile_text(
+    text: &str,
+    include_debug_symbols: bool,
+) -> Result<CompiledProgram, ProgramError> {
+    let template = TemplateProgram::new(text).map_err(|message| {
+        // The compiler reports errors as text, so only a failed program is parsed again
+        // to tell parse errors with their location apart from other errors
+        match simplicityhl::parse::Program::parse_from_str(text) {
+            Err(error) => ProgramError::Parse(Box::new(error)),
+            Ok(..) => ProgramError::Compile(message),
+        }
+    })?;
+    let args = match source::module_span(text, ModuleKind::Param) {
+        Some(..) => simplicityhl::Arguments::parse_from_str(text)
+            .map_err(|error| value_error(text, ModuleKind::Param, &error))?,
+        None => simplicityhl::Arguments::default(),
+    };
+    template
+        .instantiate(args, include_debug_symbols)
+        .map_err(ProgramError::Compile)
+}
+
+fn value_error(text: &str, kind: ModuleKind, error: &RichError) -> ProgramError {
+    ProgramError::Witness {
+        message: witness::explain_value_error(text, kind, error),
+        span: *error.span(),
+    }
+}
+
+/// Satisfy the compiled program with the witness values of the program `text`.
+///
+/// A missing witness module is treated as empty.
+pub fn satisfy_text(
+    text: &str,
+    compiled: &CompiledProgram,
+) -> Result<SatisfiedProgram, ProgramError> {
+    let witness = match source::module_span(text, ModuleKind::Witness) {
+        Some(..) => WitnessValues::parse_from_str(text)
+            .map_err(|error| value_error(text, ModuleKind::Witness, &error))?,
+        None => WitnessValues::default(),
+    };
+    compiled
+        .satisfy(witness)
+        .map_err(|error| ProgramError::Satisfy(witness::explain_satisfaction_error(error)))
+}
+
+/// Outcome of running a program to completion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunOutcome {
+    /// Whether the run succeeded, or why it failed.
+    pub result: Result<(), ProgramError>,
+    /// Output of the `dbg!` expressions.
+    pub debug_output: Vec<String>,
+    /// Number of executed steps.
+    pub steps: usize,
+}
+
+impl fmt::Display for RunOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self
+            .result
+            .as_ref()
+            .map_err(|error| error as &dyn fmt::Display);
+        write_outcome(f, result, self.steps, &self.debug_output)
+    }
+}
+
+/// Write the outcome of a run, whether it is a [`RunOutcome`] or a report of the run.
+pub(crate) fn write_outcome(
+    f: &mut fmt::Formatter<'_>,
+    result: Result<&(), &dyn fmt::Display>,
+    steps: usize,
+    debug_output: &[String],
+) -> fmt::Result {
+    match result {
+        Ok(()) => writeln!(f, "Success after {steps} steps")?,
+        Err(error) => writeln!(f, "Failure after {steps} steps: {error}")?,
+    }
+    for line in debug_output {
+        writeln!(f, "{line}")?;
+    }
+    Ok(())
+}
+
+impl RunOutcome {
+    /// Return the outcome of the run of `runner` that ended with `result`.
+    pub fn of_run(runner: &Runner, result: Result<(), ErrorKind>) -> Self {
+        Self {
+            result: result.map_err(ProgramError::from),
+            debug_output: runner.debug_output().to_vec(),
+            steps: runner.executed_steps(),
+        }
+    }
+
+    /// Return the outcome of a program that failed before it ran.
+    pub fn failed(error: ProgramError) -> Self {
+        Self {
+            result: Err(error),
+            debug_output: vec![],
+            steps: 0,
+        }
+    }
+}
+
+/// Run the satisfied program in the transaction environment `env`.
+///
+/// The run is aborted after `step_limit` steps, if there is a limit.
+/// Return an error if the program cannot be loaded into the bit machine.
+pub fn run_satisfied(
+    satisfied: &SatisfiedProgram,
+    env: &ElementsEnv<Arc<elements::Transaction>>,
+    step_limit: Option<usize>,
+) -> Result<RunOutcome, ProgramError> {
+    let runner = load_satisfied(satisfied)?;
+    Ok(run_to_end(runner, env, step_limit))
+}
+
+/// Load the satisfied program into a runner, before its first step.
+///
+/// Return an error if the program exceeds the resources of the bit machine.
+pub fn load_satisfied(satisfied: &SatisfiedProgram) -> Result<Runner, ProgramError> {
+    Runner::for_program(satisfied).map_err(ProgramError::Resources)
+}
+
+fn run_to_end(
+    mut runner: Runner,
+    env: &ElementsEnv<Arc<elements::Transaction>>,
+    step_limit: Option<usize>,
+) -> RunOutcome {
+    let result = match step_limit {
+        Some(max_steps) => runner.run_with_limit(env, max_steps),
+        None => runner.run(env),
+    };
+    RunOutcome::of_run(&runner, result)
+}
+
+/// Compile the program `text`, satisfy it with its witness module and run it
+/// in the transaction environment `env`.
+///
+/// The run fails after `step_limit` steps, if there is a limit.
+/// Return an error if the program does not compile or cannot be satisfied;
+/// the outcome holds the error if the run itself fails.
+pub fn run_program(
+    text: &str,
+    env: &ElementsEnv<Arc<elements::Transaction>>,
+    step_limit: Option<usize>,
+) -> Result<RunOutcome, ProgramError> {
+    let compiled = compile_text(text, false)?;
+    let satisfied = satisfy_text(text, &compiled)?;
+    run_satisfied(&satisfied, env, step_limit)
+}
+
+/// Compile the program `text` and satisfy it with its witness module,
+/// so it can run in the transaction environment `params`.
+///
+/// The program includes debug symbols if `include_debug_symbols` is set.
+/// Return the runner before its first step together with the environment of the run,
+/// for callers that run the program in portions.
+pub fn load_text(
+    text: &str,
+    params: &TxParams,
+    include_debug_symbols: bool,
+) -> Result<(Runner, ElementsEnv<Arc<elements::Transaction>>), ProgramError> {
+    let compiled = compile_text(text, include_debug_symbols)?;
+    let env = params.tx_env(compiled.commit().cmr());
+    let satisfied = satisfy_text(text, &compiled)?;
+    Ok((load_satisfied(&satisfied)?, env))
+}
+
+/// Compile the program `text`, satisfy it with its witness module and run it
+/// in the transaction environment `params`.
+///
+/// The run fails after `step_limit` steps, if there is a limit.
+/// Errors before the run are reported as the result of the outcome.
+pub fn run_text(text: &str, params: &TxParams, step_limit: Option<usize>) -> RunOutcome {
+    run_text_with_symbols(text, params, step_limit, false)
+}
+
+/// Like [`run_text`], but the program includes debug symbols if `include_debug_symbols` is set,
+/// which changes its CMR and the errors of failed calls.
+pub fn run_text_with_symbols(
+    text: &str,
+    params: &TxParams,
+    step_limit: Option<usize>,
+    include_debug_symbols: bool,
+) -> RunOutcome {
+    match load_text(text, params, include_debug_symbols) {
+        Ok((runner, env)) => run_to_end(runner, &env, step_limit),
+        Err(error) => RunOutcome::failed(error),
+    }
+}
+
+/// Run the program `text` twice in the transaction environment `params`
+/// and return the outcome if both runs agree.
+///
+/// Runs are deterministic, so differing outcomes point to a bug
+/// in the runner or in the construction of the environment.
+pub fn check_determinism(
+    text: &str,
+    params: &TxParams,
+    step_limit: Option<usize>,
+) -> Result<RunOutcome, Box<(RunOutcome, RunOutcome)>> {
+    let first = run_text(text, params, step_limit);
+    let second = run_text(text, params, step_limit);
+    same_outcome(first, second)
+}
+
+/// Return the outcome of two runs of the same program if both runs agree,
+/// or else both outcomes.
+pub fn same_outcome<T: PartialEq>(first: T, second: T) -> Result<T, Box<(T, T)>> {
+    match first == second {
+        true => Ok(first),
+        false => Err(Box::new((first, second))),
+    }
+}
+
+/// Run the program `text` once for each input of the transaction environment `params`,
+/// with that input as the spent input.
+///
+/// Return the outcomes in the order of the inputs.
+pub fn run_each_input(text: &str, params: &TxParams, step_limit: Option<usize>) -> Vec<RunOutcome> {
+    each_input(params)
+        .iter()
+        .map(|params| run_text(text, params, step_limit))
+        .collect()
+}
+
+/// Return the transaction environment `params` once for each of its inputs,
+/// with that input as the spent input.
+pub fn each_input(params: &TxParams) -> Vec<TxParams> {
+    (0..params.inputs())
+        .map(|input_index| TxParams {
+            input_index,
+            ..params.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn run_program_headless() {
+        let text = "mod witness { const X: u32 = 7; } fn main() { let x: u32 = dbg!(witness::X); assert!(jet::eq_32(x, 7)); }";
+        let compiled = compile_text(text, false).unwrap();
+        let env = TxParams::default().tx_env(compiled.commit().cmr());
+        let outcome = run_program(text, &env, None).unwrap();
+        assert_eq!(outcome.result, Ok(()));
+        assert_eq!(outcome, run_text(text, &TxParams::default(), None));
+        assert!(0 < outcome.steps);
+
+        let failing = text.replace("= 7;", "= 8;");
+        let outcome = run_program(&failing, &env, None).unwrap();
+        assert!(matches!(outcome.result, Err(ProgramError::Runtime { .. })));
+
+        let outcome = run_program(text, &env, Some(2)).unwrap();
+        assert_eq!(
+            outcome.result,
+            Err(ProgramError::from(ErrorKind::ExecutionAborted { steps: 2 }))
+        );
+        assert_eq!(outcome.steps, 2);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn categorize_errors() {
+        let category = |text: &str| {
+            let compiled = compile_text(text, false)?;
+            satisfy_text(text, &compiled).map(|_| ())
+        };
+        let error = category("fn main() {").unwrap_err();
+        assert!(matches!(error, ProgramError::Parse(..)));
+        assert!(error.span().is_some());
+        assert_eq!(
+            error.to_string(),
+            CompiledProgram::new("fn main() {", simplicityhl::Arguments::default(), false)
+                .err()
+                .unwrap()
+        );
+        let error = category("fn main() { let x: u8 = 256; }").unwrap_err();
+        assert_eq!(error.category(), "compile");
+        assert!(error.span().is_none());
+        let error =
+            category("mod witness { const X: u8 = true; } fn main() { let x: u8 = witness::X; }")
+                .unwrap_err();
+        assert!(matches!(error, ProgramError::Witness { .. }));
+        assert!(error.span().is_some());
+        let error = category("fn main() { assert!(witness::X); }").unwrap_err();
+        assert!(matches!(error, ProgramError::Satisfy(..)));
+        assert_eq!(
+            ProgramError::from(ErrorKind::JetFailed).to_string(),
+            "Jet failed"
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn run_on_each_network() {
+        let text = "fn main() { let hash: u256 = dbg!(jet::genesis_block_hash()); }";
+        let compiled = compile_text(text, true).unwrap();
+        let satisfied = satisfy_text(text, &compiled).unwrap();
+        let run = |params: &TxParams| {
+            let env = params.tx_env(compiled.commit().cmr());
+            run_satisfied(&satisfied, &env, None).unwrap()
+        };
+        let outcomes: Vec<RunOutcome> = Network::ALL
+            .into_iter()
+            .map(|network| {
+                run(&TxParams {
+                    network,
+                    ..TxParams::default()
+                })
+            })
+            .collect();
+        for (network, outcome) in Network::ALL.into_iter().zip(&outcomes) {
+            assert_eq!(outcome.result, Ok(()), "{}", network.name());
+        }
+        assert_ne!(outcomes[0].debug_output, outcomes[1].debug_output);
+        assert_ne!(outcomes[1].debug_output, outcomes[2].debug_output);
+        assert_eq!(TxParams::default().network, Network::LiquidTestnet);
+        assert_eq!(outcomes[1], run(&TxParams::default()));
+    }
+}
@@ -1,12 +1,16 @@
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use elements::confidential;
 use elements::hashes::Hash;
+use hex_conservative::{DisplayHex, FromHex};
 use simplicity::jet::elements::{ElementsEnv, ElementsUtxo};
 use simplicityhl::simplicity::jet::Elements;
 use simplicityhl::simplicity::RedeemNode;
 use simplicityhl::{elements, simplicity};
 
+use crate::network::Network;
 use crate::util;
 
 #[derive(Clone, Debug)]
@@ -18,6 +22,59 @@ pub struct TxParams {
     pub fee: u64,
     pub lock_time: elements::LockTime,
     pub sequence: elements::Sequence,
+    pub network: Network,
+    /// Number of inputs of the transaction.
+    ///
+    /// All inputs spend UTXOs of the program with the same value.
+    pub input_count: u32,
+    /// Index of the input that is spent by the program,
+    /// which is the input whose signature hash `jet::sig_all_hash` computes.
+    pub input_index: u32,
+    /// Transaction that replaces the transaction of the other fields, if there is one.
+    ///
+    /// The UTXO fields still describe the spent outputs,
+    /// which a transaction does not contain.
+    pub raw_transaction: Option<Arc<elements::Transaction>>,
+    /// Outputs of the transaction after the payment to the recipient and before the fee.
+    pub extra_outputs: Vec<TxOutput>,
+}
+
+/// Additional output of the transaction, which pays the recipient.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TxOutput {
+    pub value: u64,
+    /// Explicit asset of the output, or the bitcoin asset of the network if there is none.
+    pub asset: Option<elements::AssetId>,
+}
+
+impl fmt::Display for TxOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.asset {
+            Some(asset) => write!(f, "{}@{asset}", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl FromStr for TxOutput {
+    type Err = String;
+
+    /// Parse an output of the form `value` or `value@asset`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, asset) = match s.split_once('@') {
+            Some((value, asset)) => (value, Some(asset)),
+            None => (s, None),
+        };
+        let value = value
+            .trim()
+            .parse::<u64>()
+            .map_err(|error| format!("Invalid value: {error}"))?;
+        let asset = asset
+            .map(|asset| elements::AssetId::from_str(asset.trim()))
+            .transpose()
+            .map_err(|error| format!("Invalid asset id: {error}"))?;
+        Ok(Self { value, asset })
+    }
 }
 
 impl Default for TxParams {
@@ -30,70 +87,280 @@ impl Default for TxParams {
             fee: 1_000,
             lock_time: elements::LockTime::from_consensus(0),
             sequence: elements::Sequence::from_consensus(0),
+            network: Network::default(),
+            input_count: 1,
+            input_index: 0,
+            raw_transaction: None,
+            extra_outputs: vec![],
         }
     }
 }
 
+/// Deserialize a transaction from its consensus encoding in hex.
+///
+/// The transaction must have an input that the program can spend.
+pub fn parse_transaction_hex(hex: &str) -> Result<elements::Transaction, String> {
+    let bytes = Vec::<u8>::from_hex(hex.trim()).map_err(|error| format!("Invalid hex: {error}"))?;
+    let tx: elements::Transaction = elements::encode::deserialize(&bytes)
+        .map_err(|error| format!("The hex is not a valid transaction: {error}"))?;
+    match tx.input.is_empty() {
+        true => Err("The transaction has no inputs".to_string()),
+        false => Ok(tx),
+    }
+}
+
+/// Parse a comma-separated list of outputs, as written by [`outputs_string`].
+pub fn parse_outputs(s: &str) -> Result<Vec<TxOutput>, String> {
+    s.split(',')
+        .filter(|output| !output.trim().is_empty())
+        .map(TxOutput::from_str)
+        .collect()
+}
+
+/// Write the outputs as a comma-separated list.
+pub fn outputs_string(outputs: &[TxOutput]) -> String {
+    outputs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Serialize a transaction into its consensus encoding in hex.
+pub fn transaction_hex(tx: &elements::Transaction) -> String {
+    elements::encode::serialize(tx).to_lower_hex_string()
+}
+
 impl TxParams {
+    /// Read the transaction parameters from the strings of [`TxParams::to_strings`].
+    ///
+    /// Parameters that later versions added may be missing and take their default.
+    pub fn from_strings(mut values: impl Iterator<Item = String>) -> Option<Self> {
+        let txid = values.next().and_then(|s| s.parse().ok())?;
+        let vout = values.next().and_then(|s| s.parse().ok())?;
+        let value_in = values.next().and_then(|s| s.parse().ok())?;
+        let recipient_address = values.next().and_then(|s| s.parse().ok());
+        let fee = values.next().and_then(|s| s.parse().ok())?;
+        let lock_time = values.next().and_then(|s| s.parse().ok())?;
+        let sequence = values.next().and_then(|s| s.parse().ok())?;
+        let network = values
+            .next()
+            .and_then(|s| Network::from_name(&s))
+            .unwrap_or_default();
+        let input_count = values.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+        let input_index = values.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let raw_transaction = values
+            .next()
+            .and_then(|s| parse_transaction_hex(&s).ok())
+            .map(Arc::new);
+        let extra_outputs = values
+            .next()
+            .and_then(|s| parse_outputs(&s).ok())
+            .unwrap_or_default();
+
+        Some(Self {
+            txid,
+            vout,
+            value_in,
+            recipient_address,
+            fee,
+            lock_time,
+            sequence,
+            network,
+            input_count,
+            input_index,
+            raw_transaction,
+            extra_outputs,
+        })
+    }
+
+    /// Check that `value` is a valid value of the parameter at `index` of [`TxParams::to_strings`].
+    ///
+    /// Unlike [`TxParams::from_strings`], which replaces some invalid values by their default,
+    /// this check rejects every invalid value.
+    /// Empty values are valid for the optional parameters, which they leave unset.
+    pub fn check_string(index: usize, value: &str) -> Result<(), String> {
+        fn parse<T: FromStr>(value: &str) -> Result<(), String>
+        where
+            T::Err: fmt::Display,
+        {
+            value
+                .parse::<T>()
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        }
+        match index {
+            0 => parse::<elements::Txid>(value),
+            1 | 9 => parse::<u32>(value),
+            2 | 4 => parse::<u64>(value),
+            3 if value.is_empty() => Ok(()),
+            3 => parse::<elements::Address>(value),
+            5 => parse::<elements::LockTime>(value),
+            6 => parse::<elements::Sequence>(value),
+            7 => Network::from_name(value)
+                .map(|_| ())
+                .ok_or_else(|| format!("Unknown network `{value}`")),
+            8 => match value.parse::<u32>() {
+                Ok(0) => Err("There must be at least one input".to_string()),
+                Ok(..) => Ok(()),
+                Err(error) => Err(error.to_string()),
+            },
+            10 if value.trim().is_empty() => Ok(()),
+            10 => parse_transaction_hex(value).map(|_| ()),
+            11 => parse_outputs(value).map(|_| ()),
+            _ => Err(format!("There is no parameter at position {index}")),
+        }
+    }
+
+    /// Write each transaction parameter as a string, in a fixed order,
+    /// so the parameters can be stored or sent.
+    pub fn to_strings(&self) -> [String; 12] {
+        [
+            self.txid.to_string(),
+            self.vout.to_string(),
+            self.value_in.to_string(),
+            self.recipient_address
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            self.fee.to_string(),
+            self.lock_time.to_string(),
+            self.sequence.to_string(),
+            self.network.to_string(),
+            self.input_count.to_string(),
+            self.input_index.to_string(),
+            self.raw_transaction
+                .as_deref()
+                .map(transaction_hex)
+                .unwrap_or_default(),
+            outputs_string(&self.extra_outputs),
+        ]
+    }
+
+    /// Return the number of inputs of the transaction, which is at least one.
+    pub fn inputs(&self) -> u32 {
+        match &self.raw_transaction {
+            // safety: transactions with more than 2^32 inputs are unrealistic
+            // pasted transactions have at least one input, see `parse_transaction_hex`
+            Some(tx) => tx.input.len() as u32,
+            None => self.input_count.max(1),
+        }
+    }
+
+    /// Return the index of the spent input.
+    ///
+    /// Return an error if the transaction has no input at that index.
+    pub fn spent_input_index(&self) -> Result<u32, String> {
+        let input_count = self.inputs();
+        match self.input_index < input_count {
+            true => Ok(self.input_index),
+            false => Err(format!(
+                "Input {} does not exist, because the transaction has {input_count} inputs. \
+                Runs spend input {} until the index is valid.",
+                self.input_index,
+                self.env_input_index()
+            )),
+        }
+    }
+
+    /// Return the index of the input that transaction environments spend.
+    ///
+    /// Environments spend the last input while the index is out of bounds,
+    /// so the other parameters can still be edited and tried out.
+    fn env_input_index(&self) -> u32 {
+        self.input_index.min(self.inputs().saturating_sub(1))
+    }
+
+    fn unsatisfied_input(&self, index: u32) -> elements::TxIn {
+        // The other inputs spend further outputs of the same transaction
+        let vout = match index == self.env_input_index() {
+            true => self.vout,
+            false => self.vout.wrapping_add(1 + index),
+        };
+        elements::TxIn {
+            previous_output: elements::OutPoint {
+                txid: self.txid,
+                vout,
+            },
+            is_pegin: false,
+            script_sig: elements::Script::new(),
+            sequence: self.sequence,
+            asset_issuance: elements::AssetIssuance::null(),
+            witness: elements::TxInWitness::empty(), // not required here
+        }
+    }
+
     fn unsatisfied_transaction(&self) -> elements::Transaction {
+        if let Some(tx) = &self.raw_transaction {
+            return tx.as_ref().clone();
+        }
+        let script_pubkey = self.recipient_address.as_ref().map_or_else(
+            util::liquid_testnet_faucet_script_pubkey,
+            elements::Address::script_pubkey,
+        );
+        let payment = |value: u64, asset: elements::AssetId| elements::TxOut {
+            asset: confidential::Asset::Explicit(asset),
+            value: confidential::Value::Explicit(value),
+            nonce: confidential::Nonce::Null,
+            script_pubkey: script_pubkey.clone(),
+            witness: elements::TxOutWitness::empty(),
+        };
+        let bitcoin = self.network.bitcoin_asset();
+        let mut output = vec![payment(self.value_in.saturating_sub(self.fee), bitcoin)];
+        output.extend(
+            self.extra_outputs
+                .iter()
+                .map(|extra| payment(extra.value, extra.asset.unwrap_or(bitcoin))),
+        );
+        output.push(elements::TxOut::new_fee(self.fee, bitcoin));
         elements::Transaction {
             version: 2,
             lock_time: self.lock_time,
-            input: vec![elements::TxIn {
-                previous_output: elements::OutPoint {
-                    txid: self.txid,
-                    vout: self.vout,
-                },
-                is_pegin: false,
-                script_sig: elements::Script::new(),
-                sequence: self.sequence,
-                asset_issuance: elements::AssetIssuance::null(),
-                witness: elements::TxInWitness::empty(), // not required here
-            }],
-            output: vec![
-                elements::TxOut {
-                    asset: confidential::Asset::Explicit(util::liquid_testnet_bitcoin_asset()),
-                    value: confidential::Value::Explicit(self.value_in.saturating_sub(self.fee)),
-                    nonce: confidential::Nonce::Null,
-                    script_pubkey: self.recipient_address.as_ref().map_or_else(
-                        util::liquid_testnet_faucet_script_pubkey,
-                        elements::Address::script_pubkey,
-                    ),
-                    witness: elements::TxOutWitness::empty(),
-                },
-                elements::TxOut::new_fee(self.fee, util::liquid_testnet_bitcoin_asset()),
-            ],
+            input: (0..self.input_count.max(1))
+                .map(|index| self.unsatisfied_input(index))
+                .collect(),
+            output,
         }
     }
 
     fn utxo(&self, script_pubkey: elements::Script) -> ElementsUtxo {
         ElementsUtxo {
             script_pubkey,
-            asset: confidential::Asset::Explicit(util::liquid_testnet_bitcoin_asset()),
+            asset: confidential::Asset::Explicit(self.network.bitcoin_asset()),
             value: confidential::Value::Explicit(self.value_in),
         }
     }
 
     pub fn tx_env(&self, cmr: simplicity::Cmr) -> ElementsEnv<Arc<elements::Transaction>> {
         let script_pubkey = util::liquid_testnet_address(cmr).script_pubkey();
-        let index = 0;
+        let index = self.env_input_index();
         let annex = None;
+        let utxos = (0..self.inputs())
+            .map(|_| self.utxo(script_pubkey.clone()))
+            .collect();
         ElementsEnv::new(
             Arc::new(self.unsatisfied_transaction()),
-            vec![self.utxo(script_pubkey)],
+            utxos,
             index,
             cmr,
             util::control_block(cmr),
             annex,
-            util::liquid_testnet_genesis(),
+            self.network.genesis_hash(),
         )
     }
 
-    pub fn transaction(&self, pruned: &RedeemNode<Elements>) -> elements::Transaction {
+    /// Return the transaction that spends the input with the pruned program.
+    ///
+    /// Return an error if the index of the spent input is out of bounds.
+    pub fn transaction(
+        &self,
+        pruned: &RedeemNode<Elements>,
+    ) -> Result<elements::Transaction, String> {
+        let index = self.spent_input_index()?;
         let mut tx = self.unsatisfied_transaction();
         let (simplicity_program_bytes, simplicity_witness_bytes) = pruned.to_vec_with_witness();
         let cmr = pruned.cmr();
-        tx.input[0].witness = elements::TxInWitness {
+        tx.input[index as usize].witness = elements::TxInWitness {
             amount_rangeproof: None,
             inflation_keys_rangeproof: None,
             script_witness: vec![
@@ -104,6 +371,136 @@ impl TxParams {
             ],
             pegin_witness: vec![],
         };
-        tx
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn outputs_round_trip() {
+        let asset = Network::ElementsRegtest.bitcoin_asset();
+        let outputs = vec![
+            TxOutput {
+                value: 5_000,
+                asset: None,
+            },
+            TxOutput {
+                value: 7,
+                asset: Some(asset),
+            },
+        ];
+        let s = outputs_string(&outputs);
+        assert_eq!(s, format!("5000,7@{asset}"));
+        assert_eq!(parse_outputs(&s).unwrap(), outputs);
+        assert_eq!(
+            parse_outputs(&format!(" 5000 , 7 @ {asset} ")).unwrap(),
+            outputs
+        );
+        assert_eq!(parse_outputs("").unwrap(), vec![]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn invalid_outputs() {
+        let error = parse_outputs("1000@not-an-asset").unwrap_err();
+        assert!(error.starts_with("Invalid asset id"), "{error}");
+        let error = parse_outputs("1000,-1").unwrap_err();
+        assert!(error.starts_with("Invalid value"), "{error}");
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn fee_output_comes_last() {
+        let asset = Network::ElementsRegtest.bitcoin_asset();
+        let params = TxParams {
+            extra_outputs: vec![
+                TxOutput {
+                    value: 10,
+                    asset: None,
+                },
+                TxOutput {
+                    value: 20,
+                    asset: Some(asset),
+                },
+            ],
+            ..TxParams::default()
+        };
+        let env = params.tx_env(simplicity::Cmr::unit());
+        let outputs = &env.tx().output;
+        assert_eq!(outputs.len(), 4);
+        let bitcoin = params.network.bitcoin_asset();
+        assert_eq!(
+            outputs[0].value,
+            confidential::Value::Explicit(params.value_in - params.fee)
+        );
+        assert_eq!(outputs[1].value, confidential::Value::Explicit(10));
+        assert_eq!(outputs[1].asset, confidential::Asset::Explicit(bitcoin));
+        assert_eq!(outputs[2].value, confidential::Value::Explicit(20));
+        assert_eq!(outputs[2].asset, confidential::Asset::Explicit(asset));
+        assert!(outputs[3].is_fee());
+        assert_eq!(outputs[3].value, confidential::Value::Explicit(params.fee));
+        assert!(outputs[..3].iter().all(|output| !output.is_fee()));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn transaction_hex_round_trip() {
+        let params = TxParams {
+            input_count: 2,
+            ..TxParams::default()
+        };
+        let tx = params.unsatisfied_transaction();
+        let hex = transaction_hex(&tx);
+        assert_eq!(parse_transaction_hex(&hex).unwrap(), tx);
+        assert_eq!(parse_transaction_hex(&format!("  {hex}\n")).unwrap(), tx);
+
+        let params = TxParams {
+            raw_transaction: Some(Arc::new(tx.clone())),
+            ..TxParams::default()
+        };
+        assert_eq!(params.inputs(), 2);
+        assert_eq!(params.tx_env(simplicity::Cmr::unit()).tx(), &tx);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn malformed_transaction_hex() {
+        for hex in ["zz", "abc", "ü"] {
+            let error = parse_transaction_hex(hex).unwrap_err();
+            assert!(error.starts_with("Invalid hex"), "{hex}: {error}");
+        }
+        let hex = transaction_hex(&TxParams::default().unsatisfied_transaction());
+        for hex in ["", "00", &hex[..hex.len() - 2]] {
+            let error = parse_transaction_hex(hex).unwrap_err();
+            assert!(
+                error.starts_with("The hex is not a valid transaction"),
+                "{hex}: {error}"
+            );
+        }
+
+        let mut tx = TxParams::default().unsatisfied_transaction();
+        tx.input.clear();
+        let error = parse_transaction_hex(&transaction_hex(&tx)).unwrap_err();
+        assert_eq!(error, "The transaction has no inputs");
+
+        let tx = TxParams {
+            input_count: 2,
+            ..TxParams::default()
+        }
+        .unsatisfied_transaction();
+        let params = TxParams {
+            input_index: 2,
+            raw_transaction: Some(Arc::new(
+                parse_transaction_hex(&transaction_hex(&tx)).unwrap(),
+            )),
+            ..TxParams::default()
+        };
+        let error = params.spent_input_index().unwrap_err();
+        assert!(error.starts_with("Input 2 does not exist"), "{error}");
+        assert_eq!(params.tx_env(simplicity::Cmr::unit()).ix(), 1);
     }
 }
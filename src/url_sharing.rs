@@ -1,12 +1,45 @@
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
-use leptos::wasm_bindgen::JsValue;
+use leptos::wasm_bindgen::{JsCast, JsValue};
 use miniz_oxide::deflate::compress_to_vec;
 use miniz_oxide::inflate::decompress_to_vec_with_limit;
 use web_sys::window;
 
+use crate::components::LocalStorage;
+use crate::transaction::TxParams;
+use crate::util::source::{self, ModuleKind};
+
 const URL_PREFIX: &str = "#code=";
+const TAB_SEPARATOR: &str = "&tab=";
+/// Tabs and views that share links can open, besides the execution tab.
+///
+/// The names are those of the tabs of the run window and of the views of the program window.
+const SHARED_TABS: [&str; 7] = [
+    "Witness",
+    "Parameters",
+    "Transaction",
+    "Key Store",
+    "Hash Store",
+    "Values",
+    "Analyze",
+];
+const REPRO_PREFIX: &str = "#repro=";
+const REPRO_HEADER: &str = "simplicity-webide reproduction";
+const EMBED_PARAMETER: &str = "embed";
+const READ_ONLY_PARAMETER: &str = "readonly";
+const HELP_PARAMETER: &str = "help";
 const MAX_DECOMPRESSED_SIZE: usize = 65_536;
+/// Length of the longest share link that is offered for copying.
+///
+/// Some browsers, chat clients and link shorteners truncate or reject longer links.
+pub const MAX_SHARE_URL_LENGTH: usize = 8_000;
+/// Origins of the pages that may load programs into an embedded IDE,
+/// in addition to the origin of the IDE itself.
+const EMBED_ORIGINS: [&str; 3] = [
+    "https://ide.simplicity-lang.org",
+    "https://docs.simplicity-lang.org",
+    "https://simplicity-lang.org",
+];
 
 fn encode_program(text: &str) -> Option<String> {
     if text.is_empty() {
@@ -22,26 +55,286 @@ fn decode_program(encoded: &str) -> Option<String> {
     String::from_utf8(decompressed).ok()
 }
 
-pub fn build_share_url(text: &str) -> Option<String> {
+/// Convert the name of a tab into the form that share links use, like `key-store`.
+fn tab_slug(tab: &str) -> String {
+    tab.to_lowercase().replace(' ', "-")
+}
+
+/// Return the hash of a share link for the program `text` that opens the given `tab`.
+///
+/// Tabs that share links cannot open are left out.
+fn share_hash(text: &str, tab: Option<&str>) -> Option<String> {
     let encoded = encode_program(text)?;
+    let tab = tab
+        .filter(|tab| SHARED_TABS.contains(tab))
+        .map(|tab| format!("{TAB_SEPARATOR}{}", tab_slug(tab)))
+        .unwrap_or_default();
+    Some(format!("{URL_PREFIX}{encoded}{tab}"))
+}
+
+/// Split the hash of a share link into the encoded program and the shared tab.
+fn split_share_hash(hash: &str) -> Option<(&str, Option<&'static str>)> {
+    let rest = hash.strip_prefix(URL_PREFIX)?;
+    let Some((encoded, slug)) = rest.split_once(TAB_SEPARATOR) else {
+        return Some((rest, None));
+    };
+    let tab = SHARED_TABS.into_iter().find(|tab| tab_slug(tab) == slug);
+    Some((encoded, tab))
+}
+
+/// Build a link to the program `text` that opens the given `tab` of the IDE.
+pub fn build_share_url(text: &str, tab: Option<&str>) -> Option<String> {
+    let hash = share_hash(text, tab)?;
     let window = window()?;
     let location = window.location();
     let origin = location.origin().ok()?;
     let pathname = location.pathname().ok()?;
-    Some(format!("{origin}{pathname}{URL_PREFIX}{encoded}"))
+    Some(format!("{origin}{pathname}{hash}"))
 }
 
 pub fn read_shared_program() -> Option<Result<String, ()>> {
     let hash = window()?.location().hash().ok()?;
-    let encoded = hash.strip_prefix(URL_PREFIX)?;
+    let (encoded, _) = split_share_hash(&hash)?;
     Some(decode_program(encoded).ok_or(()))
 }
 
-pub fn set_url_hash(text: &str) {
-    let Some(encoded) = encode_program(text) else {
+/// Return the hash of the share link `hash` with its program replaced by `text`.
+///
+/// The shared tab stays the same.
+/// If the linked program has no witness module, then neither has the new one,
+/// so editing a program that was shared without its witness does not put the witness in the link.
+/// Return `None` if `hash` is not a share link or if `text` is empty.
+fn updated_share_hash(hash: &str, text: &str) -> Option<String> {
+    let (encoded, tab) = split_share_hash(hash)?;
+    let without_witness = decode_program(encoded)
+        .is_some_and(|linked| source::module_span(&linked, ModuleKind::Witness).is_none());
+    match without_witness {
+        true => share_hash(&source::remove_module(text, ModuleKind::Witness), tab),
+        false => share_hash(text, tab),
+    }
+}
+
+/// Replace the program of the share link in the address bar with `text`,
+/// so that reloading the page keeps the edits.
+///
+/// Do nothing if the address bar holds no share link.
+pub fn update_shared_program(text: &str) {
+    let Some(window) = window() else {
+        return;
+    };
+    let Some(hash) = window
+        .location()
+        .hash()
+        .ok()
+        .and_then(|hash| updated_share_hash(&hash, text))
+    else {
+        return;
+    };
+    let _ = window.history().ok().and_then(|h| {
+        h.replace_state_with_url(&JsValue::NULL, "", Some(&hash))
+            .ok()
+    });
+}
+
+/// Read the tab that the share link opens, if it names one.
+pub fn read_shared_tab() -> Option<&'static str> {
+    let hash = window()?.location().hash().ok()?;
+    split_share_hash(&hash)?.1
+}
+
+/// Bundle the program and the transaction environment into a single text.
+///
+/// The text starts with a header and `key=value` lines of the environment,
+/// followed by an empty line and the program.
+fn encode_repro(text: &str, params: &TxParams) -> String {
+    let mut bundle = format!("{REPRO_HEADER}\nversion={}\n", env!("CARGO_PKG_VERSION"));
+    for (key, value) in TxParams::keys().zip(params.to_values()) {
+        bundle.push_str(&format!("{key}={value}\n"));
+    }
+    bundle.push('\n');
+    bundle.push_str(text);
+    bundle
+}
+
+fn decode_repro(bundle: &str) -> Option<(String, TxParams)> {
+    let rest = bundle.strip_prefix(REPRO_HEADER)?.strip_prefix('\n')?;
+    let (header, text) = rest.split_once("\n\n")?;
+    let values: Vec<(&str, &str)> = header
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .collect();
+    let value_of = |key: &str| {
+        values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| (*value).to_string())
+    };
+    let params = TxParams::from_values(TxParams::keys().filter_map(value_of))?;
+    Some((text.to_string(), params))
+}
+
+/// Build a link that reproduces the program together with its transaction environment.
+pub fn build_repro_url(text: &str, params: &TxParams) -> Option<String> {
+    let encoded = encode_program(&encode_repro(text, params))?;
+    let window = window()?;
+    let location = window.location();
+    let origin = location.origin().ok()?;
+    let pathname = location.pathname().ok()?;
+    Some(format!("{origin}{pathname}{REPRO_PREFIX}{encoded}"))
+}
+
+/// Read the program and the transaction environment from a reproduction link.
+pub fn read_repro() -> Option<Result<(String, TxParams), ()>> {
+    let hash = window()?.location().hash().ok()?;
+    let encoded = hash.strip_prefix(REPRO_PREFIX)?;
+    Some(
+        decode_program(encoded)
+            .and_then(|bundle| decode_repro(&bundle))
+            .ok_or(()),
+    )
+}
+
+/// Return the value of the parameter `name` in the query string `search`.
+fn query_value<'a>(search: &'a str, name: &str) -> Option<&'a str> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+/// Check if the query string `search` sets the flag `name`,
+/// as in `?name=1` or `?name=true`.
+fn query_flag(search: &str, name: &str) -> bool {
+    matches!(query_value(search, name), Some("1" | "true"))
+}
+
+/// Check if the query string `search` clears the flag `name`,
+/// as in `?name=0` or `?name=false`.
+fn query_flag_cleared(search: &str, name: &str) -> bool {
+    matches!(query_value(search, name), Some("0" | "false"))
+}
+
+/// Check if the IDE is embedded in another page via `?embed=1`.
+///
+/// Embedded IDEs render a minimal layout without navigation.
+pub fn is_embedded() -> bool {
+    window()
+        .and_then(|w| w.location().search().ok())
+        .is_some_and(|search| query_flag(&search, EMBED_PARAMETER))
+}
+
+/// Check if the help button is hidden via `?help=0`.
+///
+/// Embedding pages often have their own documentation.
+pub fn is_help_hidden() -> bool {
+    window()
+        .and_then(|w| w.location().search().ok())
+        .is_some_and(|search| query_flag_cleared(&search, HELP_PARAMETER))
+}
+
+/// Check if the editor starts locked via `?readonly=1`.
+///
+/// Together with `?embed=1`, this showcases programs without accidental edits.
+pub fn is_read_only() -> bool {
+    window()
+        .and_then(|w| w.location().search().ok())
+        .is_some_and(|search| query_flag(&search, READ_ONLY_PARAMETER))
+}
+
+/// Check if a page of the given `origin` may load programs into the embedded IDE,
+/// which is served from `own_origin`.
+fn is_allowed_origin(origin: &str, own_origin: &str) -> bool {
+    origin == own_origin || EMBED_ORIGINS.contains(&origin)
+}
+
+/// Program that the parent page of an embedded IDE sent via `postMessage`.
+///
+/// The message is an object `{ program, witness, env }`.
+/// `program` is the program text and is required.
+/// `witness` is the source of a witness module that replaces the one of the program.
+/// `env` is an object with the fields of the transaction environment,
+/// using the same keys as the browser storage, such as `fee` or `lock_time`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EmbedMessage {
+    pub program: String,
+    pub witness: Option<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl EmbedMessage {
+    /// Read the message that was sent by the parent page.
+    ///
+    /// Return `None` if the sender is not allowed or if the message is malformed.
+    pub fn from_event(event: &web_sys::MessageEvent) -> Option<Self> {
+        let own_origin = window()?.location().origin().ok()?;
+        if !is_allowed_origin(&event.origin(), &own_origin) {
+            return None;
+        }
+        let data = event.data();
+        let field = |name: &str| js_sys::Reflect::get(&data, &JsValue::from_str(name)).ok();
+        let program = field("program")?.as_string()?;
+        let witness = field("witness").and_then(|witness| witness.as_string());
+        let env = field("env")
+            .and_then(|env| env.dyn_into::<js_sys::Object>().ok())
+            .map(|env| {
+                js_sys::Object::entries(&env)
+                    .iter()
+                    .filter_map(|entry| {
+                        let entry = entry.dyn_into::<js_sys::Array>().ok()?;
+                        let key = entry.get(0).as_string()?;
+                        let value = entry.get(1);
+                        let value = value
+                            .as_string()
+                            .or_else(|| value.as_f64().map(|number| number.to_string()))?;
+                        Some((key, value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            program,
+            witness,
+            env,
+        })
+    }
+
+    /// Return the program text with the witness module of the message.
+    pub fn program_text(&self) -> String {
+        match &self.witness {
+            Some(witness) => source::replace_module(&self.program, ModuleKind::Witness, witness),
+            None => self.program.clone(),
+        }
+    }
+
+    /// Return the transaction environment `params` with the fields of the message.
+    ///
+    /// Return an error if a field has an invalid value.
+    /// Fields with unknown keys are ignored.
+    pub fn tx_params(&self, params: &TxParams) -> Result<TxParams, String> {
+        for (key, value) in &self.env {
+            if let Some(index) = TxParams::keys().position(|k| k == key) {
+                TxParams::check_string(index, value)
+                    .map_err(|error| format!("Invalid `{key}`: {error}"))?;
+            }
+        }
+        let values = TxParams::keys()
+            .zip(params.to_values())
+            .map(|(key, value)| {
+                self.env
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map_or(value, |(_, value)| value.clone())
+            });
+        TxParams::from_values(values)
+            .ok_or_else(|| "The transaction environment is invalid".to_string())
+    }
+}
+
+pub fn set_url_hash(text: &str, tab: Option<&str>) {
+    let Some(hash) = share_hash(text, tab) else {
         return;
     };
-    let hash = format!("{URL_PREFIX}{encoded}");
     let _ = window().and_then(|w| w.history().ok()).and_then(|h| {
         h.replace_state_with_url(&JsValue::NULL, "", Some(&hash))
             .ok()
@@ -61,6 +354,44 @@ mod tests {
         assert_eq!(text, decoded);
     }
 
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn roundtrip_shared_tab() {
+        let text = "fn main() {}";
+        for tab in SHARED_TABS {
+            let hash = share_hash(text, Some(tab)).unwrap();
+            let (encoded, shared_tab) = split_share_hash(&hash).unwrap();
+            assert_eq!(text, decode_program(encoded).unwrap());
+            assert_eq!(Some(tab), shared_tab);
+        }
+        let hash = share_hash(text, Some("Execution")).unwrap();
+        assert!(!hash.contains(TAB_SEPARATOR));
+        assert_eq!(None, split_share_hash(&hash).unwrap().1);
+        let (encoded, tab) = split_share_hash("#code=abc&tab=unknown").unwrap();
+        assert_eq!(("abc", None), (encoded, tab));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn update_share_hash() {
+        let witness = "mod witness {\n    const A: u8 = 1;\n}\n\n";
+        let text = format!("{witness}fn main() {{}}");
+        let hash = share_hash(&text, Some("Witness")).unwrap();
+        let edited = format!("{witness}fn main() {{ assert!(true); }}");
+        let updated = updated_share_hash(&hash, &edited).unwrap();
+        let (encoded, tab) = split_share_hash(&updated).unwrap();
+        assert_eq!(edited, decode_program(encoded).unwrap());
+        assert_eq!(Some("Witness"), tab);
+
+        let hash = share_hash("fn main() {}", None).unwrap();
+        let updated = updated_share_hash(&hash, &edited).unwrap();
+        let (encoded, _) = split_share_hash(&updated).unwrap();
+        assert!(!decode_program(encoded).unwrap().contains("mod witness"));
+
+        assert_eq!(None, updated_share_hash("#repro=abc", &edited));
+        assert!(updated_share_hash("#code=!!!", &edited).is_some());
+    }
+
     #[test]
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn roundtrip_empty() {
@@ -112,4 +443,99 @@ mod tests {
             assert_eq!(text, decoded);
         }
     }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn embed_query_flag() {
+        assert!(query_flag("?embed=1", EMBED_PARAMETER));
+        assert!(query_flag("?theme=dark&embed=true", EMBED_PARAMETER));
+        assert!(!query_flag("?embed=0", EMBED_PARAMETER));
+        assert!(!query_flag("?embedded=1", EMBED_PARAMETER));
+        assert!(!query_flag("", EMBED_PARAMETER));
+        assert!(query_flag("?embed=1&readonly=1", READ_ONLY_PARAMETER));
+        assert!(query_flag_cleared("?embed=1&help=0", HELP_PARAMETER));
+        assert!(query_flag_cleared("?help=false", HELP_PARAMETER));
+        assert!(!query_flag_cleared("?help=1", HELP_PARAMETER));
+        assert!(!query_flag_cleared("?embed=1", HELP_PARAMETER));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn embed_message() {
+        assert!(is_allowed_origin(
+            "https://docs.simplicity-lang.org",
+            "http://localhost"
+        ));
+        assert!(is_allowed_origin("http://localhost", "http://localhost"));
+        assert!(!is_allowed_origin(
+            "https://example.com",
+            "http://localhost"
+        ));
+
+        let message = EmbedMessage {
+            program: "fn main() {}".to_string(),
+            witness: Some("mod witness { const A: u8 = 1; }".to_string()),
+            env: vec![("fee".to_string(), "500".to_string())],
+        };
+        assert_eq!(
+            "mod witness { const A: u8 = 1; }\n\nfn main() {}",
+            message.program_text()
+        );
+        let params = message.tx_params(&TxParams::default()).unwrap();
+        assert_eq!(500, params.fee);
+        assert_eq!(TxParams::default().lock_time, params.lock_time);
+
+        let invalid = EmbedMessage {
+            env: vec![("fee".to_string(), "lots".to_string())],
+            ..message.clone()
+        };
+        let error = invalid.tx_params(&TxParams::default()).unwrap_err();
+        assert!(error.starts_with("Invalid `fee`"), "{error}");
+
+        for (key, value) in [
+            ("raw_transaction", "not hex"),
+            ("raw_transaction", "0200"),
+            ("network", "moon"),
+            ("recipient", "not an address"),
+            ("input_count", "0"),
+            ("extra_outputs", "1@no-asset"),
+        ] {
+            let invalid = EmbedMessage {
+                env: vec![(key.to_string(), value.to_string())],
+                ..message.clone()
+            };
+            let error = invalid.tx_params(&TxParams::default()).unwrap_err();
+            assert!(error.starts_with(&format!("Invalid `{key}`")), "{error}");
+        }
+        let cleared = EmbedMessage {
+            env: vec![
+                ("raw_transaction".to_string(), String::new()),
+                ("recipient".to_string(), String::new()),
+                ("unknown".to_string(), "ignored".to_string()),
+            ],
+            ..message
+        };
+        let params = cleared.tx_params(&TxParams::default()).unwrap();
+        assert!(params.raw_transaction.is_none());
+        assert!(params.recipient_address.is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn roundtrip_repro() {
+        let text = "mod witness {}\n\nfn main() {\n\n    assert!(true);\n}";
+        let params = TxParams {
+            fee: 1234,
+            ..TxParams::default()
+        };
+        let bundle = encode_repro(text, &params);
+        assert!(bundle.contains("\nfee=1234\n"));
+        let (decoded_text, decoded_params) = decode_repro(&bundle).unwrap();
+        assert_eq!(text, decoded_text);
+        assert_eq!(
+            params.to_values().collect::<Vec<_>>(),
+            decoded_params.to_values().collect::<Vec<_>>(),
+        );
+        assert!(decode_repro(text).is_none());
+    }
 }
@@ -0,0 +1,107 @@
+//! Inferred types of the variables that `let` statements bind.
+
+use simplicityhl::ast::Program;
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::pattern::Pattern;
+use simplicityhl::types::TypeInner;
+use simplicityhl::ResolvedType;
+
+use crate::util::walk::{walk, Visit};
+
+/// Variable that is bound by a `let` statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Binding {
+    /// Name of the variable.
+    pub name: String,
+    /// Inferred type of the variable.
+    pub ty: String,
+    /// Line of the `let` statement.
+    ///
+    /// Starts at 1.
+    pub line: usize,
+}
+
+/// Return the variables that the `let` statements of the program `text` bind,
+/// ordered by line.
+pub fn let_bindings(text: &str) -> Result<Vec<Binding>, String> {
+    let parsed = simplicityhl::parse::Program::parse_from_str(text).map_err(|e| e.to_string())?;
+    let program = Program::analyze(&parsed).map_err(|e| e.to_string())?;
+    let mut bindings = Vec::new();
+    walk(program.main(), &mut |visit| {
+        if let Visit::Assignment(assignment) = visit {
+            let line = assignment.span().start.line.get();
+            let ty = assignment.expression().ty();
+            bind_pattern(assignment.pattern(), ty, line, &mut bindings);
+        }
+    });
+    // Custom functions are inlined into each of their calls
+    bindings.sort_by_key(|binding| binding.line);
+    bindings.dedup();
+    Ok(bindings)
+}
+
+/// Return the binding of the variable `name` that is visible on the given line.
+pub fn binding_at<'a>(bindings: &'a [Binding], name: &str, line: usize) -> Option<&'a Binding> {
+    bindings
+        .iter()
+        .rev()
+        .find(|binding| binding.name == name && binding.line <= line)
+}
+
+fn bind_pattern(pattern: &Pattern, ty: &ResolvedType, line: usize, bindings: &mut Vec<Binding>) {
+    match (pattern, ty.as_inner()) {
+        (Pattern::Identifier(identifier), _) => bindings.push(Binding {
+            name: identifier.to_string(),
+            ty: ty.to_string(),
+            line,
+        }),
+        (Pattern::Ignore, _) => {}
+        (Pattern::Tuple(patterns), TypeInner::Tuple(types)) => {
+            for (pattern, ty) in patterns.iter().zip(types.iter()) {
+                bind_pattern(pattern, ty, line, bindings);
+            }
+        }
+        (Pattern::Array(patterns), TypeInner::Array(ty, _)) => {
+            for pattern in patterns.iter() {
+                bind_pattern(pattern, ty, line, bindings);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn infer_binding_types() {
+        let text = r#"fn double(x: u8) -> u8 {
+    let (carry, y): (bool, u8) = jet::add_8(x, x);
+    y
+}
+
+fn main() {
+    let a: u8 = double(2);
+    let [b, _]: [u8; 2] = [a, a];
+    assert!(jet::eq_8(b, 4));
+}"#;
+        let bindings = let_bindings(text).unwrap();
+        let describe: Vec<_> = bindings
+            .iter()
+            .map(|b| (b.name.as_str(), b.ty.as_str(), b.line))
+            .collect();
+        assert_eq!(
+            describe,
+            [
+                ("carry", "bool", 2),
+                ("y", "u8", 2),
+                ("a", "u8", 7),
+                ("b", "u8", 8)
+            ]
+        );
+        assert_eq!(binding_at(&bindings, "a", 9).unwrap().ty, "u8");
+        assert!(binding_at(&bindings, "b", 7).is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! Code frames that show the source of an error, like the output of rustc.
+
+use std::ops::Range;
+
+/// Number of lines that are shown before and after the lines of an error.
+const CONTEXT_LINES: usize = 2;
+
+/// Source lines around an error, with the location of the error underlined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeFrame {
+    /// Text of the error before its location.
+    pub preamble: String,
+    /// Lines of the program around the error.
+    pub lines: Vec<FrameLine>,
+    /// Message of the error.
+    pub message: String,
+}
+
+/// Line of a code frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameLine {
+    /// Number of the line.
+    ///
+    /// Starts at 1.
+    pub number: usize,
+    /// Text of the line.
+    pub content: String,
+    /// Columns of the error on this line, as a range of character indices.
+    pub underline: Option<Range<usize>>,
+}
+
+/// Location of an error, as found in its string.
+struct Location {
+    preamble: String,
+    /// Range of 1-based line numbers.
+    lines: Range<usize>,
+    /// Character indices of the underline on the first line.
+    underline: Range<usize>,
+    message: String,
+}
+
+/// Find the location of an error in its string.
+///
+/// Errors of `SimplicityHL` show the lines of the error,
+/// followed by carets that underline the error and the message of the error.
+fn locate(error: &str) -> Option<Location> {
+    let lines: Vec<&str> = error.lines().collect();
+    let numbered = |line: &str| -> Option<usize> {
+        let (number, _) = line.split_once(" | ")?;
+        number.trim().parse().ok()
+    };
+    let first = lines.iter().position(|line| numbered(line).is_some())?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| numbered(line).is_some())
+        .count();
+    let first_number = numbered(lines[first])?;
+
+    let caret_line = lines.get(first + count)?;
+    let (_, after_bar) = caret_line.split_once('|')?;
+    let before_carets = after_bar.len() - after_bar.trim_start().len();
+    let carets = after_bar
+        .trim_start()
+        .chars()
+        .take_while(|c| *c == '^')
+        .count();
+    if carets == 0 {
+        return None;
+    }
+    // The source of each line is separated from the bar by one space
+    let start = before_carets.saturating_sub(1);
+    let mut message = after_bar.trim_start()[carets..].trim_start().to_string();
+    for line in &lines[first + count + 1..] {
+        message.push('\n');
+        message.push_str(line);
+    }
+    // The frame starts with an empty line that only contains the bar
+    let preamble_end = match first.checked_sub(1) {
+        Some(bar) if lines[bar].trim() == "|" => bar,
+        _ => first,
+    };
+
+    Some(Location {
+        preamble: lines[..preamble_end].join("\n"),
+        lines: first_number..first_number + count,
+        underline: start..start + carets,
+        message,
+    })
+}
+
+/// Return the code frame of the `error` of the program `text`,
+/// or `None` if the error has no location.
+pub fn code_frame(text: &str, error: &str) -> Option<CodeFrame> {
+    frame(text, locate(error)?)
+}
+
+/// Return the code frame of the program `text` that underlines the byte range `span`,
+/// such as the call that made a run fail.
+///
+/// Return `None` if `span` is not inside `text`.
+pub fn span_frame(
+    text: &str,
+    span: Range<usize>,
+    preamble: String,
+    message: String,
+) -> Option<CodeFrame> {
+    let before = text.get(..span.start)?;
+    let spanned = text.get(span)?;
+    let first = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    let start = before[line_start..].chars().count();
+    let end = match spanned.contains('\n') {
+        true => start,
+        false => start + spanned.chars().count(),
+    };
+    let location = Location {
+        preamble,
+        lines: first..first + spanned.matches('\n').count() + 1,
+        underline: start..end,
+        message,
+    };
+    frame(text, location)
+}
+
+fn frame(text: &str, location: Location) -> Option<CodeFrame> {
+    let first = location.lines.start.saturating_sub(CONTEXT_LINES).max(1);
+    let last = location.lines.end - 1 + CONTEXT_LINES;
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, content)| (index + 1, content))
+        .filter(|(number, _)| first <= *number && *number <= last)
+        .map(|(number, content)| {
+            let underline = match location.lines.len() {
+                1 => (number == location.lines.start).then(|| location.underline.clone()),
+                // Errors that span multiple lines underline all of them
+                _ => location
+                    .lines
+                    .contains(&number)
+                    .then(|| 0..content.chars().count()),
+            };
+            FrameLine {
+                number,
+                content: content.to_string(),
+                underline,
+            }
+        })
+        .collect::<Vec<_>>();
+    if lines.iter().all(|line| line.underline.is_none()) {
+        return None;
+    }
+
+    Some(CodeFrame {
+        preamble: location.preamble,
+        lines,
+        message: location.message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simplicityhl::{Arguments, CompiledProgram};
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn frame_compile_error() {
+        let text = "fn main() {\n    let a: u8 = 1;\n    let x: u8 = foo;\n    assert!(jet::eq_8(a, x));\n}";
+        let error = CompiledProgram::new(text, Arguments::default(), false).unwrap_err();
+        let frame = code_frame(text, &error).expect("error should have a location");
+
+        assert_eq!(frame.message, "Variable `foo` is not defined");
+        let numbers: Vec<_> = frame.lines.iter().map(|line| line.number).collect();
+        assert_eq!(numbers, [1, 2, 3, 4, 5]);
+        let underlined = &frame.lines[2];
+        let underline = underlined.underline.clone().expect("line 3 is underlined");
+        assert_eq!(&underlined.content[underline], "foo");
+
+        assert!(code_frame(text, "Jet failed").is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn frame_span() {
+        let text = "fn main() {\n    assert!(jet::eq_8(1, 2));\n}";
+        let start = text.find("jet::").unwrap();
+        let span = start..start + "jet::eq_8(1, 2)".len();
+        let frame = span_frame(text, span, "Assertion failed".into(), "Line 2".into())
+            .expect("span is inside the text");
+
+        assert_eq!(frame.preamble, "Assertion failed");
+        let underlined = &frame.lines[1];
+        let underline = underlined.underline.clone().expect("line 2 is underlined");
+        assert_eq!(&underlined.content[underline], "jet::eq_8(1, 2)");
+        assert!(span_frame(text, 0..text.len() + 1, String::new(), String::new()).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+//! Structural comparison of two committed Simplicity programs.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use simplicity::jet::Elements;
+use simplicity::node::Inner;
+use simplicity::{Cmr, CommitNode};
+use simplicityhl::simplicity;
+
+/// Maximum number of differences that are reported.
+///
+/// Unrelated programs differ everywhere, and a long list of differences does not help.
+pub const MAX_DIFFERENCES: usize = 50;
+
+/// Pair of nodes at the same position in both programs that differ in their kind.
+///
+/// Nodes of the same kind whose children differ are not reported themselves;
+/// the comparison continues with their children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference {
+    /// Kinds of the nodes on the way from the root to the differing nodes,
+    /// with the index of the child that was taken.
+    pub path: String,
+    /// Node of the left program.
+    pub left: String,
+    /// Node of the right program.
+    pub right: String,
+}
+
+/// Return the differences between the committed programs `left` and `right`,
+/// in pre-order.
+///
+/// The list is empty if and only if both programs have the same CMR.
+pub fn differences(
+    left: &Arc<CommitNode<Elements>>,
+    right: &Arc<CommitNode<Elements>>,
+) -> Vec<Difference> {
+    let mut comparison = Comparison {
+        visited: HashSet::new(),
+        differences: Vec::new(),
+    };
+    comparison.compare(left, right, "root".to_string());
+    comparison.differences
+}
+
+struct Comparison {
+    /// Pairs of nodes that were already compared, to avoid exponential work on shared subtrees
+    visited: HashSet<(Cmr, Cmr)>,
+    differences: Vec<Difference>,
+}
+
+impl Comparison {
+    fn compare(
+        &mut self,
+        left: &Arc<CommitNode<Elements>>,
+        right: &Arc<CommitNode<Elements>>,
+        path: String,
+    ) {
+        if left.cmr() == right.cmr()
+            || MAX_DIFFERENCES <= self.differences.len()
+            || !self.visited.insert((left.cmr(), right.cmr()))
+        {
+            return;
+        }
+        let (left_kind, right_kind) = (left.inner().to_string(), right.inner().to_string());
+        let (left_children, right_children) = (children(left), children(right));
+        if left_kind != right_kind || left_children.is_empty() {
+            self.differences.push(Difference {
+                path,
+                left: describe(left),
+                right: describe(right),
+            });
+            return;
+        }
+        for (index, (left, right)) in left_children.iter().zip(&right_children).enumerate() {
+            self.compare(left, right, format!("{path} › {left_kind}[{index}]"));
+        }
+    }
+}
+
+fn children(node: &Arc<CommitNode<Elements>>) -> Vec<&Arc<CommitNode<Elements>>> {
+    match node.inner() {
+        Inner::InjL(child)
+        | Inner::InjR(child)
+        | Inner::Take(child)
+        | Inner::Drop(child)
+        | Inner::AssertL(child, _)
+        | Inner::AssertR(_, child)
+        | Inner::Disconnect(child, _) => vec![child],
+        Inner::Comp(left, right) | Inner::Case(left, right) | Inner::Pair(left, right) => {
+            vec![left, right]
+        }
+        Inner::Iden
+        | Inner::Unit
+        | Inner::Witness(..)
+        | Inner::Fail(..)
+        | Inner::Jet(..)
+        | Inner::Word(..) => vec![],
+    }
+}
+
+fn describe(node: &Arc<CommitNode<Elements>>) -> String {
+    let cmr = node.cmr().to_string();
+    format!("{} ({}…)", node.inner(), &cmr[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simplicityhl::{Arguments, CompiledProgram};
+
+    fn commit(text: &str) -> Arc<CommitNode<Elements>> {
+        CompiledProgram::new(text, Arguments::default(), false)
+            .unwrap()
+            .commit()
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn compare_programs() {
+        let original = commit("fn main() { assert!(jet::eq_8(1, 1)); }");
+        let changed = commit("fn main() { assert!(jet::eq_8(1, 2)); }");
+
+        assert!(differences(&original, &original).is_empty());
+        let diff = differences(&original, &changed);
+        assert!(!diff.is_empty());
+        assert!(diff.iter().all(|d| d.path.starts_with("root")));
+        assert!(diff.iter().any(|d| d.left.starts_with("word")));
+    }
+}
@@ -9,7 +9,7 @@ use simplicityhl::simplicity;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::util::Expression;
+use crate::util::{self, Expression};
 
 /// Metadata for a single node in the DAG.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -106,7 +106,7 @@ impl DagBuilder {
 
         // Get type arrow
         let arrow = node.arrow();
-        let type_arrow = format!("{} → {}", arrow.source, arrow.target);
+        let type_arrow = util::type_arrow(arrow);
 
         // Get CMR as hex
         let cmr = node.cmr().as_ref().as_hex().to_string();
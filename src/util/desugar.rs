@@ -0,0 +1,280 @@
+//! Render the intermediate form of a `SimplicityHL` program.
+//!
+//! The intermediate form is the analyzed AST that the compiler lowers to Simplicity.
+//! Type aliases are resolved, every variable has an explicit type,
+//! and custom functions are inlined into their calls.
+//! The output is meant to be read, not to be compiled again.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use simplicityhl::ast::{
+    Call, CallName, CustomFunction, Expression, ExpressionInner, Match, Program, SingleExpression,
+    SingleExpressionInner, Statement,
+};
+use simplicityhl::either::Either;
+use simplicityhl::parse::ParseFromStr;
+
+const INDENT: &str = "    ";
+
+/// Parse, analyze and desugar the program `text`.
+pub fn desugar_text(text: &str) -> Result<String, String> {
+    let parsed = simplicityhl::parse::Program::parse_from_str(text).map_err(|e| e.to_string())?;
+    let program =
+        Program::analyze(&parsed).map_err(|e| e.with_file(Arc::from(text)).to_string())?;
+    Ok(desugar(&program))
+}
+
+/// Render the main function of the analyzed `program`.
+pub fn desugar(program: &Program) -> String {
+    let mut output = String::from("fn main() ");
+    write_expression(&mut output, program.main(), 0);
+    output.push('\n');
+    output
+}
+
+fn write_indent(output: &mut String, depth: usize) {
+    for _ in 0..depth {
+        output.push_str(INDENT);
+    }
+}
+
+fn write_expression(output: &mut String, expression: &Expression, depth: usize) {
+    match expression.inner() {
+        ExpressionInner::Single(single) => write_single(output, single, depth),
+        ExpressionInner::Block(statements, result) => {
+            output.push_str("{\n");
+            for statement in statements.iter() {
+                write_indent(output, depth + 1);
+                write_statement(output, statement, depth + 1);
+                output.push('\n');
+            }
+            if let Some(result) = result {
+                write_indent(output, depth + 1);
+                write_expression(output, result, depth + 1);
+                output.push('\n');
+            }
+            write_indent(output, depth);
+            output.push('}');
+        }
+    }
+}
+
+fn write_statement(output: &mut String, statement: &Statement, depth: usize) {
+    match statement {
+        Statement::Assignment(assignment) => {
+            let ty = assignment.expression().ty();
+            let _ = write!(output, "let {}: {ty} = ", assignment.pattern());
+            write_expression(output, assignment.expression(), depth);
+        }
+        Statement::Expression(expression) => write_expression(output, expression, depth),
+    }
+    output.push(';');
+}
+
+fn write_list(output: &mut String, expressions: &[Expression], depth: usize) {
+    for (index, expression) in expressions.iter().enumerate() {
+        if 0 < index {
+            output.push_str(", ");
+        }
+        write_expression(output, expression, depth);
+    }
+}
+
+fn write_single(output: &mut String, single: &SingleExpression, depth: usize) {
+    match single.inner() {
+        SingleExpressionInner::Constant(value) => {
+            let _ = write!(output, "{value}");
+        }
+        SingleExpressionInner::Witness(name) => {
+            let _ = write!(output, "witness::{name}");
+        }
+        SingleExpressionInner::Parameter(name) => {
+            let _ = write!(output, "param::{name}");
+        }
+        SingleExpressionInner::Variable(identifier) => {
+            let _ = write!(output, "{identifier}");
+        }
+        SingleExpressionInner::Expression(inner) => {
+            output.push('(');
+            write_expression(output, inner, depth);
+            output.push(')');
+        }
+        SingleExpressionInner::Tuple(elements) => {
+            output.push('(');
+            write_list(output, elements, depth);
+            if elements.len() == 1 {
+                output.push(',');
+            }
+            output.push(')');
+        }
+        SingleExpressionInner::Array(elements) => {
+            output.push('[');
+            write_list(output, elements, depth);
+            output.push(']');
+        }
+        SingleExpressionInner::List(elements) => {
+            output.push_str("list![");
+            write_list(output, elements, depth);
+            output.push(']');
+        }
+        SingleExpressionInner::Either(either) => {
+            let (name, inner) = match either {
+                Either::Left(inner) => ("Left", inner),
+                Either::Right(inner) => ("Right", inner),
+            };
+            let _ = write!(output, "{name}(");
+            write_expression(output, inner, depth);
+            output.push(')');
+        }
+        SingleExpressionInner::Option(None) => output.push_str("None"),
+        SingleExpressionInner::Option(Some(inner)) => {
+            output.push_str("Some(");
+            write_expression(output, inner, depth);
+            output.push(')');
+        }
+        SingleExpressionInner::Call(call) => write_call(output, call, depth),
+        SingleExpressionInner::Match(match_) => write_match(output, match_, depth),
+    }
+}
+
+fn write_call(output: &mut String, call: &Call, depth: usize) {
+    let name = match call.name() {
+        CallName::Jet(jet) => format!("jet::{jet}"),
+        CallName::UnwrapLeft(ty) => format!("unwrap_left::<{ty}>"),
+        CallName::UnwrapRight(ty) => format!("unwrap_right::<{ty}>"),
+        CallName::IsNone(ty) => format!("is_none::<{ty}>"),
+        CallName::Unwrap => "unwrap".to_string(),
+        CallName::Assert => "assert!".to_string(),
+        CallName::Panic => "panic!".to_string(),
+        CallName::Debug => "dbg!".to_string(),
+        CallName::TypeCast(source) => format!("<{source}>::into"),
+        CallName::Custom(function) => {
+            write_inlined_call(output, function, call.args(), depth);
+            return;
+        }
+        CallName::Fold(function, bound) => {
+            write_higher_order_call(output, &format!("fold::<{bound}>"), function, call, depth);
+            return;
+        }
+        CallName::ArrayFold(function, size) => {
+            let name = format!("array_fold::<{size}>");
+            write_higher_order_call(output, &name, function, call, depth);
+            return;
+        }
+        CallName::ForWhile(function, bound) => {
+            let name = format!("for_while::<{bound}>");
+            write_higher_order_call(output, &name, function, call, depth);
+            return;
+        }
+    };
+    output.push_str(&name);
+    output.push('(');
+    write_list(output, call.args(), depth);
+    output.push(')');
+}
+
+/// Write the tuple type of the parameters of `function`.
+fn parameters_type(function: &CustomFunction) -> String {
+    let types: Vec<String> = function
+        .params()
+        .iter()
+        .map(|param| param.ty().to_string())
+        .collect();
+    match types.len() {
+        1 => format!("({},)", types[0]),
+        _ => format!("({})", types.join(", ")),
+    }
+}
+
+/// Write the call of a custom function as a block that binds the arguments to the parameters.
+fn write_inlined_call(
+    output: &mut String,
+    function: &CustomFunction,
+    args: &[Expression],
+    depth: usize,
+) {
+    output.push_str("{\n");
+    write_indent(output, depth + 1);
+    let _ = write!(
+        output,
+        "let {}: {} = (",
+        function.params_pattern(),
+        parameters_type(function)
+    );
+    write_list(output, args, depth + 1);
+    if args.len() == 1 {
+        output.push(',');
+    }
+    output.push_str(");\n");
+    write_indent(output, depth + 1);
+    write_expression(output, function.body(), depth + 1);
+    output.push('\n');
+    write_indent(output, depth);
+    output.push('}');
+}
+
+/// Write the call of a builtin that takes a custom function as a closure.
+fn write_higher_order_call(
+    output: &mut String,
+    name: &str,
+    function: &CustomFunction,
+    call: &Call,
+    depth: usize,
+) {
+    let _ = write!(output, "{name}(");
+    write_list(output, call.args(), depth);
+    if !call.args().is_empty() {
+        output.push_str(", ");
+    }
+    output.push('|');
+    for (index, param) in function.params().iter().enumerate() {
+        if 0 < index {
+            output.push_str(", ");
+        }
+        let _ = write!(output, "{}: {}", param.identifier(), param.ty());
+    }
+    let _ = write!(output, "| -> {} ", function.body().ty());
+    write_expression(output, function.body(), depth);
+    output.push(')');
+}
+
+fn write_match(output: &mut String, match_: &Match, depth: usize) {
+    output.push_str("match ");
+    write_expression(output, match_.scrutinee(), depth);
+    output.push_str(" {\n");
+    for arm in [match_.left(), match_.right()] {
+        write_indent(output, depth + 1);
+        let _ = write!(output, "{} => ", arm.pattern());
+        write_expression(output, arm.expression(), depth + 1);
+        output.push_str(",\n");
+    }
+    write_indent(output, depth);
+    output.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn inline_functions_and_aliases() {
+        let text = r#"type Byte = u8;
+
+fn double(x: Byte) -> Byte {
+    let (_, y): (bool, Byte) = jet::add_8(x, x);
+    y
+}
+
+fn main() {
+    assert!(jet::eq_8(double(2), 4));
+}"#;
+        let desugared = desugar_text(text).unwrap();
+        assert!(desugared.starts_with("fn main() {\n    assert!(jet::eq_8({"));
+        assert!(desugared.contains(": (u8,) = (2,);"));
+        assert!(desugared.contains("let (_, y): (bool, u8) = jet::add_8(x, x);"));
+        assert!(!desugared.contains("Byte"));
+        assert!(!desugared.contains("double"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Problems of the program, located in its text so that the editor can mark them.
+
+use std::ops::Range;
+
+use simplicityhl::error::{Position, Span};
+
+use crate::util::code_frame;
+
+/// How bad a problem is.
+///
+/// Errors are more severe than warnings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Return the CSS class of text with a problem of this severity.
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Warning => "diagnostic-warning",
+            Self::Error => "diagnostic-error",
+        }
+    }
+}
+
+/// Problem of the program with its location in the program text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Line and column where the problem starts.
+    ///
+    /// Lines start at 1. Columns are character indices that start at 0.
+    pub start: (usize, usize),
+    /// Line and column where the problem ends, exclusive.
+    pub end: (usize, usize),
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    /// Locate the compilation `error` of the program `text`.
+    ///
+    /// Errors without a location cover the entire program, so that they are not lost.
+    pub fn from_error(text: &str, error: &str) -> Self {
+        let located = code_frame::code_frame(text, error).and_then(|frame| {
+            let mut underlined = frame.lines.iter().filter_map(|line| {
+                let underline = line.underline.clone()?;
+                let width = line.content.chars().count();
+                Some((
+                    line.number,
+                    underline.start.min(width),
+                    underline.end.min(width),
+                ))
+            });
+            let (first, start, first_end) = underlined.next()?;
+            let (last, end) = underlined
+                .next_back()
+                .map_or((first, first_end), |(number, _, end)| (number, end));
+            let message = match frame.preamble.is_empty() {
+                true => frame.message,
+                false => format!("{}\n{}", frame.preamble, frame.message),
+            };
+            Some(((first, start), (last, end), message))
+        });
+        let ((start, end), message) = match located {
+            Some((start, end, message)) => ((start, end), message),
+            None => (((1, 0), position(text, text.len())), error.to_string()),
+        };
+        Self {
+            start,
+            end,
+            message,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Cover the `span` of an error that the compiler located.
+    pub fn from_span(span: &Span, message: String) -> Self {
+        // Columns of the compiler start at 1
+        let position =
+            |position: Position| (position.line.get(), position.col.get().saturating_sub(1));
+        Self {
+            start: position(span.start),
+            end: position(span.end),
+            message,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Cover the given `line` of the program `text`, such as for a warning about that line.
+    pub fn line(text: &str, line: usize, message: String, severity: Severity) -> Self {
+        let width = text
+            .lines()
+            .nth(line.saturating_sub(1))
+            .map_or(0, |content| content.chars().count());
+        Self {
+            start: (line, 0),
+            end: (line, width),
+            message,
+            severity,
+        }
+    }
+
+    /// Return the byte range of the problem in the program `text`.
+    pub fn span(&self, text: &str) -> Range<usize> {
+        let start = offset(text, self.start);
+        start..offset(text, self.end).max(start)
+    }
+}
+
+/// Return the line and column of the byte `offset` of `text`.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count(),
+    )
+}
+
+/// Return the byte offset of the given line and column of `text`.
+///
+/// Positions past the end of a line are clamped to the end of that line.
+fn offset(text: &str, (line, column): (usize, usize)) -> usize {
+    let line_start = match line.saturating_sub(1) {
+        0 => 0,
+        skipped => text
+            .match_indices('\n')
+            .nth(skipped - 1)
+            .map_or(text.len(), |(newline, _)| newline + 1),
+    };
+    let content = &text[line_start..];
+    let content = &content[..content.find('\n').unwrap_or(content.len())];
+    line_start
+        + content
+            .char_indices()
+            .nth(column)
+            .map_or(content.len(), |(index, _)| index)
+}
+
+/// Split `text` into pieces that are marked with the severity of the problems that cover them.
+///
+/// Where problems overlap, the more severe problem marks the text.
+pub fn mark<'a>(text: &'a str, diagnostics: &[Diagnostic]) -> Vec<(Option<Severity>, &'a str)> {
+    let mut severities = vec![None; text.len()];
+    for diagnostic in diagnostics {
+        for severity in &mut severities[diagnostic.span(text)] {
+            *severity = (*severity).max(Some(diagnostic.severity));
+        }
+    }
+    let mut pieces = Vec::<(Option<Severity>, &str)>::new();
+    let mut start = 0;
+    for end in 1..=text.len() {
+        if end == text.len() || (text.is_char_boundary(end) && severities[end] != severities[start])
+        {
+            pieces.push((severities[start], &text[start..end]));
+            start = end;
+        }
+    }
+    pieces
+}
+
+/// Return the most severe problem at the byte `offset` of `text`.
+pub fn at<'a>(text: &str, diagnostics: &'a [Diagnostic], offset: usize) -> Option<&'a Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            let span = diagnostic.span(text);
+            span.contains(&offset) || span.end == offset
+        })
+        .max_by_key(|diagnostic| diagnostic.severity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simplicityhl::{Arguments, CompiledProgram};
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn locate_compile_error() {
+        let text = "fn main() {\n    let x: u8 = foo;\n}";
+        let error = CompiledProgram::new(text, Arguments::default(), false).unwrap_err();
+        let diagnostic = Diagnostic::from_error(text, &error);
+        assert_eq!(((2, 16), (2, 19)), (diagnostic.start, diagnostic.end));
+        assert_eq!("Variable `foo` is not defined", diagnostic.message);
+        assert_eq!("foo", &text[diagnostic.span(text)]);
+        let offset = text.find("foo").unwrap() + 1;
+        assert_eq!(
+            Some(&diagnostic),
+            at(text, std::slice::from_ref(&diagnostic), offset)
+        );
+
+        let error =
+            crate::pipeline::compile_text("fn main() {\n    let x: u8 = ;\n}", false).unwrap_err();
+        let diagnostic = Diagnostic::from_span(error.span().unwrap(), error.message());
+        assert_eq!((2, 16), diagnostic.start);
+        assert!(!diagnostic.message.contains('|'));
+
+        let unlocated = Diagnostic::from_error(text, "Jet failed");
+        assert_eq!(0..text.len(), unlocated.span(text));
+        assert_eq!("Jet failed", unlocated.message);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn mark_diagnostics() {
+        let text = "ab\ncdé\nf";
+        let warning = Diagnostic::line(text, 2, "Warning".into(), Severity::Warning);
+        assert_eq!("cdé", &text[warning.span(text)]);
+        let error = Diagnostic {
+            start: (2, 2),
+            end: (3, 1),
+            message: "Error".into(),
+            severity: Severity::Error,
+        };
+        assert_eq!(
+            vec![
+                (None, "ab\n"),
+                (Some(Severity::Warning), "cd"),
+                (Some(Severity::Error), "é\nf"),
+            ],
+            mark(text, &[warning, error])
+        );
+        assert!(mark("", &[]).is_empty());
+    }
+}
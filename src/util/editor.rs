@@ -0,0 +1,389 @@
+//! Text transformations for the program editor.
+//!
+//! The browser reports selections in UTF-16 code units,
+//! so the functions in this module accept and return UTF-16 offsets.
+
+use std::ops::Range;
+
+use crate::jet;
+use crate::util::source;
+use crate::util::witness;
+
+/// Convert a UTF-16 offset into a byte offset of `text`.
+///
+/// Offsets past the end of `text` are clamped to its length.
+pub fn byte_offset(text: &str, utf16_offset: usize) -> usize {
+    let mut utf16_count = 0;
+    for (index, c) in text.char_indices() {
+        if utf16_offset <= utf16_count {
+            return index;
+        }
+        utf16_count += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Convert a byte offset of `text` into a UTF-16 offset.
+pub fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+/// Toggle `//` line comments on all lines touched by the selection `start..end`.
+///
+/// If every non-blank selected line is commented, then the comments are removed.
+/// Otherwise, all non-blank selected lines are commented out.
+///
+/// Return the new text and the new selection, which spans the affected lines.
+pub fn toggle_line_comments(text: &str, start: usize, end: usize) -> (String, usize, usize) {
+    let start = byte_offset(text, start);
+    let mut end = byte_offset(text, end).max(start);
+    // A selection that ends at the beginning of a line does not include that line
+    if start < end && text[..end].ends_with('\n') {
+        end -= 1;
+    }
+    let first_line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let last_line_end = text[end..].find('\n').map_or(text.len(), |i| end + i);
+
+    let lines = text[first_line_start..last_line_end]
+        .split('\n')
+        .collect::<Vec<_>>();
+    let is_blank = |line: &&str| line.trim().is_empty();
+    let all_commented = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .all(|line| line.trim_start().starts_with("//"));
+    let indent = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let toggled = lines
+        .iter()
+        .map(|line| {
+            if is_blank(line) {
+                return (*line).to_string();
+            }
+            match all_commented {
+                true => {
+                    let content_start = line.len() - line.trim_start().len();
+                    let (whitespace, content) = line.split_at(content_start);
+                    let uncommented = content.strip_prefix("//").unwrap_or(content);
+                    let uncommented = uncommented.strip_prefix(' ').unwrap_or(uncommented);
+                    format!("{whitespace}{uncommented}")
+                }
+                false => format!("{}// {}", &line[..indent], &line[indent..]),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let new_text = format!(
+        "{}{toggled}{}",
+        &text[..first_line_start],
+        &text[last_line_end..]
+    );
+    let new_start = utf16_offset(&new_text, first_line_start);
+    let new_end = utf16_offset(&new_text, first_line_start + toggled.len());
+    (new_text, new_start, new_end)
+}
+
+/// Insert `width` spaces at the cursor.
+///
+/// Return the new text and the new cursor, which follows the spaces.
+pub fn indent(text: &str, cursor: usize, width: usize) -> (String, usize) {
+    let mut new_text = text.to_string();
+    new_text.insert_str(byte_offset(text, cursor), &" ".repeat(width));
+    (new_text, cursor + width)
+}
+
+/// Remove `width` spaces before the cursor.
+///
+/// Return the new text and the new cursor,
+/// or nothing if there are fewer than `width` spaces before the cursor.
+pub fn unindent(text: &str, cursor: usize, width: usize) -> Option<(String, usize)> {
+    let end = byte_offset(text, cursor);
+    // Spaces are one byte long and one UTF-16 code unit long
+    let start = text[..end].strip_suffix(&" ".repeat(width)).map(str::len)?;
+    let mut new_text = text.to_string();
+    new_text.replace_range(start..end, "");
+    Some((new_text, cursor - width))
+}
+
+/// Make whitespace and invisible characters of `text` visible.
+///
+/// Spaces become `·`, tabs become `→`, carriage returns become `␍`,
+/// and the end of each line is marked with `¶`.
+/// Other invisible characters, such as zero-width spaces, are shown as their code point.
+pub fn visualize_whitespace(text: &str) -> String {
+    let mut visible = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' ' => visible.push('·'),
+            '\t' => visible.push('→'),
+            '\r' => visible.push('␍'),
+            '\n' => visible.push_str("¶\n"),
+            '\u{a0}' | '\u{200b}'..='\u{200f}' | '\u{2028}'..='\u{202f}' | '\u{feff}' => {
+                visible.push_str(&format!("<U+{:04X}>", u32::from(c)));
+            }
+            c => visible.push(c),
+        }
+    }
+    visible
+}
+
+/// Character outside of comments that is likely to break parsing,
+/// such as a zero-width space or a smart quote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuspiciousChar {
+    /// Line of the character.
+    ///
+    /// Starts at 1.
+    pub line: usize,
+    /// Column of the character, counted in characters.
+    ///
+    /// Starts at 1.
+    pub column: usize,
+    pub character: char,
+}
+
+impl SuspiciousChar {
+    /// Describe the character by its code point and name.
+    pub fn description(&self) -> String {
+        let code_point = format!("U+{:04X}", u32::from(self.character));
+        match replacement(self.character) {
+            Some((name, _)) => format!("{code_point} ({name})"),
+            None => format!("{code_point} ('{}')", self.character),
+        }
+    }
+}
+
+/// Return the name of a known suspicious character and the ASCII text that replaces it.
+fn replacement(c: char) -> Option<(&'static str, &'static str)> {
+    let replacement = match c {
+        '\u{a0}' => ("no-break space", " "),
+        '\u{2000}'..='\u{200a}' => ("typographic space", " "),
+        '\u{200b}' => ("zero-width space", ""),
+        '\u{200c}' => ("zero-width non-joiner", ""),
+        '\u{200d}' => ("zero-width joiner", ""),
+        '\u{200e}' | '\u{200f}' => ("direction mark", ""),
+        '\u{2028}' => ("line separator", "\n"),
+        '\u{2029}' => ("paragraph separator", "\n"),
+        '\u{202a}'..='\u{202e}' => ("bidirectional control", ""),
+        '\u{202f}' => ("narrow no-break space", " "),
+        '\u{2060}' => ("word joiner", ""),
+        '\u{feff}' => ("byte order mark", ""),
+        '\u{2018}' | '\u{2019}' => ("smart single quote", "'"),
+        '\u{201c}' | '\u{201d}' => ("smart double quote", "\""),
+        '\u{2013}' | '\u{2014}' | '\u{2212}' => ("dash", "-"),
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// Iterate over the non-ASCII characters of `text` outside of comments,
+/// together with their byte offsets.
+fn non_ascii_code(text: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    // Comments are blanked with spaces, and non-ASCII characters are never spaces
+    let code = source::blank_comments(text).into_bytes();
+    text.char_indices()
+        .filter(move |(offset, c)| !c.is_ascii() && code[*offset] != b' ')
+}
+
+/// Return the non-ASCII characters of `text` outside of comments, in order of occurrence.
+///
+/// Programs consist of ASCII only, so these characters cause parse errors
+/// that are hard to understand if the characters are invisible or look like ASCII.
+pub fn suspicious_chars(text: &str) -> Vec<SuspiciousChar> {
+    non_ascii_code(text)
+        .map(|(offset, character)| {
+            let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+            SuspiciousChar {
+                line: source::line_of(text, offset),
+                column: text[line_start..offset].chars().count() + 1,
+                character,
+            }
+        })
+        .collect()
+}
+
+/// Replace known suspicious characters outside of comments by their ASCII equivalents.
+///
+/// Other non-ASCII characters are kept, because there is no obvious replacement.
+pub fn sanitize(text: &str) -> String {
+    let mut sanitized = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (offset, c) in non_ascii_code(text) {
+        if let Some((_, ascii)) = replacement(c) {
+            sanitized.push_str(&text[copied..offset]);
+            sanitized.push_str(ascii);
+            copied = offset + c.len_utf8();
+        }
+    }
+    sanitized.push_str(&text[copied..]);
+    sanitized
+}
+
+/// Name that completes the partial name before the cursor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    pub name: String,
+    /// Signature of a jet or type of a witness or parameter.
+    pub detail: String,
+    /// Link to the documentation of a jet.
+    pub documentation: Option<String>,
+}
+
+/// Return the completions of the partial name before the cursor and the span of that name.
+///
+/// Names of jets complete after `jet::`,
+/// and names that the program declares in its modules complete after `witness::` and `param::`.
+/// A partial name that already is the only completion has no completions.
+pub fn completions(text: &str, cursor: usize) -> Option<(Range<usize>, Vec<Completion>)> {
+    let offset = byte_offset(text, cursor);
+    let (span, completions) = if let Some(span) = source::jet_prefix(text, offset) {
+        let completions = jet::completions(&text[span.clone()])
+            .into_iter()
+            .map(|jet| Completion {
+                name: jet.to_string(),
+                detail: jet::signature(jet),
+                documentation: jet::documentation_url(jet),
+            })
+            .collect();
+        (span, completions)
+    } else {
+        let (kind, span) = source::reference_prefix(text, offset)?;
+        let prefix = &text[span.clone()];
+        let mut completions = source::declarations(text, kind)
+            .into_iter()
+            .filter(|declaration| declaration.name.starts_with(prefix))
+            .map(|declaration| Completion {
+                detail: witness::declared_type(&text[declaration.span])
+                    .unwrap_or_default()
+                    .to_string(),
+                name: declaration.name,
+                documentation: None,
+            })
+            .collect::<Vec<_>>();
+        if matches!(completions.as_slice(), [completion] if completion.name == prefix) {
+            completions.clear();
+        }
+        (span, completions)
+    };
+    let span = utf16_offset(text, span.start)..utf16_offset(text, span.end);
+    Some((span, completions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn comment_single_line() {
+        let text = "fn main() {\n    assert!(true);\n}";
+        let (toggled, start, end) = toggle_line_comments(text, 16, 16);
+        assert_eq!("fn main() {\n    // assert!(true);\n}", toggled);
+        assert_eq!((12, 33), (start, end));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn toggle_is_involution() {
+        let text = "fn main() {\n    let a: u8 = 1;\n\n        assert!(true);\n}";
+        let (commented, start, end) = toggle_line_comments(text, 0, text.len());
+        assert!(commented
+            .lines()
+            .all(|l| l.is_empty() || l.starts_with("// ")));
+        let (uncommented, ..) = toggle_line_comments(&commented, start, end);
+        assert_eq!(text, uncommented);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn mixed_selection_is_commented() {
+        let text = "// a\nb";
+        let (toggled, ..) = toggle_line_comments(text, 0, text.len());
+        assert_eq!("// // a\n// b", toggled);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn selection_ending_at_line_start() {
+        let text = "a\nb\nc";
+        let (toggled, ..) = toggle_line_comments(text, 0, 4);
+        assert_eq!("// a\n// b\nc", toggled);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn indent_after_non_ascii() {
+        let text = "// é → ✍️\n    x";
+        // UTF-16 offset of `x`
+        let cursor = text.encode_utf16().count() - 1;
+        let (indented, new_cursor) = indent(text, cursor, 4);
+        assert_eq!("// é → ✍️\n        x", indented);
+        assert_eq!(cursor + 4, new_cursor);
+        assert_eq!(
+            Some((text.to_string(), cursor)),
+            unindent(&indented, new_cursor, 4)
+        );
+        assert_eq!(None, unindent(text, cursor, 8));
+        assert_eq!(None, unindent(text, 2, 2));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn utf16_offsets() {
+        let text = "// ✍️ x\ny";
+        let offset = text.encode_utf16().count();
+        assert_eq!(text.len(), byte_offset(text, offset));
+        assert_eq!(offset, utf16_offset(text, text.len()));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn visible_whitespace() {
+        let text = "a b\r\n\tc\u{200b}";
+        assert_eq!("a·b␍¶\n→c<U+200B>", visualize_whitespace(text));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_and_sanitize_suspicious_chars() {
+        let text =
+            "// “comments” are fine\nfn main() {\n    assert!(jet::eq_8(1,\u{200b} 1));\n}\n";
+        let found = suspicious_chars(text);
+        assert_eq!(1, found.len());
+        assert_eq!((3, 25), (found[0].line, found[0].column));
+        assert_eq!("U+200B (zero-width space)", found[0].description());
+
+        let sanitized = sanitize(text);
+        assert!(suspicious_chars(&sanitized).is_empty());
+        assert!(sanitized.starts_with("// “comments”"));
+        assert!(sanitized.contains("jet::eq_8(1, 1)"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn complete_references() {
+        let text = "mod witness {\n    const SIG: Signature = 0x00;\n    const SALT: u8 = 1;\n}\n\nmod param {\n    const KEY: Pubkey = 0x00;\n}\n\nfn main() {\n    let a = witness::S\n}";
+        let cursor = utf16_offset(text, text.rfind('\n').unwrap());
+        let (span, found) = completions(text, cursor).unwrap();
+        assert_eq!(cursor - 1..cursor, span);
+        let names: Vec<_> = found.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(vec!["SIG", "SALT"], names);
+        assert_eq!("Signature", found[0].detail);
+
+        let text = text.replace("witness::S", "param::");
+        let cursor = utf16_offset(&text, text.rfind('\n').unwrap());
+        let (_, found) = completions(&text, cursor).unwrap();
+        assert_eq!("KEY", found[0].name);
+
+        let text = text.replace("param::", "param::KEY");
+        let cursor = utf16_offset(&text, text.rfind('\n').unwrap());
+        assert!(completions(&text, cursor).unwrap().1.is_empty());
+        assert_eq!(None, completions(&text, 3));
+    }
+}
@@ -0,0 +1,89 @@
+//! Serialize satisfied programs the way they are put on chain.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hex_conservative::DisplayHex;
+use simplicityhl::SatisfiedProgram;
+
+/// Program and witness data of a satisfied program, serialized separately.
+///
+/// The spending transaction carries both as separate items of its witness stack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodedProgram {
+    /// Bytes of the program without its witness data.
+    pub program: Vec<u8>,
+    /// Bytes of the witness data.
+    pub witness: Vec<u8>,
+}
+
+impl EncodedProgram {
+    pub fn program_hex(&self) -> String {
+        self.program.as_hex().to_string()
+    }
+
+    pub fn program_base64(&self) -> String {
+        STANDARD.encode(&self.program)
+    }
+
+    pub fn witness_hex(&self) -> String {
+        self.witness.as_hex().to_string()
+    }
+
+    pub fn witness_base64(&self) -> String {
+        STANDARD.encode(&self.witness)
+    }
+}
+
+/// Serialize the program and the witness data of the satisfied program `p`.
+pub fn encode_satisfied(p: &SatisfiedProgram) -> EncodedProgram {
+    let (program, witness) = p.redeem().to_vec_with_witness();
+    EncodedProgram { program, witness }
+}
+
+#[cfg(test)]
+mod tests {
+    use simplicityhl::parse::ParseFromStr;
+    use simplicityhl::simplicity::jet::Elements;
+    use simplicityhl::simplicity::{BitIter, RedeemNode};
+    use simplicityhl::{Arguments, CompiledProgram, WitnessValues};
+
+    use super::*;
+
+    fn encode(text: &str) -> (SatisfiedProgram, EncodedProgram) {
+        let compiled = CompiledProgram::new(text, Arguments::default(), false).unwrap();
+        let witness = WitnessValues::parse_from_str(text).unwrap_or_default();
+        let satisfied = compiled.satisfy(witness).unwrap();
+        let encoded = encode_satisfied(&satisfied);
+        (satisfied, encoded)
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn encode_round_trip() {
+        let (satisfied, encoded) = encode(
+            "mod witness { const X: u32 = 7; } fn main() { assert!(jet::eq_32(witness::X, 7)); }",
+        );
+        assert_eq!(
+            satisfied.redeem().to_vec_with_witness(),
+            (encoded.program.clone(), encoded.witness.clone())
+        );
+        assert!(!encoded.witness.is_empty());
+        let decoded = RedeemNode::<Elements>::decode(
+            BitIter::from(encoded.program.iter().copied()),
+            BitIter::from(encoded.witness.iter().copied()),
+        )
+        .unwrap();
+        assert_eq!(decoded.cmr(), satisfied.redeem().cmr());
+        assert_eq!(
+            STANDARD.decode(encoded.program_base64()).unwrap(),
+            encoded.program
+        );
+        assert_eq!(
+            STANDARD.decode(encoded.witness_base64()).unwrap(),
+            encoded.witness
+        );
+
+        let (_, encoded) = encode("fn main() { assert!(true); }");
+        assert!(encoded.witness.is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+//! Format `SimplicityHL` programs consistently.
+//!
+//! Formatting only changes whitespace: each line is indented by how deeply it is nested,
+//! trailing whitespace is removed, runs of blank lines shrink to a single blank line
+//! and the program ends with one newline.
+//! Comments stay where they are, and lines inside block comments stay unchanged.
+
+use simplicityhl::parse::ParseFromStr;
+
+use crate::util::source;
+
+/// Return the formatted form of the program `text`, indented by `indent` spaces per level,
+/// or the parse error if the program does not parse.
+pub fn format(text: &str, indent: usize) -> Result<String, String> {
+    simplicityhl::parse::Program::parse_from_str(text).map_err(|error| error.to_string())?;
+
+    let blanked = source::blank_comments(text);
+    let mut output = String::with_capacity(text.len());
+    let mut depth = 0_usize;
+    let mut in_block_comment = false;
+    let mut after_blank = true;
+    for (line, code) in text.lines().zip(blanked.lines()) {
+        let starts_in_comment = in_block_comment;
+        in_block_comment = ends_in_block_comment(line, in_block_comment);
+        let closing = code
+            .trim_start()
+            .chars()
+            .take_while(|c| matches!(c, '}' | ')' | ']'))
+            .count();
+        let line_depth = depth.saturating_sub(closing);
+        for c in code.chars() {
+            match c {
+                '{' | '(' | '[' => depth += 1,
+                '}' | ')' | ']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        if starts_in_comment {
+            output.push_str(line.trim_end());
+            output.push('\n');
+            after_blank = false;
+            continue;
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            if !after_blank {
+                output.push('\n');
+            }
+            after_blank = true;
+            continue;
+        }
+        output.extend(std::iter::repeat(' ').take(line_depth * indent));
+        output.push_str(line);
+        output.push('\n');
+        after_blank = false;
+    }
+    while output.ends_with("\n\n") {
+        output.pop();
+    }
+    Ok(output)
+}
+
+/// Check whether a block comment is still open at the end of `line`,
+/// given whether one was open at its start.
+fn ends_in_block_comment(line: &str, mut in_block_comment: bool) -> bool {
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (in_block_comment, c, chars.peek()) {
+            (true, '*', Some('/')) => {
+                chars.next();
+                in_block_comment = false;
+            }
+            (false, '/', Some('*')) => {
+                chars.next();
+                in_block_comment = true;
+            }
+            (false, '/', Some('/')) => break,
+            _ => {}
+        }
+    }
+    in_block_comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn format_program() {
+        let text = "mod witness {\nconst A: u8 = 1;   \n}\n\n\n\nfn main() {\n  // Check it\n        let (a, b): (u8, u8) = (\nwitness::A,\n            1,\n  );\n/* keep\n      this */\n    assert!(jet::eq_8(a, b));\n}\n\n";
+        let expected = "mod witness {\n    const A: u8 = 1;\n}\n\nfn main() {\n    // Check it\n    let (a, b): (u8, u8) = (\n        witness::A,\n        1,\n    );\n    /* keep\n      this */\n    assert!(jet::eq_8(a, b));\n}\n";
+        assert_eq!(format(text, 4).unwrap(), expected);
+        assert_eq!(format(expected, 4).unwrap(), expected);
+        assert!(format("fn main( {", 4).is_err());
+        let modules =
+            "mod param {\nconst P: u8 = 2;\n}\nmod witness {\n  const W: u8 = 2;\n}\nfn main() {}";
+        let formatted = format(modules, 2).unwrap();
+        assert_eq!(formatted, "mod param {\n  const P: u8 = 2;\n}\nmod witness {\n  const W: u8 = 2;\n}\nfn main() {}\n");
+        assert_eq!(format(&formatted, 2).unwrap(), formatted);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn format_examples() {
+        for name in examples::keys() {
+            let text = examples::get(name).unwrap().template_text();
+            let formatted = format(text, 4).unwrap_or_else(|error| panic!("{name}: {error}"));
+            let parse = |text: &str| {
+                simplicityhl::parse::Program::parse_from_str(text)
+                    .unwrap()
+                    .to_string()
+            };
+            assert_eq!(parse(text), parse(&formatted), "{name}");
+            assert_eq!(format(&formatted, 4).unwrap(), formatted, "{name}");
+        }
+    }
+}
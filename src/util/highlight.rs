@@ -0,0 +1,301 @@
+//! Classification of program text for syntax highlighting.
+
+use std::collections::{HashMap, HashSet};
+
+use simplicityhl::parse::{Item, ParseFromStr};
+use simplicityhl::ResolvedType;
+
+/// Kind of a highlighted piece of program text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Comment,
+    Keyword,
+    /// Macro such as `assert!`, including the exclamation mark.
+    Macro,
+    /// Jet such as `jet::add_8`, including the module.
+    Jet,
+    /// Witness or parameter such as `witness::SIG`, including the module.
+    Module,
+    /// Witness such as `witness::SIG`, including the module.
+    Witness,
+    /// Parameter such as `param::KEY`, including the module.
+    Param,
+    /// Name of a custom function where it is defined or called.
+    Function,
+    /// Builtin type such as `u8` or type alias of the program.
+    Type,
+    /// Hexadecimal literal such as `0xff`.
+    Hex,
+    /// Decimal or binary literal such as `42` or `0b1010`.
+    Number,
+}
+
+impl TokenKind {
+    /// Return the CSS class of the kind.
+    pub fn class(self) -> &'static str {
+        match self {
+            TokenKind::Comment => "hl-comment",
+            TokenKind::Keyword => "hl-keyword",
+            TokenKind::Macro => "hl-macro",
+            TokenKind::Jet => "hl-jet",
+            TokenKind::Module => "hl-module",
+            TokenKind::Witness => "hl-witness",
+            TokenKind::Param => "hl-param",
+            TokenKind::Function => "hl-function",
+            TokenKind::Type => "hl-type",
+            TokenKind::Hex => "hl-hex",
+            TokenKind::Number => "hl-number",
+        }
+    }
+}
+
+const KEYWORDS: [&str; 12] = [
+    "fn", "let", "match", "mod", "const", "type", "true", "false", "None", "Some", "Left", "Right",
+];
+const MACROS: [&str; 3] = ["assert", "panic", "dbg"];
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Return the end of the identifier that starts at `start`.
+fn identifier_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|byte| !is_identifier_byte(*byte))
+        .map_or(bytes.len(), |length| start + length)
+}
+
+/// Split the program `text` into consecutive pieces,
+/// each with the kind of its token or `None` for plain text.
+///
+/// The pieces form the original text when they are joined.
+pub fn highlight(text: &str) -> Vec<(Option<TokenKind>, &str)> {
+    let bytes = text.as_bytes();
+    let mut pieces = Vec::new();
+    let mut plain_start = 0;
+    let mut index = 0;
+
+    // Pieces start and end next to ASCII bytes, which are always at char boundaries
+    while index < bytes.len() {
+        let (kind, end) = match (bytes[index], bytes.get(index + 1)) {
+            (b'/', Some(b'/')) => {
+                let end = text[index..].find('\n').map_or(text.len(), |i| index + i);
+                (TokenKind::Comment, end)
+            }
+            (b'/', Some(b'*')) => {
+                let end = text[index + 2..]
+                    .find("*/")
+                    .map_or(text.len(), |i| index + 2 + i + 2);
+                (TokenKind::Comment, end)
+            }
+            (b'0'..=b'9', _) => {
+                let end = identifier_end(bytes, index);
+                match text[index..end].starts_with("0x") {
+                    true => (TokenKind::Hex, end),
+                    false => (TokenKind::Number, end),
+                }
+            }
+            (byte, _) if byte.is_ascii_alphabetic() || byte == b'_' => {
+                let end = identifier_end(bytes, index);
+                let word = &text[index..end];
+                let is_path = text[end..].starts_with("::")
+                    && bytes
+                        .get(end + 2)
+                        .is_some_and(|byte| is_identifier_byte(*byte));
+                match word {
+                    "jet" if is_path => (TokenKind::Jet, identifier_end(bytes, end + 2)),
+                    "witness" | "param" if is_path => {
+                        (TokenKind::Module, identifier_end(bytes, end + 2))
+                    }
+                    _ if KEYWORDS.contains(&word) => (TokenKind::Keyword, end),
+                    _ if MACROS.contains(&word) && bytes.get(end) == Some(&b'!') => {
+                        (TokenKind::Macro, end + 1)
+                    }
+                    _ => {
+                        index = end;
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        if plain_start < index {
+            pieces.push((None, &text[plain_start..index]));
+        }
+        pieces.push((Some(kind), &text[index..end]));
+        index = end;
+        plain_start = end;
+    }
+    if plain_start < text.len() {
+        pieces.push((None, &text[plain_start..]));
+    }
+    pieces
+}
+
+/// Names that the parse tree of a program declares.
+struct Names {
+    functions: HashSet<String>,
+    aliases: HashSet<String>,
+    /// Whether each identifier that was looked up is a builtin type.
+    builtin_types: HashMap<String, bool>,
+}
+
+impl Names {
+    fn is_type(&mut self, word: &str) -> bool {
+        if self.aliases.contains(word) {
+            return true;
+        }
+        if let Some(is_type) = self.builtin_types.get(word) {
+            return *is_type;
+        }
+        // Only builtin types resolve without the aliases of the program
+        let is_type = ResolvedType::parse_from_str(word).is_ok();
+        self.builtin_types.insert(word.to_string(), is_type);
+        is_type
+    }
+
+    /// Split plain text into pieces, marking the functions and types.
+    fn refine<'a>(&mut self, plain: &'a str, pieces: &mut Vec<(Option<TokenKind>, &'a str)>) {
+        let bytes = plain.as_bytes();
+        let mut plain_start = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index];
+            let at_word_start = index == 0 || !is_identifier_byte(bytes[index - 1]);
+            if !(at_word_start && (byte.is_ascii_alphabetic() || byte == b'_')) {
+                index += 1;
+                continue;
+            }
+            let end = identifier_end(bytes, index);
+            let word = &plain[index..end];
+            let is_call = plain[end..].trim_start().starts_with('(');
+            let kind = match () {
+                () if is_call && self.functions.contains(word) => TokenKind::Function,
+                () if self.is_type(word) => TokenKind::Type,
+                () => {
+                    index = end;
+                    continue;
+                }
+            };
+            if plain_start < index {
+                pieces.push((None, &plain[plain_start..index]));
+            }
+            pieces.push((Some(kind), word));
+            index = end;
+            plain_start = end;
+        }
+        if plain_start < plain.len() {
+            pieces.push((None, &plain[plain_start..]));
+        }
+    }
+}
+
+/// Split the program `text` into pieces like [`highlight`],
+/// with kinds that are refined by what the parser knows about the program.
+///
+/// Witnesses and parameters are told apart,
+/// and the custom functions, type aliases and builtin types are marked.
+/// Programs that do not parse are highlighted like with [`highlight`].
+pub fn highlight_semantic(text: &str) -> Vec<(Option<TokenKind>, &str)> {
+    let Ok(parsed) = simplicityhl::parse::Program::parse_from_str(text) else {
+        return highlight(text);
+    };
+    let mut names = Names {
+        functions: HashSet::new(),
+        aliases: HashSet::new(),
+        builtin_types: HashMap::new(),
+    };
+    for item in parsed.items() {
+        match item {
+            Item::Function(function) => names.functions.insert(function.name().to_string()),
+            Item::TypeAlias(alias) => names.aliases.insert(alias.name().to_string()),
+            Item::Module => continue,
+        };
+    }
+
+    let mut pieces = Vec::new();
+    for (kind, piece) in highlight(text) {
+        match kind {
+            Some(TokenKind::Module) if piece.starts_with("witness") => {
+                pieces.push((Some(TokenKind::Witness), piece));
+            }
+            Some(TokenKind::Module) => pieces.push((Some(TokenKind::Param), piece)),
+            Some(kind) => pieces.push((Some(kind), piece)),
+            None => names.refine(piece, &mut pieces),
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn highlight_tokens() {
+        let text = "fn main() { // ünïcode\n    let x2: u8 = 0xff; /* 1 */ assert!(jet::eq_8(witness::X, 0b1)); }";
+        let pieces = highlight(text);
+        assert_eq!(
+            text,
+            pieces.iter().map(|(_, piece)| *piece).collect::<String>()
+        );
+
+        let tokens: Vec<_> = pieces
+            .into_iter()
+            .filter_map(|(kind, piece)| kind.map(|kind| (kind, piece)))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::Keyword, "fn"),
+                (TokenKind::Comment, "// ünïcode"),
+                (TokenKind::Keyword, "let"),
+                (TokenKind::Hex, "0xff"),
+                (TokenKind::Comment, "/* 1 */"),
+                (TokenKind::Macro, "assert!"),
+                (TokenKind::Jet, "jet::eq_8"),
+                (TokenKind::Module, "witness::X"),
+                (TokenKind::Number, "0b1"),
+            ]
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn highlight_semantic_tokens() {
+        let text = "type Hash = u256;\nfn check(h: Hash) {}\nfn main() {\n    let check_sum: u8 = param::N;\n    check(witness::H);\n}";
+        let pieces = highlight_semantic(text);
+        assert_eq!(
+            text,
+            pieces.iter().map(|(_, piece)| *piece).collect::<String>()
+        );
+
+        let tokens: Vec<_> = pieces
+            .into_iter()
+            .filter_map(|(kind, piece)| kind.map(|kind| (kind, piece)))
+            .filter(|(kind, _)| *kind != TokenKind::Keyword)
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::Type, "Hash"),
+                (TokenKind::Type, "u256"),
+                (TokenKind::Function, "check"),
+                (TokenKind::Type, "Hash"),
+                (TokenKind::Function, "main"),
+                (TokenKind::Type, "u8"),
+                (TokenKind::Param, "param::N"),
+                (TokenKind::Function, "check"),
+                (TokenKind::Witness, "witness::H"),
+            ]
+        );
+
+        // Programs that do not parse are highlighted lexically
+        assert_eq!(highlight_semantic("fn main( {"), highlight("fn main( {"));
+    }
+}
@@ -0,0 +1,202 @@
+//! Warnings about likely mistakes in programs.
+
+use simplicityhl::ast::{
+    Call, CallName, Expression, ExpressionInner, Program, SingleExpressionInner,
+};
+use simplicityhl::either::Either;
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::{Arguments, CompiledProgram, WitnessValues};
+
+use crate::jet;
+use crate::util::source::{self, ModuleKind};
+use crate::util::walk::{walk, Visit};
+use crate::util::{self, editor};
+
+/// Warning about a program, located at a line of its source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    /// Line that the warning refers to.
+    ///
+    /// Starts at 1.
+    pub line: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+/// Run all checks on the program `text`, ordered by line.
+///
+/// Checks that need the analyzed program are skipped if the program does not compile.
+pub fn lint(text: &str) -> Vec<Lint> {
+    let mut lints = unused_declarations(text);
+    lints.extend(suspicious_chars(text));
+    if let Ok(parsed) = simplicityhl::parse::Program::parse_from_str(text) {
+        if let Ok(program) = Program::analyze(&parsed) {
+            walk(program.main(), &mut |visit| {
+                if let Visit::Call(call) = visit {
+                    lints.extend(constant_assertion_lint(call));
+                }
+            });
+            lints.extend(constant_program(text));
+        }
+    }
+    lints.sort_by_key(|lint| lint.line);
+    // Custom functions are inlined into each of their calls
+    lints.dedup();
+    lints
+}
+
+/// Warn about witnesses and parameters that are declared but never referenced.
+fn unused_declarations(text: &str) -> Vec<Lint> {
+    ModuleKind::ALL
+        .into_iter()
+        .flat_map(|kind| {
+            let references = source::references(text, kind);
+            let noun = match kind {
+                ModuleKind::Witness => "Witness",
+                ModuleKind::Param => "Parameter",
+            };
+            source::declarations(text, kind)
+                .into_iter()
+                .filter(move |declaration| {
+                    !references.iter().any(|(name, _)| *name == declaration.name)
+                })
+                .map(move |declaration| Lint {
+                    line: declaration.line,
+                    message: format!(
+                        "{noun} `{}` is declared, but the program never uses it.",
+                        declaration.name
+                    ),
+                })
+        })
+        .collect()
+}
+
+/// Warn about non-ASCII characters in the code, which cannot be parsed.
+fn suspicious_chars(text: &str) -> Vec<Lint> {
+    editor::suspicious_chars(text)
+        .into_iter()
+        .map(|suspicious| Lint {
+            line: suspicious.line,
+            message: format!(
+                "Column {} contains the character {}, which is not allowed in code.",
+                suspicious.column,
+                suspicious.description()
+            ),
+        })
+        .collect()
+}
+
+/// Warn about a program that uses neither the witness nor the transaction.
+///
+/// Such a program has the same outcome in every execution:
+/// either anyone can spend the coins that it locks, or nobody can.
+fn constant_program(text: &str) -> Option<Lint> {
+    let arguments = Arguments::parse_from_str(text).unwrap_or_default();
+    let compiled = CompiledProgram::new(text, arguments, false).ok()?;
+    let witness = WitnessValues::parse_from_str(text).unwrap_or_default();
+    let satisfied = compiled.satisfy(witness).ok()?;
+    let message = match util::constant_outcome(satisfied.redeem())? {
+        true => "The program uses neither the witness nor the transaction, so anyone can spend its coins.",
+        false => "The program uses neither the witness nor the transaction, and it always fails, so nobody can spend its coins.",
+    };
+    let main = source::blank_comments(text).find("fn main")?;
+    Some(Lint {
+        line: source::line_of(text, main),
+        message: message.to_string(),
+    })
+}
+
+/// Warn about an assertion whose condition is the same in every execution.
+fn constant_assertion_lint(call: &Call) -> Option<Lint> {
+    if !matches!(call.name(), CallName::Assert) {
+        return None;
+    }
+    let message = call.args().first().and_then(constant_assertion)?;
+    Some(Lint {
+        line: call.span().start.line.get(),
+        message: message.to_string(),
+    })
+}
+
+/// Describe the problem with an assertion whose condition is the same in every execution.
+fn constant_assertion(condition: &Expression) -> Option<&'static str> {
+    if let ExpressionInner::Single(single) = condition.inner() {
+        if let SingleExpressionInner::Constant(value) = single.inner() {
+            return Some(match value.to_string().as_str() {
+                "true" => "This assertion always succeeds, so it has no effect.",
+                _ => "This assertion always fails, so the program can never be satisfied.",
+            });
+        }
+    }
+    is_constant(condition).then_some(
+        "This assertion depends neither on the witness, nor on the parameters, \
+        nor on the transaction, so its outcome is the same in every execution.",
+    )
+}
+
+/// Check whether the value of an expression is the same in every execution.
+///
+/// Expressions that use variables are assumed to vary.
+fn is_constant(expression: &Expression) -> bool {
+    match expression.inner() {
+        ExpressionInner::Block(..) => false,
+        ExpressionInner::Single(single) => match single.inner() {
+            SingleExpressionInner::Constant(..) | SingleExpressionInner::Option(None) => true,
+            SingleExpressionInner::Witness(..)
+            | SingleExpressionInner::Parameter(..)
+            | SingleExpressionInner::Variable(..)
+            | SingleExpressionInner::Match(..) => false,
+            SingleExpressionInner::Expression(inner)
+            | SingleExpressionInner::Option(Some(inner))
+            | SingleExpressionInner::Either(Either::Left(inner) | Either::Right(inner)) => {
+                is_constant(inner)
+            }
+            SingleExpressionInner::Tuple(elements)
+            | SingleExpressionInner::Array(elements)
+            | SingleExpressionInner::List(elements) => elements.iter().all(is_constant),
+            SingleExpressionInner::Call(call) => {
+                let pure = match call.name() {
+                    CallName::Jet(jet) => !jet::reads_environment(*jet),
+                    CallName::TypeCast(..) => true,
+                    _ => false,
+                };
+                pure && call.args().iter().all(is_constant)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_lints() {
+        let text = r#"mod witness {
+    const USED: u8 = 1;
+    const UNUSED: u8 = 2;
+}
+
+mod param {
+    const EXTRA: bool = true;
+}
+
+fn main() {
+    assert!(true);
+    assert!(jet::eq_8(1, 2));
+    assert!(jet::eq_8(witness::USED, 1));
+}"#;
+        let lines: Vec<_> = lint(text).iter().map(|lint| lint.line).collect();
+        assert_eq!(lines, [3, 7, 11, 12]);
+        assert!(lint(text)[0].message.contains("`UNUSED`"));
+
+        let constant = lint("fn main() {\n    assert!(jet::eq_8(1, 1));\n}");
+        assert_eq!(constant[0].line, 1);
+        assert!(constant[0].message.contains("anyone can spend"));
+        let never = lint("fn main() {\n    assert!(jet::eq_8(1, 2));\n}");
+        assert_eq!(never[0].line, 1);
+        assert!(never[0].message.contains("nobody can spend"));
+        assert!(lint("fn main() {\n    assert!(jet::eq_32(jet::lock_time(), 0));\n}").is_empty());
+    }
+}
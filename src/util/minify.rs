@@ -0,0 +1,79 @@
+//! Minify `SimplicityHL` programs for deployment.
+//!
+//! The minified program has no comments and as little whitespace as the grammar allows.
+//! Each top-level item is on its own line, so programs still diff well.
+//! Unlike the parse tree, the minified text keeps the contents of the witness
+//! and parameter modules.
+
+use simplicityhl::parse::ParseFromStr;
+
+use crate::util::source;
+
+/// Check whether the character can be part of an identifier, keyword or number.
+fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Return the minified form of the program `text`,
+/// or the parse error if the program does not parse.
+pub fn minify(text: &str) -> Result<String, String> {
+    simplicityhl::parse::Program::parse_from_str(text).map_err(|error| error.to_string())?;
+
+    let blanked = source::blank_comments(text);
+    let mut output = String::with_capacity(text.len());
+    let mut depth = 0_usize;
+    let mut after_space = false;
+    for c in blanked.chars() {
+        if c.is_whitespace() {
+            after_space = true;
+            continue;
+        }
+        // Whitespace is only needed between two words, like `let x`
+        if after_space && output.chars().next_back().is_some_and(is_word) && is_word(c) {
+            output.push(' ');
+        }
+        after_space = false;
+        output.push(c);
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && matches!(c, '}' | ';') {
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn minify_program() {
+        let text = "// Check the sum\nmod witness {\n    const A: u8 = 1; /* first */\n}\n\nfn main() {\n    let x: u8 = witness::A;\n    assert!(jet::eq_8(x, 1));\n}\n";
+        let expected =
+            "mod witness{const A:u8=1;}\nfn main(){let x:u8=witness::A;assert!(jet::eq_8(x,1));}\n";
+        assert_eq!(minify(text).unwrap(), expected);
+        assert!(minify("fn main( {").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn minify_examples() {
+        for name in examples::keys() {
+            let text = examples::get(name).unwrap().template_text();
+            let minified = minify(text).unwrap_or_else(|error| panic!("{name}: {error}"));
+            let parse = |text: &str| {
+                simplicityhl::parse::Program::parse_from_str(text)
+                    .unwrap()
+                    .to_string()
+            };
+            assert_eq!(parse(text), parse(&minified), "{name}");
+            assert_eq!(minify(&minified).unwrap(), minified, "{name}");
+        }
+    }
+}
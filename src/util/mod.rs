@@ -1,15 +1,40 @@
+pub mod binding;
+pub mod code_frame;
+pub mod compare;
 pub mod dag;
+pub mod desugar;
+pub mod diagnostic;
+pub mod editor;
+pub mod encoding;
+pub mod format;
+pub mod highlight;
+pub mod lint;
+pub mod minify;
+pub mod report;
+pub mod rust_test;
+pub mod source;
+pub mod tabs;
+pub mod value;
+pub mod walk;
+pub mod witness;
+
+use std::str::FromStr;
 
 use elements::hashes::{sha256, Hash};
 use elements::secp256k1_zkp as secp256k1;
+use itertools::Itertools;
 use secp256k1::rand::{self, Rng, SeedableRng};
 use simplicity::dag::{DagLike, MaxSharing, NoSharing};
 use simplicity::jet::Elements;
-use simplicity::{node, RedeemNode};
+use simplicity::types::arrow::FinalArrow;
+use simplicity::{node, BitMachine, RedeemNode};
 use simplicityhl::num::U256;
 use simplicityhl::simplicity::Preimage32;
 use simplicityhl::{elements, simplicity};
 
+use crate::jet;
+use crate::transaction::TxParams;
+
 #[derive(Clone, Debug)]
 pub struct SigningKeys {
     pub random_seed: U256,
@@ -114,6 +139,100 @@ pub fn get_compression_factor<M: node::Marker>(node: &node::Node<M>) -> usize {
     unshared_len / shared_len
 }
 
+/// Structural metrics of a Simplicity program.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ComplexityMetrics {
+    /// Number of distinct nodes in the DAG.
+    pub unique_nodes: usize,
+    /// Number of nodes in the tree that results from expanding all shared nodes.
+    ///
+    /// Saturates at `u64::MAX`.
+    pub total_nodes: u64,
+    /// Number of nodes on the longest path from the root to a leaf.
+    pub max_depth: usize,
+    /// Number of distinct nodes that are referenced more than once.
+    pub shared_nodes: usize,
+}
+
+pub fn get_complexity_metrics<M: node::Marker>(node: &node::Node<M>) -> ComplexityMetrics {
+    let mut tree_size = Vec::<u64>::new();
+    let mut depth = Vec::<usize>::new();
+    let mut references = Vec::<usize>::new();
+
+    for item in node.post_order_iter::<MaxSharing<M>>() {
+        let children = [item.left_index, item.right_index];
+        let children = children.iter().flatten().copied();
+        tree_size.push(
+            children
+                .clone()
+                .fold(1, |size, child| size.saturating_add(tree_size[child])),
+        );
+        depth.push(
+            1 + children
+                .clone()
+                .map(|child| depth[child])
+                .max()
+                .unwrap_or(0),
+        );
+        references.push(0);
+        for child in children {
+            references[child] += 1;
+        }
+    }
+
+    ComplexityMetrics {
+        unique_nodes: tree_size.len(),
+        total_nodes: tree_size.last().copied().unwrap_or(0),
+        max_depth: depth.last().copied().unwrap_or(0),
+        shared_nodes: references.iter().filter(|count| 1 < **count).count(),
+    }
+}
+
+/// Return the weight of the program in weight units, rounded up.
+pub fn weight(expression: &Expression) -> u32 {
+    // FIXME: Add conversion method to simplicity::Cost
+    let milli_weight = u32::from_str(&expression.bounds().cost.to_string()).unwrap();
+    milli_weight.saturating_add(999) / 1000
+}
+
+/// Render the type of a program or of one of its nodes, such as `1 → 1`.
+pub fn type_arrow(arrow: &FinalArrow) -> String {
+    format!("{} → {}", arrow.source, arrow.target)
+}
+
+/// Return whether the type is `1 → 1`: the program takes no input and produces no output.
+///
+/// Only such programs can lock coins.
+pub fn is_predicate(arrow: &FinalArrow) -> bool {
+    arrow.source.is_unit() && arrow.target.is_unit()
+}
+
+/// Return the outcome of a program that neither takes witness data nor reads the transaction.
+///
+/// Such a program always succeeds or always fails, no matter who spends the coins and how.
+/// Return `None` if the outcome depends on the witness or on the transaction.
+pub fn constant_outcome(expression: &Expression) -> Option<bool> {
+    let is_constant = expression
+        .pre_order_iter::<MaxSharing<_>>()
+        .all(|node| match node.inner() {
+            node::Inner::Witness(..) => false,
+            node::Inner::Jet(jet) => !jet::reads_environment(*jet),
+            _ => true,
+        });
+    if !is_constant {
+        return None;
+    }
+    // The environment is never read, so any environment will do
+    let env = TxParams::default().tx_env(expression.cmr());
+    let mut mac = BitMachine::for_program(expression).ok()?;
+    Some(mac.exec(expression, &env).is_ok())
+}
+
+/// Render bytes as a string of bits, grouped by byte.
+pub fn bit_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:08b}")).join(" ")
+}
+
 // BIP-0341's unspendable NUMS public key
 fn unspendable_internal_key() -> secp256k1::XOnlyPublicKey {
     secp256k1::XOnlyPublicKey::from_slice(&[
@@ -180,3 +299,61 @@ pub fn control_block(cmr: simplicity::Cmr) -> elements::taproot::ControlBlock {
     info.control_block(&script_ver)
         .expect("control block should exist")
 }
+
+#[cfg(test)]
+mod tests {
+    use simplicityhl::parse::ParseFromStr;
+    use simplicityhl::{Arguments, CompiledProgram, WitnessValues};
+
+    use super::*;
+
+    fn outcome(text: &str) -> Option<bool> {
+        let compiled = CompiledProgram::new(text, Arguments::default(), false).unwrap();
+        let witness = WitnessValues::parse_from_str(text).unwrap_or_default();
+        let satisfied = compiled.satisfy(witness).unwrap();
+        constant_outcome(satisfied.redeem())
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn render_type_arrows() {
+        let compiled =
+            CompiledProgram::new("fn main() { assert!(true); }", Arguments::default(), false)
+                .unwrap();
+        let commit = compiled.commit();
+        assert_eq!("1 → 1", type_arrow(commit.arrow()));
+        assert!(is_predicate(commit.arrow()));
+
+        let unit = simplicity::types::Final::unit();
+        let bit = simplicity::types::Final::two_two_n(0);
+        let arrow = FinalArrow {
+            source: unit.clone(),
+            target: bit.clone(),
+        };
+        assert_eq!("1 → 2", type_arrow(&arrow));
+        assert!(!is_predicate(&arrow));
+        let arrow = FinalArrow {
+            source: bit,
+            target: unit,
+        };
+        assert!(!is_predicate(&arrow));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn detect_constant_programs() {
+        assert_eq!(Some(true), outcome("fn main() { assert!(true); }"));
+        assert_eq!(
+            Some(false),
+            outcome("fn main() { assert!(jet::eq_32(1, 2)); }")
+        );
+        assert_eq!(
+            None,
+            outcome("fn main() { assert!(jet::eq_32(jet::current_index(), 0)); }")
+        );
+        assert_eq!(
+            None,
+            outcome("mod witness { const X: bool = true; } fn main() { assert!(witness::X); }")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Summarize a program in a Markdown report, for documentation or review.
+//!
+//! The report contains the source of the program, its Merkle roots, type and costs,
+//! the jets that it uses and the warnings about it.
+
+use std::collections::BTreeMap;
+
+use simplicity::dag::{DagLike, MaxSharing};
+use simplicity::jet::Jet as _;
+use simplicity::node;
+use simplicityhl::simplicity;
+
+use crate::jet;
+use crate::util::{self, lint, Expression};
+
+/// Return the Markdown report of the program `text`, whose satisfied program is `expression`.
+pub fn report(text: &str, expression: &Expression) -> String {
+    let arrow = expression.arrow();
+    let bounds = expression.bounds();
+    let weight = util::weight(expression);
+    let metrics = util::get_complexity_metrics(expression);
+    let (program_bytes, witness_bytes) = expression.to_vec_with_witness();
+
+    let mut report = String::from("# SimplicityHL program report\n\n");
+    report.push_str("| Property | Value |\n| --- | --- |\n");
+    for (property, value) in [
+        ("CMR", expression.cmr().to_string()),
+        ("IMR", expression.ihr().to_string()),
+        ("AMR", expression.amr().to_string()),
+        ("Type", util::type_arrow(arrow)),
+        ("Program size", format!("{}B", program_bytes.len())),
+        ("Witness size", format!("{}B", witness_bytes.len())),
+        ("Weight", format!("{weight}WU")),
+        ("Virtual size", format!("{}vB", weight.div_ceil(4))),
+        ("Cost", format!("{}mWU", bounds.cost)),
+        (
+            "Maximum memory",
+            format!("{}B", bounds.extra_cells.div_ceil(8)),
+        ),
+        ("Unique nodes", metrics.unique_nodes.to_string()),
+        ("Maximum depth", metrics.max_depth.to_string()),
+    ] {
+        report.push_str(&format!("| {property} | `{value}` |\n"));
+    }
+
+    report.push_str("\n## Jets\n\n");
+    let jets = used_jets(expression);
+    if jets.is_empty() {
+        report.push_str("The program uses no jets.\n");
+    }
+    for (name, jet) in jets {
+        report.push_str(&format!(
+            "- `{name}`: `{}`, costs {} mWU\n",
+            jet::signature(jet),
+            jet.cost()
+        ));
+    }
+
+    report.push_str("\n## Warnings\n\n");
+    let warnings = lint::lint(text);
+    if warnings.is_empty() {
+        report.push_str("There are no warnings.\n");
+    }
+    for warning in warnings {
+        report.push_str(&format!("- Line {}: {}\n", warning.line, warning.message));
+    }
+
+    report.push_str(&format!(
+        "\n## Source\n\n```simplicityhl\n{}\n```\n",
+        text.trim_end()
+    ));
+    report
+}
+
+/// Return the jets that the program uses, ordered by name.
+fn used_jets(expression: &Expression) -> BTreeMap<String, simplicity::jet::Elements> {
+    expression
+        .pre_order_iter::<MaxSharing<_>>()
+        .filter_map(|node| match node.inner() {
+            node::Inner::Jet(jet) => Some((jet.to_string(), *jet)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use simplicityhl::parse::ParseFromStr;
+    use simplicityhl::{Arguments, CompiledProgram, WitnessValues};
+
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn report_program() {
+        let text = "fn main() {\n    assert!(jet::eq_32(jet::current_index(), 0));\n}\n";
+        let compiled = CompiledProgram::new(text, Arguments::default(), false).unwrap();
+        let witness = WitnessValues::parse_from_str(text).unwrap_or_default();
+        let satisfied = compiled.satisfy(witness).unwrap();
+        let report = report(text, satisfied.redeem());
+
+        let cmr = satisfied.redeem().cmr().to_string();
+        assert!(report.contains(&format!("| CMR | `{cmr}` |")));
+        assert!(report.contains("| Type | `1 → 1` |"));
+        assert!(report.contains("- `current_index`: `fn current_index() -> u32`, costs "));
+        assert!(report.contains("- `eq_32`: `fn eq_32(u32, u32) -> bool`, costs "));
+        assert!(report.contains("There are no warnings."));
+        assert!(report.ends_with(&format!("```simplicityhl\n{}```\n", text)));
+    }
+}
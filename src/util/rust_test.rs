@@ -0,0 +1,107 @@
+//! Export the state of the IDE as a Rust test of the runner.
+
+use std::fmt::Write;
+
+use crate::transaction::TxParams;
+
+/// Return a raw string literal that contains `text`.
+fn raw_string_literal(text: &str) -> String {
+    let mut hashes = String::from("#");
+    while text.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+/// Generate a test that runs the program `text` in the transaction environment `params`.
+///
+/// The test asserts that the run succeeds or fails, as given by `expect_success`.
+/// It is written for the test module of `function.rs`.
+pub fn rust_test(text: &str, params: &TxParams, expect_success: bool) -> String {
+    let recipient_address = match &params.recipient_address {
+        Some(address) => format!("Some(\"{address}\".parse().unwrap())"),
+        None => "None".to_string(),
+    };
+    let raw_transaction = match &params.raw_transaction {
+        Some(tx) => format!(
+            "Some(std::sync::Arc::new(\n            crate::transaction::parse_transaction_hex(\"{}\").unwrap(),\n        ))",
+            crate::transaction::transaction_hex(tx)
+        ),
+        None => "None".to_string(),
+    };
+    let mut test = String::new();
+    let _ = write!(
+        test,
+        r#"#[test]
+#[wasm_bindgen_test::wasm_bindgen_test]
+fn reproduce_ide_run() {{
+    use simplicityhl::parse::ParseFromStr as _;
+
+    let text = {text};
+    let arguments = simplicityhl::Arguments::parse_from_str(text).unwrap();
+    let compiled = simplicityhl::CompiledProgram::new(text, arguments, false)
+        .expect("program should compile");
+    let witness = simplicityhl::WitnessValues::parse_from_str(text).unwrap();
+    let satisfied = compiled
+        .satisfy(witness)
+        .expect("program should be satisfied");
+    let params = crate::transaction::TxParams {{
+        txid: "{txid}".parse().unwrap(),
+        vout: {vout},
+        value_in: {value_in},
+        recipient_address: {recipient_address},
+        fee: {fee},
+        lock_time: simplicityhl::elements::LockTime::from_consensus({lock_time}),
+        sequence: simplicityhl::elements::Sequence::from_consensus({sequence}),
+        network: crate::network::Network::from_name("{network}").unwrap(),
+        input_count: {input_count},
+        input_index: {input_index},
+        raw_transaction: {raw_transaction},
+        extra_outputs: crate::transaction::parse_outputs("{extra_outputs}").unwrap(),
+    }};
+    let tx_env = params.tx_env(compiled.commit().cmr());
+    let mut runner = Runner::for_program(&satisfied).expect("program within limits");
+"#,
+        text = raw_string_literal(text),
+        txid = params.txid,
+        vout = params.vout,
+        value_in = params.value_in,
+        fee = params.fee,
+        lock_time = params.lock_time.to_consensus_u32(),
+        sequence = params.sequence.to_consensus_u32(),
+        network = params.network.name(),
+        input_count = params.input_count,
+        input_index = params.input_index,
+        extra_outputs = crate::transaction::outputs_string(&params.extra_outputs),
+    );
+    match expect_success {
+        true => test.push_str("    runner.run(&tx_env).expect(\"program should run\");\n"),
+        false => test.push_str("    assert!(runner.run(&tx_env).is_err());\n"),
+    }
+    test.push_str("}\n");
+    test
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn escape_raw_string() {
+        assert_eq!("r#\"fn main() {}\"#", raw_string_literal("fn main() {}"));
+        assert_eq!(
+            "r##\"let s = \"#\";\"##",
+            raw_string_literal("let s = \"#\";")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn generate_test() {
+        let test = rust_test("fn main() {}", &TxParams::default(), false);
+        assert!(test.starts_with("#[test]\n"));
+        assert!(test.contains("let text = r#\"fn main() {}\"#;"));
+        assert!(test.contains("    assert!(runner.run(&tx_env).is_err());\n}\n"));
+    }
+}
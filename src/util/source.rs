@@ -0,0 +1,1136 @@
+//! Lightweight scanning of `SimplicityHL` source text.
+//!
+//! The scanner does not require the program to parse,
+//! which is the common case while the user is typing.
+//! All positions are byte offsets into the scanned text.
+
+use std::ops::Range;
+
+/// Module of a `SimplicityHL` program that declares named values.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ModuleKind {
+    Witness,
+    Param,
+}
+
+impl ModuleKind {
+    /// Both kinds of modules, in the order in which they usually appear.
+    pub const ALL: [Self; 2] = [Self::Witness, Self::Param];
+
+    /// Name of the module in source code.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Witness => "witness",
+            Self::Param => "param",
+        }
+    }
+}
+
+/// Declaration `const NAME: TYPE = VALUE;` inside a module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Declaration {
+    /// Name of the declared value.
+    pub name: String,
+    /// Position of the name.
+    pub name_span: Range<usize>,
+    /// Position of the entire declaration, from `const` until `;`.
+    pub span: Range<usize>,
+    /// Line of the declaration.
+    ///
+    /// Starts at 1.
+    pub line: usize,
+}
+
+/// Replace the contents of comments by spaces.
+///
+/// The result has the same length as the input,
+/// so byte offsets can be used on both of them.
+pub fn blank_comments(text: &str) -> String {
+    let mut blanked = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                blanked.push_str("  ");
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        blanked.push('\n');
+                        break;
+                    }
+                    blanked.extend(std::iter::repeat(' ').take(c.len_utf8()));
+                }
+            }
+            ('/', Some('*')) => {
+                blanked.push_str("  ");
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '*' && chars.peek() == Some(&'/') {
+                        chars.next();
+                        blanked.push_str("  ");
+                        break;
+                    }
+                    match c {
+                        '\n' => blanked.push('\n'),
+                        _ => blanked.extend(std::iter::repeat(' ').take(c.len_utf8())),
+                    }
+                }
+            }
+            _ => blanked.push(c),
+        }
+    }
+    debug_assert_eq!(text.len(), blanked.len());
+    blanked
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Find the next occurrence of the keyword `word` in `code`, starting at `from`.
+///
+/// Occurrences inside longer words are ignored.
+fn find_word(code: &str, word: &str, from: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut from = from;
+    while let Some(index) = code.get(from..)?.find(word).map(|i| from + i) {
+        let end = index + word.len();
+        let boundary_before = index == 0 || !is_word_byte(bytes[index - 1]);
+        let boundary_after = end == bytes.len() || !is_word_byte(bytes[end]);
+        if boundary_before && boundary_after {
+            return Some(index);
+        }
+        from = end;
+    }
+    None
+}
+
+/// Find the matching closing brace of the opening brace at `open`.
+fn matching_brace(code: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in code.bytes().enumerate().skip(open) {
+        match byte {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Return the position of the module `mod NAME { ... }` of the given `kind`,
+/// including the closing brace.
+///
+/// Return `None` if the module does not exist or if it is not closed.
+pub fn module_span(text: &str, kind: ModuleKind) -> Option<Range<usize>> {
+    let code = blank_comments(text);
+    let mut from = 0;
+    while let Some(start) = find_word(&code, "mod", from) {
+        from = start + 3;
+        let rest = &code[from..];
+        let name_start = from + (rest.len() - rest.trim_start().len());
+        let Some(after_name) = code[name_start..].strip_prefix(kind.name()) else {
+            continue;
+        };
+        let after_name_index = code.len() - after_name.len();
+        if after_name.bytes().next().is_some_and(is_word_byte) {
+            continue;
+        }
+        if !after_name.trim_start().starts_with('{') {
+            continue;
+        }
+        let open = after_name_index + (after_name.len() - after_name.trim_start().len());
+        let close = matching_brace(&code, open)?;
+        return Some(start..close + 1);
+    }
+    None
+}
+
+/// Return the declarations inside the module of the given `kind`, in order of occurrence.
+pub fn declarations(text: &str, kind: ModuleKind) -> Vec<Declaration> {
+    let Some(module) = module_span(text, kind) else {
+        return vec![];
+    };
+    let code = blank_comments(text);
+    let open = module.start + code[module.clone()].find('{').expect("module has brace");
+    let close = module.end - 1;
+    let mut declarations = vec![];
+    let mut from = open + 1;
+
+    while let Some(start) = find_word(&code[..close], "const", from) {
+        let rest = &code[start + 5..close];
+        let name_start = start + 5 + (rest.len() - rest.trim_start().len());
+        let name_len = code[name_start..close]
+            .bytes()
+            .take_while(|byte| is_word_byte(*byte))
+            .count();
+        let end = code[start..close]
+            .find(';')
+            .map_or(close, |index| start + index + 1);
+        if 0 < name_len {
+            declarations.push(Declaration {
+                name: text[name_start..name_start + name_len].to_string(),
+                name_span: name_start..name_start + name_len,
+                span: start..end,
+                line: line_of(text, start),
+            });
+        }
+        from = end.max(start + 5);
+    }
+
+    declarations
+}
+
+/// Return the line of the given byte offset.
+///
+/// Starts at 1.
+pub fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count() + 1
+}
+
+/// Return the position of the word (identifier) that contains the given byte offset.
+pub fn word_at(text: &str, offset: usize) -> Option<Range<usize>> {
+    let bytes = text.as_bytes();
+    let offset = offset.min(bytes.len());
+    let start = bytes[..offset]
+        .iter()
+        .rposition(|byte| !is_word_byte(*byte))
+        .map_or(0, |i| i + 1);
+    let end = bytes[offset..]
+        .iter()
+        .position(|byte| !is_word_byte(*byte))
+        .map_or(bytes.len(), |i| offset + i);
+    match start < end {
+        true => Some(start..end),
+        false => None,
+    }
+}
+
+/// Return the module reference `witness::NAME` or `param::NAME` at the given byte offset.
+pub fn reference_at(text: &str, offset: usize) -> Option<(ModuleKind, &str)> {
+    let word = word_at(text, offset)?;
+    let before = &text[..word.start];
+    let kind = ModuleKind::ALL
+        .into_iter()
+        .find(|kind| before.ends_with(&format!("{}::", kind.name())))?;
+    Some((kind, &text[word]))
+}
+
+/// Return the jet `jet::NAME` at the given byte offset.
+pub fn jet_at(text: &str, offset: usize) -> Option<simplicityhl::simplicity::jet::Elements> {
+    let word = word_at(text, offset)?;
+    if !text[..word.start].ends_with("jet::") {
+        return None;
+    }
+    text[word].parse().ok()
+}
+
+/// Return the start of the partial word that ends at the given byte offset.
+fn partial_word_start(text: &str, offset: usize) -> usize {
+    text.as_bytes()[..offset]
+        .iter()
+        .rposition(|byte| !is_word_byte(*byte))
+        .map_or(0, |i| i + 1)
+}
+
+/// Return the partial jet name that ends at the given byte offset, such as `add_` in `jet::add_`.
+///
+/// The partial name may be empty if the offset directly follows `jet::`.
+pub fn jet_prefix(text: &str, offset: usize) -> Option<Range<usize>> {
+    let offset = offset.min(text.len());
+    let start = partial_word_start(text, offset);
+    text[..start].ends_with("jet::").then_some(start..offset)
+}
+
+/// Return the partial name of a module reference that ends at the given byte offset,
+/// such as `SI` in `witness::SI`.
+///
+/// The partial name may be empty if the offset directly follows `witness::` or `param::`.
+pub fn reference_prefix(text: &str, offset: usize) -> Option<(ModuleKind, Range<usize>)> {
+    let offset = offset.min(text.len());
+    let start = partial_word_start(text, offset);
+    let before = &text[..start];
+    let kind = ModuleKind::ALL
+        .into_iter()
+        .find(|kind| before.ends_with(&format!("{}::", kind.name())))?;
+    Some((kind, start..offset))
+}
+
+/// Return the positions of the text of a tracked call in the program `text`.
+///
+/// Debug symbols store the text of calls without excess whitespace,
+/// so the source is normalized in the same way before searching.
+/// The text may occur multiple times, so there may be multiple positions.
+pub fn call_spans(text: &str, call_text: &str) -> Vec<Range<usize>> {
+    if call_text.is_empty() {
+        return vec![];
+    }
+    let mut normalized = String::with_capacity(text.len());
+    let mut original_offset = Vec::with_capacity(text.len());
+    let mut last_was_space = true;
+    for (offset, c) in text.char_indices() {
+        let is_excess = match c {
+            ' ' => std::mem::replace(&mut last_was_space, true),
+            '\n' => true,
+            _ => {
+                last_was_space = false;
+                false
+            }
+        };
+        if !is_excess {
+            normalized.push(c);
+            original_offset.extend(std::iter::repeat(offset).take(c.len_utf8()));
+        }
+    }
+
+    normalized
+        .match_indices(call_text)
+        .map(|(index, call)| {
+            let last = original_offset[index + call.len() - 1];
+            let last_len = text[last..].chars().next().map_or(0, char::len_utf8);
+            original_offset[index]..last + last_len
+        })
+        .collect()
+}
+
+/// Return the lines on which the text of a tracked call starts.
+///
+/// The text may occur multiple times, so there may be multiple lines.
+pub fn call_lines(text: &str, call_text: &str) -> Vec<usize> {
+    let mut lines = call_spans(text, call_text)
+        .into_iter()
+        .map(|span| line_of(text, span.start))
+        .collect::<Vec<_>>();
+    lines.dedup();
+    lines
+}
+
+/// Replace the module of the given `kind` by `module`, which is the source of a whole module.
+///
+/// If there is no such module, then `module` is inserted at the start of the program.
+pub fn replace_module(text: &str, kind: ModuleKind, module: &str) -> String {
+    let module = module.trim();
+    match module_span(text, kind) {
+        Some(span) => format!("{}{module}{}", &text[..span.start], &text[span.end..]),
+        None => format!("{module}\n\n{text}"),
+    }
+}
+
+/// Remove the module of the given `kind` together with the blank lines that follow it.
+///
+/// If there is no such module, then `text` is returned unchanged.
+pub fn remove_module(text: &str, kind: ModuleKind) -> String {
+    match module_span(text, kind) {
+        Some(span) => {
+            let rest = text[span.end..].trim_start_matches(char::is_whitespace);
+            format!("{}{rest}", &text[..span.start])
+        }
+        None => text.to_string(),
+    }
+}
+
+/// Return the given 1-based line of `text` together with its neighboring lines,
+/// prefixed with line numbers and with the given line marked by `>`.
+pub fn line_context(text: &str, line: usize) -> String {
+    let first = line.saturating_sub(1).max(1);
+    let last = line + 1;
+    let width = last.to_string().len();
+    text.lines()
+        .enumerate()
+        .map(|(index, content)| (index + 1, content))
+        .filter(|(number, _)| first <= *number && *number <= last)
+        .map(|(number, content)| {
+            let marker = if number == line { '>' } else { ' ' };
+            format!("{marker} {number:>width$} | {content}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Kind of a definition in the outline of a program.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    TypeAlias,
+    Module,
+    /// Declaration inside a module.
+    Constant,
+}
+
+impl SymbolKind {
+    /// Keyword that starts the definition in source code.
+    pub const fn keyword(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::TypeAlias => "type",
+            Self::Module => "mod",
+            Self::Constant => "const",
+        }
+    }
+}
+
+/// Definition in the outline of a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Line of the definition.
+    ///
+    /// Starts at 1.
+    pub line: usize,
+}
+
+/// Return the top-level definitions of the program `text`, in order of occurrence.
+///
+/// The declarations inside the witness and parameter modules follow their module.
+pub fn outline(text: &str) -> Vec<Symbol> {
+    let code = blank_comments(text);
+    let bytes = code.as_bytes();
+    let mut symbols = vec![];
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            byte if depth == 0 && is_word_byte(byte) => {
+                let end = index
+                    + bytes[index..]
+                        .iter()
+                        .take_while(|b| is_word_byte(**b))
+                        .count();
+                let kind = [
+                    SymbolKind::Function,
+                    SymbolKind::TypeAlias,
+                    SymbolKind::Module,
+                ]
+                .into_iter()
+                .find(|kind| kind.keyword() == &code[index..end]);
+                if let Some(kind) = kind {
+                    let rest = &code[end..];
+                    let name_start = end + (rest.len() - rest.trim_start().len());
+                    let name_len = bytes[name_start..]
+                        .iter()
+                        .take_while(|byte| is_word_byte(**byte))
+                        .count();
+                    if 0 < name_len {
+                        let name = &text[name_start..name_start + name_len];
+                        symbols.push(Symbol {
+                            kind,
+                            name: name.to_string(),
+                            line: line_of(text, index),
+                        });
+                        let module = ModuleKind::ALL.into_iter().find(|m| m.name() == name);
+                        if let (SymbolKind::Module, Some(module)) = (kind, module) {
+                            symbols.extend(declarations(text, module).into_iter().map(
+                                |declaration| Symbol {
+                                    kind: SymbolKind::Constant,
+                                    name: declaration.name,
+                                    line: declaration.line,
+                                },
+                            ));
+                        }
+                    }
+                }
+                index = end;
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    symbols
+}
+
+/// Return the declaration of the module reference at the given byte offset.
+pub fn definition_at(text: &str, offset: usize) -> Option<Declaration> {
+    let (kind, name) = reference_at(text, offset)?;
+    declarations(text, kind)
+        .into_iter()
+        .rev() // later declarations shadow earlier ones
+        .find(|declaration| declaration.name == name)
+}
+
+/// Return the names that are declared more than once in the module of the given `kind`,
+/// together with the lines of their declarations, in order of first declaration.
+pub fn duplicate_declarations(text: &str, kind: ModuleKind) -> Vec<(String, Vec<usize>)> {
+    let mut lines_by_name: Vec<(String, Vec<usize>)> = vec![];
+    for declaration in declarations(text, kind) {
+        match lines_by_name
+            .iter_mut()
+            .find(|(name, _)| *name == declaration.name)
+        {
+            Some((_, lines)) => lines.push(declaration.line),
+            None => lines_by_name.push((declaration.name, vec![declaration.line])),
+        }
+    }
+    lines_by_name.retain(|(_, lines)| 1 < lines.len());
+    lines_by_name
+}
+
+/// Return the positions of all references `witness::NAME` or `param::NAME`
+/// outside of the module declarations, in order of occurrence.
+pub fn references(text: &str, kind: ModuleKind) -> Vec<(String, Range<usize>)> {
+    let code = blank_comments(text);
+    let module = module_span(text, kind).unwrap_or(0..0);
+    let prefix = format!("{}::", kind.name());
+    let mut references = vec![];
+    let mut from = 0;
+
+    while let Some(start) = find_word(&code, kind.name(), from) {
+        from = start + kind.name().len();
+        if module.contains(&start) || !code[start..].starts_with(&prefix) {
+            continue;
+        }
+        let name_start = start + prefix.len();
+        let name_len = code[name_start..]
+            .bytes()
+            .take_while(|byte| is_word_byte(*byte))
+            .count();
+        if 0 < name_len {
+            let name_span = name_start..name_start + name_len;
+            references.push((text[name_span.clone()].to_string(), name_span));
+        }
+    }
+
+    references
+}
+
+/// Reorder the declarations of the witness module in the given `order` of names.
+///
+/// Declarations whose names are not in the `order` are moved to the end.
+/// Comments in front of a declaration move together with the declaration.
+/// The rest of the program is left untouched.
+///
+/// Return `None` if there is no witness module.
+pub fn normalize_witness_module(text: &str, order: &[String]) -> Option<String> {
+    let module = module_span(text, ModuleKind::Witness)?;
+    let declarations = declarations(text, ModuleKind::Witness);
+    let open = module.start + text[module.clone()].find('{')?;
+    let close = module.end - 1;
+
+    let mut previous_end = open + 1;
+    let mut entries = vec![];
+    for declaration in &declarations {
+        let entry = text[previous_end..declaration.span.end].trim();
+        entries.push((declaration.name.as_str(), entry));
+        previous_end = declaration.span.end;
+    }
+    let trailing = text[previous_end..close].trim();
+
+    let position = |name: &str| {
+        order
+            .iter()
+            .position(|ordered| ordered == name)
+            .unwrap_or(usize::MAX)
+    };
+    entries.sort_by_key(|(name, _)| position(name));
+
+    let mut body = entries
+        .iter()
+        .map(|(_, entry)| format!("    {entry}\n"))
+        .collect::<String>();
+    if !trailing.is_empty() {
+        body.push_str(&format!("    {trailing}\n"));
+    }
+    let module_text = match body.is_empty() {
+        true => "mod witness {}".to_string(),
+        false => format!("mod witness {{\n{body}}}"),
+    };
+
+    Some(format!(
+        "{}{module_text}{}",
+        &text[..module.start],
+        &text[module.end..]
+    ))
+}
+
+/// Assign `value` to the declaration `name` in the module of the given `kind`.
+///
+/// If there is no such declaration, then a declaration of type `ty` is added.
+/// If there is no such module, then the module is added at the top of the program.
+pub fn set_declaration(text: &str, kind: ModuleKind, name: &str, ty: &str, value: &str) -> String {
+    let code = blank_comments(text);
+    if let Some(declaration) = declarations(text, kind)
+        .into_iter()
+        .find(|declaration| declaration.name == name)
+    {
+        if let Some(equals) = code[declaration.span.clone()].find('=') {
+            let value_start = declaration.span.start + equals + 1;
+            let value_end = match code[..declaration.span.end].ends_with(';') {
+                true => declaration.span.end - 1,
+                false => declaration.span.end,
+            };
+            return format!("{} {value}{}", &text[..value_start], &text[value_end..]);
+        }
+    }
+
+    let line = format!("    const {name}: {ty} = {value};\n");
+    match module_span(text, kind) {
+        Some(module) => {
+            let close = module.end - 1;
+            let before = text[..close].trim_end_matches([' ', '\t']);
+            let separator = match before.ends_with('\n') {
+                true => "",
+                false => "\n",
+            };
+            format!("{before}{separator}{line}{}", &text[close..])
+        }
+        None => format!("mod {} {{\n{line}}}\n\n{text}", kind.name()),
+    }
+}
+
+/// Return the position of the line comments directly above the declaration `name`
+/// in the module of the given `kind`, from the start of the first comment line
+/// until the start of the line of the declaration.
+///
+/// Return `None` if there is no such declaration or if it does not start its line.
+fn note_span(text: &str, kind: ModuleKind, name: &str) -> Option<Range<usize>> {
+    let declaration = declarations(text, kind)
+        .into_iter()
+        .find(|declaration| declaration.name == name)?;
+    let line_start = text[..declaration.span.start]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    if !text[line_start..declaration.span.start].trim().is_empty() {
+        return None;
+    }
+    let mut start = line_start;
+    while 0 < start {
+        let previous = text[..start - 1].rfind('\n').map_or(0, |i| i + 1);
+        if !text[previous..start].trim_start().starts_with("//") {
+            break;
+        }
+        start = previous;
+    }
+    Some(start..line_start)
+}
+
+/// Return the note of the declaration `name` in the module of the given `kind`.
+///
+/// The note consists of the line comments directly above the declaration.
+/// Return `None` if the declaration has no note.
+pub fn declaration_note(text: &str, kind: ModuleKind, name: &str) -> Option<String> {
+    let span = note_span(text, kind, name)?;
+    let note = text[span]
+        .lines()
+        .map(|line| {
+            let comment = line.trim_start().trim_start_matches('/');
+            comment.strip_prefix(' ').unwrap_or(comment).trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!note.is_empty()).then_some(note)
+}
+
+/// Replace the note of the declaration `name` in the module of the given `kind`.
+///
+/// The note is written as a line comment directly above the declaration,
+/// so it moves together with the declaration when the module is normalized.
+/// An empty note removes the comment.
+/// The text stays unchanged if there is no such declaration.
+pub fn set_declaration_note(text: &str, kind: ModuleKind, name: &str, note: &str) -> String {
+    let Some(span) = note_span(text, kind, name) else {
+        return text.to_string();
+    };
+    let line = &text[span.end..];
+    let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+    let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+    let comment = match note.is_empty() {
+        true => String::new(),
+        false => format!("{indent}// {note}\n"),
+    };
+    format!("{}{comment}{}", &text[..span.start], &text[span.end..])
+}
+
+/// Return the positions of block comments `/* mod NAME { ... } */` that contain a module,
+/// including the comment markers.
+fn commented_modules(text: &str) -> Vec<Range<usize>> {
+    let mut commented = vec![];
+    let mut from = 0;
+    while let Some(index) = text[from..].find("/*") {
+        let start = from + index;
+        let Some(length) = text[start + 2..].find("*/") else {
+            break;
+        };
+        let end = start + 2 + length + 2;
+        let inner = text[start + 2..end - 2].trim_start();
+        if ModuleKind::ALL
+            .iter()
+            .any(|kind| inner.starts_with(&format!("mod {}", kind.name())))
+        {
+            commented.push(start..end);
+        }
+        from = end;
+    }
+    commented
+}
+
+/// Comment out the witness and parameter modules with block comments,
+/// or remove these comments if there are no active modules.
+///
+/// Modules that contain block comments cannot be commented out and are left untouched.
+pub fn toggle_module_comments(text: &str) -> String {
+    let mut active: Vec<Range<usize>> = ModuleKind::ALL
+        .iter()
+        .filter_map(|kind| module_span(text, *kind))
+        .collect();
+    let mut output = text.to_string();
+
+    if active.is_empty() {
+        for comment in commented_modules(text).into_iter().rev() {
+            let inner = &text[comment.start + 2..comment.end - 2];
+            let inner = inner.strip_prefix(' ').unwrap_or(inner);
+            let inner = inner.strip_suffix(' ').unwrap_or(inner);
+            output.replace_range(comment, inner);
+        }
+        return output;
+    }
+
+    active.sort_by_key(|module| module.start);
+    for module in active.into_iter().rev() {
+        if text[module.clone()].contains("*/") {
+            continue;
+        }
+        output.insert_str(module.end, " */");
+        output.insert_str(module.start, "/* ");
+    }
+    output
+}
+
+/// Name that the main function of the program gets inside a harness.
+pub const HARNESS_MAIN: &str = "original_main";
+
+/// Return the byte range of the name of the top-level function `main` of the program `text`.
+fn main_name_span(text: &str) -> Option<Range<usize>> {
+    let code = blank_comments(text);
+    let bytes = code.as_bytes();
+    let mut depth = 0usize;
+    let mut index = 0;
+    let mut after_fn = false;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            byte if depth == 0 && is_word_byte(byte) => {
+                let end = index
+                    + bytes[index..]
+                        .iter()
+                        .take_while(|b| is_word_byte(**b))
+                        .count();
+                let word = &code[index..end];
+                if after_fn && word == "main" {
+                    return Some(index..end);
+                }
+                after_fn = word == "fn";
+                index = end;
+                continue;
+            }
+            byte if byte.is_ascii_whitespace() => {}
+            _ => after_fn = false,
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Wrap the expression `selection` of the program `text` in a program
+/// that asserts that the expression is true and prints its value.
+///
+/// The main function of the program is renamed to [`HARNESS_MAIN`],
+/// so the functions, type aliases and modules of the program stay available to the selection.
+pub fn selection_harness(text: &str, selection: &str) -> String {
+    let mut harness = text.to_string();
+    if let Some(span) = main_name_span(text) {
+        harness.replace_range(span, HARNESS_MAIN);
+    }
+    if !harness.is_empty() && !harness.ends_with('\n') {
+        harness.push('\n');
+    }
+    harness.push_str(&format!(
+        "\nfn main() {{\n    assert!(dbg!({}));\n}}\n",
+        selection.trim()
+    ));
+    harness
+}
+
+/// Default name of the file that a program is downloaded to.
+pub const DEFAULT_FILE_NAME: &str = "program.simf";
+
+/// Return the name of the file that the program `text` is downloaded to.
+///
+/// A comment on the first line names the file; otherwise the default name is used.
+pub fn file_name(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or_default().trim();
+    let Some(comment) = first_line.strip_prefix("//") else {
+        return DEFAULT_FILE_NAME.to_string();
+    };
+    let name = comment
+        .trim()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(6)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    match name.is_empty() {
+        true => DEFAULT_FILE_NAME.to_string(),
+        false => format!("{name}.simf"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = r#"mod witness {
+    // const COMMENTED: u8 = 0;
+    const SIG: Signature = 0x00;
+    const X: (u8, u8) = (1, 2);
+}
+
+mod param {
+    const PK: Pubkey = 0x01; /* const HIDDEN: u8 = 3; */
+}
+
+fn main() {
+    jet::bip_0340_verify((param::PK, msg), witness::SIG);
+}"#;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn blank_comments_preserves_offsets() {
+        let blanked = blank_comments(PROGRAM);
+        assert_eq!(PROGRAM.len(), blanked.len());
+        assert!(!blanked.contains("COMMENTED"));
+        assert!(!blanked.contains("HIDDEN"));
+        assert!(blanked.contains("const PK"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_declarations() {
+        let witness = declarations(PROGRAM, ModuleKind::Witness);
+        let names = witness.iter().map(|d| d.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(vec!["SIG", "X"], names);
+        assert_eq!(3, witness[0].line);
+        assert_eq!(
+            "const X: (u8, u8) = (1, 2);",
+            &PROGRAM[witness[1].span.clone()]
+        );
+
+        let param = declarations(PROGRAM, ModuleKind::Param);
+        assert_eq!(1, param.len());
+        assert_eq!("PK", &PROGRAM[param[0].name_span.clone()]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn missing_module() {
+        assert!(module_span("fn main() {}", ModuleKind::Witness).is_none());
+        assert!(module_span("mod witness {", ModuleKind::Witness).is_none());
+        assert!(module_span("mod witnesses {}", ModuleKind::Witness).is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn go_to_definition() {
+        let usage = PROGRAM.find("witness::SIG").unwrap() + "witness::".len() + 1;
+        let definition = definition_at(PROGRAM, usage).unwrap();
+        assert_eq!("SIG", definition.name);
+
+        let usage = PROGRAM.find("param::PK").unwrap() + "param::".len();
+        let definition = definition_at(PROGRAM, usage).unwrap();
+        assert_eq!("PK", definition.name);
+
+        let not_a_reference = PROGRAM.find("msg").unwrap();
+        assert!(definition_at(PROGRAM, not_a_reference).is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn replace_witness_module() {
+        let text = "mod witness {\n    const A: u8 = 1;\n}\n\nfn main() {}";
+        let module = "mod witness { const A: u8 = 2; }";
+        assert_eq!(
+            "mod witness { const A: u8 = 2; }\n\nfn main() {}",
+            replace_module(text, ModuleKind::Witness, module)
+        );
+        assert_eq!(
+            "mod witness { const A: u8 = 2; }\n\nfn main() {}",
+            replace_module("fn main() {}", ModuleKind::Witness, module)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn remove_witness_module() {
+        let text = "mod param {\n    const P: u8 = 1;\n}\n\nmod witness {\n    const A: u8 = 1;\n}\n\nfn main() {}";
+        assert_eq!(
+            "mod param {\n    const P: u8 = 1;\n}\n\nfn main() {}",
+            remove_module(text, ModuleKind::Witness)
+        );
+        assert_eq!(
+            "fn main() {}",
+            remove_module("fn main() {}", ModuleKind::Witness)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn show_line_context() {
+        let text = "fn main() {\n    let a: u8 = 1;\n    assert!(false);\n}";
+        assert_eq!(
+            "  2 |     let a: u8 = 1;\n> 3 |     assert!(false);\n  4 | }",
+            line_context(text, 3)
+        );
+        assert_eq!(
+            "> 1 | fn main() {\n  2 |     let a: u8 = 1;",
+            line_context(text, 1)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_call_lines() {
+        let text = "fn main() {\n    assert!(jet::eq_8(\n        1, 2));\n    assert!(jet::eq_8(1, 2));\n}";
+        assert_eq!(vec![2], call_lines(text, "jet::eq_8( 1, 2)"));
+        assert_eq!(vec![4], call_lines(text, "jet::eq_8(1, 2)"));
+        assert_eq!(vec![2, 4], call_lines(text, "assert!(jet::eq_8("));
+        assert!(call_lines(text, "jet::eq_16").is_empty());
+
+        let spans = call_spans(text, "jet::eq_8( 1, 2)");
+        assert_eq!(1, spans.len());
+        assert_eq!("jet::eq_8(\n        1, 2)", &text[spans[0].clone()]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_jet() {
+        use simplicityhl::simplicity::jet::Elements;
+
+        let usage = PROGRAM.find("bip_0340_verify").unwrap() + 3;
+        assert_eq!(Some(Elements::Bip0340Verify), jet_at(PROGRAM, usage));
+        assert_eq!(None, jet_at(PROGRAM, PROGRAM.find("SIG").unwrap()));
+        assert_eq!(None, jet_at("jet::no_such_jet", 6));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_jet_prefix() {
+        let text = "let x = jet::add_32(a, b);";
+        let end = text.find('(').unwrap();
+        assert_eq!(Some(13..end), jet_prefix(text, end));
+        assert_eq!(Some(13..16), jet_prefix(text, 16));
+        assert_eq!(Some(13..13), jet_prefix(text, 13));
+        assert_eq!(None, jet_prefix(text, 11));
+        assert_eq!(None, jet_prefix(text, 5));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_reference_prefix() {
+        let text = "(witness::SI, param::)";
+        assert_eq!(
+            Some((ModuleKind::Witness, 10..12)),
+            reference_prefix(text, 12)
+        );
+        assert_eq!(
+            Some((ModuleKind::Param, 21..21)),
+            reference_prefix(text, 21)
+        );
+        assert_eq!(None, reference_prefix(text, 13));
+        assert_eq!(None, jet_prefix(text, 12));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn normalize_witness_order() {
+        let text = r#"mod witness {
+    const UNUSED: u8 = 0;
+    // Signature of Alice
+    const SIG: Signature = 0x00;
+    const X: u8 = 1;
+}
+
+fn main() {
+    let x: u8 = witness::X;
+    jet::bip_0340_verify((pk, msg), witness::SIG);
+}"#;
+        let expected = r#"mod witness {
+    const X: u8 = 1;
+    // Signature of Alice
+    const SIG: Signature = 0x00;
+    const UNUSED: u8 = 0;
+}
+
+fn main() {
+    let x: u8 = witness::X;
+    jet::bip_0340_verify((pk, msg), witness::SIG);
+}"#;
+        let order = ["X".to_string(), "SIG".to_string()];
+        let normalized = normalize_witness_module(text, &order).unwrap();
+        assert_eq!(expected, normalized);
+        assert_eq!(
+            Some(normalized.clone()),
+            normalize_witness_module(&normalized, &order)
+        );
+        assert!(normalize_witness_module("fn main() {}", &order).is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn set_declaration_value() {
+        let text = "mod witness {\n    const A: u8 = 1;\n}\n\nfn main() {}";
+        assert_eq!(
+            "mod witness {\n    const A: u8 = 0x02;\n}\n\nfn main() {}",
+            set_declaration(text, ModuleKind::Witness, "A", "u8", "0x02"),
+        );
+        assert_eq!(
+            "mod witness {\n    const A: u8 = 1;\n    const B: bool = true;\n}\n\nfn main() {}",
+            set_declaration(text, ModuleKind::Witness, "B", "bool", "true"),
+        );
+        assert_eq!(
+            "mod witness {\n    const B: bool = false;\n}\n\nfn main() {}",
+            set_declaration("fn main() {}", ModuleKind::Witness, "B", "bool", "false"),
+        );
+        assert_eq!(
+            "mod witness {\n    const B: bool = false;\n}",
+            set_declaration("mod witness {}", ModuleKind::Witness, "B", "bool", "false"),
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn declaration_notes() {
+        let text = "mod witness {\n    // Signature\n    // of Alice\n    const SIG: u8 = 0;\n    const X: u8 = 1;\n}\n\nfn main() {\n    let x: u8 = witness::X;\n}";
+        let kind = ModuleKind::Witness;
+        assert_eq!(
+            Some("Signature of Alice".to_string()),
+            declaration_note(text, kind, "SIG")
+        );
+        assert_eq!(None, declaration_note(text, kind, "X"));
+
+        let noted = set_declaration_note(text, kind, "X", "Counter");
+        assert_eq!(
+            Some("Counter".to_string()),
+            declaration_note(&noted, kind, "X")
+        );
+        let renamed = set_declaration_note(&noted, kind, "SIG", "Alice's signature");
+        assert!(renamed.contains("    // Alice's signature\n    const SIG"));
+        let removed = set_declaration_note(&renamed, kind, "SIG", "");
+        assert_eq!(None, declaration_note(&removed, kind, "SIG"));
+        assert_eq!(noted, set_declaration_note(&noted, kind, "MISSING", "note"));
+
+        // Notes move together with their declarations
+        let normalized = normalize_witness_module(&renamed, &["X".to_string()]).unwrap();
+        assert_eq!(
+            Some("Counter".to_string()),
+            declaration_note(&normalized, kind, "X")
+        );
+        assert_eq!(
+            Some("Alice's signature".to_string()),
+            declaration_note(&normalized, kind, "SIG")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn toggle_modules() {
+        let text = "mod witness {\n    const A: u8 = 1; // one\n}\n\nmod param {}\n\nfn main() {}";
+        let commented = toggle_module_comments(text);
+        assert_eq!(
+            "/* mod witness {\n    const A: u8 = 1; // one\n} */\n\n/* mod param {} */\n\nfn main() {}",
+            commented,
+        );
+        assert!(module_span(&commented, ModuleKind::Witness).is_none());
+        assert_eq!(text, toggle_module_comments(&commented));
+        assert_eq!("fn main() {}", toggle_module_comments("fn main() {}"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_duplicates() {
+        let text =
+            "mod witness {\n    const A: u8 = 1;\n    const B: u8 = 2;\n    const A: u8 = 3;\n}";
+        assert_eq!(
+            vec![("A".to_string(), vec![2, 4])],
+            duplicate_declarations(text, ModuleKind::Witness),
+        );
+        assert!(duplicate_declarations(text, ModuleKind::Param).is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_outline() {
+        let symbols = outline(PROGRAM);
+        let entries = symbols
+            .iter()
+            .map(|symbol| (symbol.kind, symbol.name.as_str(), symbol.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (SymbolKind::Module, "witness", 1),
+                (SymbolKind::Constant, "SIG", 3),
+                (SymbolKind::Constant, "X", 4),
+                (SymbolKind::Module, "param", 7),
+                (SymbolKind::Constant, "PK", 8),
+                (SymbolKind::Function, "main", 11),
+            ],
+            entries,
+        );
+
+        let text = "type Pair = (u8, u8);\n/* fn hidden() {} */\nfn check(x: u8) {\n    let fn_x: u8 = x;\n}\nfn main(";
+        let names = outline(text)
+            .into_iter()
+            .map(|symbol| (symbol.kind, symbol.name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (SymbolKind::TypeAlias, "Pair".to_string()),
+                (SymbolKind::Function, "check".to_string()),
+                (SymbolKind::Function, "main".to_string()),
+            ],
+            names,
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn wrap_selection() {
+        let text = "// fn main() in a comment\nfn check(x: u8) -> bool {\n    jet::eq_8(x, 1)\n}\n\nfn main() {\n    assert!(check(1));\n}";
+        let expected = "// fn main() in a comment\nfn check(x: u8) -> bool {\n    jet::eq_8(x, 1)\n}\n\nfn original_main() {\n    assert!(check(1));\n}\n\nfn main() {\n    assert!(dbg!(check(2)));\n}\n";
+        assert_eq!(expected, selection_harness(text, " check(2) "));
+        let compiled = simplicityhl::CompiledProgram::new(
+            selection_harness(text, "check(1)"),
+            simplicityhl::Arguments::default(),
+            false,
+        );
+        assert!(compiled.is_ok());
+        assert_eq!(
+            "\nfn main() {\n    assert!(dbg!(true));\n}\n",
+            selection_harness("", "true")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn name_program_file() {
+        assert_eq!("program.simf", file_name("fn main() {}"));
+        assert_eq!("program.simf", file_name(""));
+        assert_eq!("program.simf", file_name("// ✍️\nfn main() {}"));
+        assert_eq!(
+            "pay-to-public-key.simf",
+            file_name("// Pay to public key!\nfn main() {}")
+        );
+    }
+}
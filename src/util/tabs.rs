@@ -0,0 +1,106 @@
+//! Bookkeeping of the program tabs, each of which is a name with a program text.
+
+use std::cmp::Ordering;
+
+/// Return the first name of the form "Program N" that none of the `tabs` has.
+pub fn new_tab_name(tabs: &[(String, String)]) -> String {
+    // One more number than there are tabs, so one of the names is free
+    (1..=tabs.len() + 1)
+        .map(|number| format!("Program {number}"))
+        .find(|name| tabs.iter().all(|(tab_name, _)| tab_name != name))
+        .expect("one of the names is free")
+}
+
+/// Return the index of the active tab after the tab at `index` is closed,
+/// given the index of the active tab and the number of tabs before closing.
+///
+/// Closing the active tab activates the next tab, or the previous tab if it was the last one.
+pub fn active_after_close(active: usize, index: usize, count: usize) -> usize {
+    match index.cmp(&active) {
+        Ordering::Less => active - 1,
+        Ordering::Equal => index.min(count.saturating_sub(2)),
+        Ordering::Greater => active,
+    }
+}
+
+/// Put `text` into a tab and return the index of that tab.
+///
+/// A tab that already holds `text` is reused, and so is a blank active tab.
+/// Otherwise, a new tab is added.
+pub fn open_text(tabs: &mut Vec<(String, String)>, active: usize, text: String) -> usize {
+    if let Some(index) = tabs.iter().position(|(_, tab_text)| *tab_text == text) {
+        return index;
+    }
+    match tabs.get_mut(active) {
+        Some((_, tab_text)) if tab_text.trim().is_empty() => {
+            *tab_text = text;
+            active
+        }
+        _ => {
+            tabs.push((new_tab_name(tabs), text));
+            tabs.len() - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(names: &[&str]) -> Vec<(String, String)> {
+        names
+            .iter()
+            .map(|&name| (name.to_string(), format!("// {name}")))
+            .collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn name_new_tabs() {
+        assert_eq!(new_tab_name(&[]), "Program 1");
+        assert_eq!(new_tab_name(&tabs(&["Program 1"])), "Program 2");
+        assert_eq!(
+            new_tab_name(&tabs(&["Program 2", "Program 3"])),
+            "Program 1"
+        );
+        assert_eq!(
+            new_tab_name(&tabs(&["Program 1", "Program 3"])),
+            "Program 2"
+        );
+        assert_eq!(new_tab_name(&tabs(&["Vault", "Program 1"])), "Program 2");
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn close_tabs() {
+        // Closing a tab before the active tab keeps the same program active
+        assert_eq!(active_after_close(2, 0, 3), 1);
+        // Closing a tab after the active tab changes nothing
+        assert_eq!(active_after_close(0, 2, 3), 0);
+        // Closing the active tab activates the next tab
+        assert_eq!(active_after_close(1, 1, 3), 1);
+        // or the previous tab, if the active tab was the last one
+        assert_eq!(active_after_close(2, 2, 3), 1);
+        assert_eq!(active_after_close(0, 0, 2), 0);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn open_texts() {
+        let mut open = tabs(&["Program 1", "Program 2"]);
+        assert_eq!(open_text(&mut open, 0, "// Program 2".to_string()), 1);
+        assert_eq!(open.len(), 2);
+        assert_eq!(open_text(&mut open, 0, "fn main() {}".to_string()), 2);
+        assert_eq!(
+            open[2],
+            ("Program 3".to_string(), "fn main() {}".to_string())
+        );
+
+        let mut open = vec![("Program 1".to_string(), " \n".to_string())];
+        assert_eq!(open_text(&mut open, 0, "fn main() {}".to_string()), 0);
+        assert_eq!(
+            open,
+            vec![("Program 1".to_string(), "fn main() {}".to_string())]
+        );
+    }
+}
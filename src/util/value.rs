@@ -0,0 +1,121 @@
+//! Inspect `SimplicityHL` values independently of any program.
+
+use hex_conservative::{DisplayHex, FromHex};
+use itertools::Itertools;
+use simplicity::{BitCollector, BitIter};
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::simplicity;
+use simplicityhl::types::StructuralType;
+use simplicityhl::value::StructuralValue;
+use simplicityhl::{ResolvedType, Value};
+
+/// Representations of a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueInfo {
+    /// `SimplicityHL` value.
+    pub value: String,
+    /// `SimplicityHL` type.
+    pub ty: String,
+    /// Simplicity value.
+    pub simplicity_value: String,
+    /// Simplicity type.
+    pub simplicity_type: String,
+    /// Compact bit encoding, as used in witness data.
+    pub compact_bits: String,
+    /// Padded bit encoding, as used in the Bit Machine.
+    pub padded_bits: String,
+    /// Compact bit encoding, filled with zeros to whole bytes, in hex.
+    pub hex: String,
+}
+
+/// Format bits as a string of zeros and ones, grouped into bytes.
+fn format_bits(bits: impl Iterator<Item = bool>) -> String {
+    bits.chunks(8)
+        .into_iter()
+        .map(|byte| {
+            byte.map(|bit| if bit { '1' } else { '0' })
+                .collect::<String>()
+        })
+        .join(" ")
+}
+
+/// Format the compact bit encoding of a Simplicity value, grouped into bytes.
+pub fn compact_bits(value: &simplicity::Value) -> String {
+    format_bits(value.iter_compact())
+}
+
+/// Parse the value `value` of type `ty` and return its representations.
+pub fn inspect_value(ty: &str, value: &str) -> Result<ValueInfo, String> {
+    let ty = ResolvedType::parse_from_str(ty).map_err(|e| e.to_string())?;
+    let value = Value::parse_from_str(value, &ty).map_err(|e| e.to_string())?;
+    Ok(describe(&value, &ty))
+}
+
+/// Decode a value of type `ty` from the hex of its compact bit encoding
+/// and return its representations.
+pub fn decode_hex(ty: &str, hex: &str) -> Result<ValueInfo, String> {
+    let ty = ResolvedType::parse_from_str(ty).map_err(|e| e.to_string())?;
+    let hex = hex.trim();
+    let bytes = Vec::<u8>::from_hex(hex.strip_prefix("0x").unwrap_or(hex))
+        .map_err(|e| format!("Invalid hex: {e}"))?;
+    let mut bits = BitIter::new(bytes.into_iter());
+    let structural =
+        simplicity::Value::from_compact_bits(&mut bits, StructuralType::from(&ty).as_ref())
+            .map_err(|e| format!("Too few bytes for the type: {e}"))?;
+    bits.close()
+        .map_err(|e| format!("Too many bytes for the type: {e}"))?;
+    let value = Value::reconstruct(&StructuralValue::from(structural), &ty)
+        .ok_or_else(|| "The bits do not form a value of the type".to_string())?;
+    Ok(describe(&value, &ty))
+}
+
+fn describe(value: &Value, ty: &ResolvedType) -> ValueInfo {
+    let structural = simplicity::Value::from(StructuralValue::from(value));
+    let (bytes, _) = structural.iter_compact().collect_bits();
+    ValueInfo {
+        value: value.to_string(),
+        ty: ty.to_string(),
+        simplicity_value: structural.to_string(),
+        simplicity_type: structural.ty().to_string(),
+        compact_bits: compact_bits(&structural),
+        padded_bits: format_bits(structural.iter_padded()),
+        hex: format!("0x{}", bytes.as_hex()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn inspect_values() {
+        let info = inspect_value("(u8, bool)", "(5, true)").unwrap();
+        assert_eq!("2^8 × 2", info.simplicity_type);
+        assert_eq!("00000101 1", info.compact_bits);
+
+        let info = inspect_value("Either<u8, bool>", "Right(true)").unwrap();
+        assert_eq!("11", info.compact_bits);
+        assert_eq!("10000000 1", info.padded_bits);
+
+        assert!(inspect_value("u8", "256").is_err());
+        assert!(inspect_value("u7", "0").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn convert_hex() {
+        let info = inspect_value("(u8, bool)", "(5, true)").unwrap();
+        assert_eq!("0x0580", info.hex);
+        let decoded = decode_hex("(u8, bool)", &info.hex).unwrap();
+        assert_eq!(info, decoded);
+
+        let info = decode_hex("Option<u16>", "800100").unwrap();
+        assert_eq!("Some(2)", info.value);
+
+        assert!(decode_hex("u16", "0x01").is_err());
+        assert!(decode_hex("u8", "0x0102").is_err());
+        assert!(decode_hex("bool", "0x81").is_err());
+        assert!(decode_hex("u8", "0xzz").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! Walk over the expressions of an analyzed program.
+
+use simplicityhl::ast::{
+    Assignment, Call, CallName, Expression, ExpressionInner, SingleExpressionInner, Statement,
+};
+use simplicityhl::either::Either;
+use simplicityhl::str::WitnessName;
+
+/// Part of the program that the walk stops at.
+#[derive(Copy, Clone, Debug)]
+pub enum Visit<'a> {
+    /// `let` statement, before its expression is walked.
+    Assignment(&'a Assignment),
+    /// Call, after its arguments and before the body of the called function are walked.
+    Call(&'a Call),
+    /// Use of a witness.
+    Witness(&'a WitnessName),
+}
+
+/// Walk over `expression` and every expression inside it, and call `visit` for each
+/// `let` statement, each call and each use of a witness.
+///
+/// Custom functions are inlined into each of their calls,
+/// so their bodies are walked once per call.
+/// Expressions are walked in the order in which the compiler translates them.
+pub fn walk<'a>(expression: &'a Expression, visit: &mut impl FnMut(Visit<'a>)) {
+    match expression.inner() {
+        ExpressionInner::Single(single) => walk_single(single.inner(), visit),
+        ExpressionInner::Block(statements, result) => {
+            for statement in statements.iter() {
+                match statement {
+                    Statement::Assignment(assignment) => {
+                        visit(Visit::Assignment(assignment));
+                        walk(assignment.expression(), visit);
+                    }
+                    Statement::Expression(expression) => walk(expression, visit),
+                }
+            }
+            if let Some(result) = result {
+                walk(result, visit);
+            }
+        }
+    }
+}
+
+fn walk_single<'a>(single: &'a SingleExpressionInner, visit: &mut impl FnMut(Visit<'a>)) {
+    match single {
+        SingleExpressionInner::Witness(name) => visit(Visit::Witness(name)),
+        SingleExpressionInner::Constant(..)
+        | SingleExpressionInner::Parameter(..)
+        | SingleExpressionInner::Variable(..)
+        | SingleExpressionInner::Option(None) => {}
+        SingleExpressionInner::Expression(inner)
+        | SingleExpressionInner::Option(Some(inner))
+        | SingleExpressionInner::Either(Either::Left(inner) | Either::Right(inner)) => {
+            walk(inner, visit);
+        }
+        SingleExpressionInner::Tuple(elements)
+        | SingleExpressionInner::Array(elements)
+        | SingleExpressionInner::List(elements) => {
+            for element in elements.iter() {
+                walk(element, visit);
+            }
+        }
+        SingleExpressionInner::Call(call) => {
+            for arg in call.args().iter() {
+                walk(arg, visit);
+            }
+            visit(Visit::Call(call));
+            match call.name() {
+                CallName::Custom(function)
+                | CallName::Fold(function, ..)
+                | CallName::ArrayFold(function, ..)
+                | CallName::ForWhile(function, ..) => walk(function.body(), visit),
+                _ => {}
+            }
+        }
+        SingleExpressionInner::Match(match_) => {
+            walk(match_.scrutinee(), visit);
+            walk(match_.left().expression(), visit);
+            walk(match_.right().expression(), visit);
+        }
+    }
+}
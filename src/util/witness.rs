@@ -0,0 +1,633 @@
+//! Actionable messages for ill-typed witness and parameter values.
+
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use simplicityhl::elements::secp256k1_zkp::rand::Rng;
+use simplicityhl::error::{Error, RichError};
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::simplicity::BitIter;
+use simplicityhl::types::{StructuralType, TypeInner, UIntType};
+use simplicityhl::value::StructuralValue;
+use simplicityhl::{ResolvedType, Value};
+
+use super::source::{self, ModuleKind};
+use super::walk::{walk, Visit};
+
+/// Convert a 1-based line and column into a byte offset of `text`.
+fn offset_of(text: &str, line: usize, col: usize) -> Option<usize> {
+    let line_start = match line {
+        0 => return None,
+        1 => 0,
+        _ => text.match_indices('\n').nth(line - 2)?.0 + 1,
+    };
+    let line_text = text[line_start..].split('\n').next()?;
+    line_text
+        .char_indices()
+        .nth(col.checked_sub(1)?)
+        .map(|(index, _)| line_start + index)
+}
+
+/// Describe the type of a value expression from its syntax.
+///
+/// Integer literals have no inherent type,
+/// so the description is based on the number of digits.
+fn describe_value(value: &str) -> String {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x") {
+        let bits = hex.chars().filter(char::is_ascii_hexdigit).count() * 4;
+        return format!("a {bits}-bit hexadecimal literal");
+    }
+    if let Some(bin) = value.strip_prefix("0b") {
+        let bits = bin.chars().filter(|c| matches!(c, '0' | '1')).count();
+        return format!("a {bits}-bit binary literal");
+    }
+    if value.starts_with(|c: char| c.is_ascii_digit()) {
+        return "a decimal literal".to_string();
+    }
+    match value {
+        "true" | "false" => return "a boolean".to_string(),
+        "None" => return "an option (`None`)".to_string(),
+        _ => {}
+    }
+    for (prefix, description) in [
+        ("Some(", "an option (`Some`)"),
+        ("Left(", "an either (`Left`)"),
+        ("Right(", "an either (`Right`)"),
+        ("list![", "a list"),
+        ("(", "a tuple"),
+        ("[", "an array"),
+    ] {
+        if value.starts_with(prefix) {
+            return description.to_string();
+        }
+    }
+    format!("the expression `{value}`")
+}
+
+/// Return the value expression of the declaration `const NAME: TYPE = VALUE;`.
+pub fn declared_value(declaration: &str) -> Option<&str> {
+    let (_, value) = declaration.split_once('=')?;
+    Some(value.trim().trim_end_matches(';').trim_end())
+}
+
+/// Return the type of the declaration `const NAME: TYPE = VALUE;`.
+pub fn declared_type(declaration: &str) -> Option<&str> {
+    let (head, _) = declaration.split_once('=')?;
+    let (_, ty) = head.split_once(':')?;
+    Some(ty.trim())
+}
+
+/// Explain an error from parsing the values of the module of the given `kind`.
+///
+/// If the error is located inside a declaration,
+/// then name the declared value, its expected type and the provided value.
+/// Otherwise, return the original error message.
+pub fn explain_value_error(text: &str, kind: ModuleKind, error: &RichError) -> String {
+    if let Error::WitnessReassigned(name) = error.error() {
+        return explain_duplicate(text, kind, name.as_inner()).unwrap_or_else(|| error.to_string());
+    }
+    let Error::ExpressionUnexpectedType(expected) = error.error() else {
+        return error.to_string();
+    };
+    let start = &error.span().start;
+    let Some(offset) = offset_of(text, start.line.get(), start.col.get()) else {
+        return error.to_string();
+    };
+    let Some(declaration) = source::declarations(text, kind)
+        .into_iter()
+        .find(|declaration| declaration.span.contains(&offset))
+    else {
+        return error.to_string();
+    };
+    let declaration_text = &text[declaration.span.clone()];
+    let provided = declared_value(declaration_text)
+        .map_or_else(|| "something else".to_string(), describe_value);
+    let declared = declared_type(declaration_text).unwrap_or_default();
+    let noun = match kind {
+        ModuleKind::Witness => "Witness",
+        ModuleKind::Param => "Parameter",
+    };
+    let mut message = format!(
+        "{noun} `{}` on line {} is declared as `{declared}`, but its value is {provided}.",
+        declaration.name, declaration.line
+    );
+    if declared != expected.to_string() {
+        message.push_str(&format!(" The value was expected to be `{expected}` here."));
+    }
+    format!("{message}\n{error}")
+}
+
+/// Describe the declarations of `name` that occur more than once in the module of the given `kind`.
+fn explain_duplicate(text: &str, kind: ModuleKind, name: &str) -> Option<String> {
+    let (_, lines) = source::duplicate_declarations(text, kind)
+        .into_iter()
+        .find(|(duplicate, _)| duplicate == name)?;
+    Some(format!(
+        "`{name}` is declared more than once in `mod {}`, on lines {}. \
+        Remove all but one declaration.",
+        kind.name(),
+        lines
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))
+}
+
+/// Warn about all names that are declared more than once in the witness or param module.
+pub fn duplicate_warnings(text: &str) -> Vec<String> {
+    ModuleKind::ALL
+        .into_iter()
+        .flat_map(|kind| {
+            source::duplicate_declarations(text, kind)
+                .into_iter()
+                .filter_map(move |(name, _)| explain_duplicate(text, kind, &name))
+        })
+        .collect()
+}
+
+/// Explain an error from satisfying a program with its witness values.
+pub fn explain_satisfaction_error(error: String) -> String {
+    match error.strip_prefix("missing witness for ") {
+        Some(name) => format!(
+            "Witness `{name}` is used by the program, but it is not declared in `mod witness`. \
+            Add a declaration like `const {name}: TYPE = VALUE;`."
+        ),
+        None => error,
+    }
+}
+
+/// Kind of input field for entering a value of a given type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// Checkbox for a boolean.
+    Boolean,
+    /// Number field for an integer that fits into 64 bits.
+    Integer,
+    /// Text field for a hexadecimal literal of the given number of bytes.
+    Hex(usize),
+    /// Text field for an arbitrary value expression.
+    Expression,
+}
+
+impl InputKind {
+    /// Return the kind of input field for values of the given type.
+    pub fn of(ty: &ResolvedType) -> Self {
+        match ty.as_inner() {
+            TypeInner::Boolean => Self::Boolean,
+            TypeInner::UInt(UIntType::U128) => Self::Hex(16),
+            TypeInner::UInt(UIntType::U256) => Self::Hex(32),
+            TypeInner::UInt(..) => Self::Integer,
+            TypeInner::Array(element, size)
+                if matches!(element.as_inner(), TypeInner::UInt(UIntType::U8)) =>
+            {
+                Self::Hex(*size)
+            }
+            _ => Self::Expression,
+        }
+    }
+
+    /// Convert the content of an input field into a value expression.
+    pub fn to_expression(self, input: &str) -> String {
+        let input = input.trim();
+        match self {
+            Self::Hex(..) if !input.starts_with("0x") => format!("0x{input}"),
+            _ => input.to_string(),
+        }
+    }
+
+    /// Return the placeholder of an empty input field.
+    pub fn placeholder(self) -> String {
+        match self {
+            Self::Boolean => String::new(),
+            Self::Integer => "0".to_string(),
+            Self::Hex(bytes) => format!("0x… ({} hex digits)", bytes * 2),
+            Self::Expression => "value expression".to_string(),
+        }
+    }
+}
+
+/// Return the error of the module of the given `kind`, if it has one.
+///
+/// Only the module itself is checked, so errors elsewhere in the program are ignored.
+/// A missing module has no error.
+pub fn module_error(text: &str, kind: ModuleKind) -> Option<String> {
+    let span = source::module_span(text, kind)?;
+    let module = &text[span];
+    let result = match kind {
+        ModuleKind::Witness => simplicityhl::WitnessValues::parse_from_str(module).map(|_| ()),
+        ModuleKind::Param => simplicityhl::Arguments::parse_from_str(module).map(|_| ()),
+    };
+    result
+        .err()
+        .map(|error| explain_value_error(module, kind, &error))
+}
+
+/// Check that the value expression `expression` is a value of the type `ty`.
+pub fn check_value(expression: &str, ty: &str) -> Result<(), String> {
+    let ty = ResolvedType::parse_from_str(ty).map_err(|error| error.to_string())?;
+    Value::parse_from_str(expression, &ty)
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+/// Value of a witness in the JSON format of witness files.
+#[derive(serde::Deserialize)]
+struct JsonValue {
+    value: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// Read witness values that were pasted from another tool.
+///
+/// The pasted text is either a witness module, the declarations of a witness module,
+/// or JSON that maps the name of each witness to its value and type,
+/// like the witness files of `simc`.
+/// Each pasted witness must be one of the `declared` witnesses of the program,
+/// which are pairs of names and types, and it must have the declared type.
+///
+/// Return the names and value expressions of the pasted witnesses, in order of occurrence.
+pub fn parse_pasted(
+    pasted: &str,
+    declared: &[(String, String)],
+) -> Result<Vec<(String, String)>, String> {
+    let pasted = pasted.trim();
+    let module = if pasted.starts_with('{') {
+        let values: std::collections::BTreeMap<String, JsonValue> = serde_json::from_str(pasted)
+            .map_err(|error| format!("The pasted JSON is not a map of witness values: {error}"))?;
+        let declarations: String = values
+            .iter()
+            .map(|(name, value)| format!("    const {name}: {} = {};\n", value.ty, value.value))
+            .collect();
+        format!("mod witness {{\n{declarations}}}")
+    } else if pasted.starts_with("mod") {
+        pasted.to_string()
+    } else {
+        format!("mod witness {{\n{pasted}\n}}")
+    };
+    let values = simplicityhl::WitnessValues::parse_from_str(&module)
+        .map_err(|error| explain_value_error(&module, ModuleKind::Witness, &error))?;
+
+    let mut pasted_values = vec![];
+    for declaration in source::declarations(&module, ModuleKind::Witness) {
+        let name = declaration.name;
+        let Some((_, declared_ty)) = declared.iter().find(|(declared, _)| *declared == name) else {
+            return Err(format!("The program does not use a witness `{name}`"));
+        };
+        let witness_name = simplicityhl::str::WitnessName::from_str_unchecked(&name);
+        let declared_ty =
+            ResolvedType::parse_from_str(declared_ty).map_err(|error| error.to_string())?;
+        if let Some(value) = values.get(&witness_name) {
+            if *value.ty() != declared_ty {
+                return Err(format!(
+                    "Witness `{name}` is declared as `{declared_ty}`, but the pasted value has type `{}`",
+                    value.ty()
+                ));
+            }
+        }
+        let Some(value) = declared_value(&module[declaration.span]) else {
+            continue;
+        };
+        pasted_values.push((name, value.to_string()));
+    }
+    if pasted_values.is_empty() {
+        return Err("The pasted text contains no witness values".to_string());
+    }
+    Ok(pasted_values)
+}
+
+/// Return the names of the witnesses in the program `text` whose values look private,
+/// such as signatures, secret keys and preimages.
+///
+/// Values that consist of a single repeated digit, such as `0x0000…`, look like placeholders.
+pub fn private_looking(text: &str) -> Vec<String> {
+    const PRIVATE_NAMES: [&str; 6] = ["sig", "key", "secret", "preimage", "priv", "seed"];
+    source::declarations(text, ModuleKind::Witness)
+        .into_iter()
+        .filter(|declaration| {
+            let source = &text[declaration.span.clone()];
+            let (Some(ty), Some(value)) = (declared_type(source), declared_value(source)) else {
+                return false;
+            };
+            let name = declaration.name.to_lowercase();
+            let private =
+                ty == "Signature" || PRIVATE_NAMES.iter().any(|private| name.contains(private));
+            let mut digits = value
+                .trim_start_matches("0x")
+                .chars()
+                .filter(char::is_ascii_hexdigit);
+            let placeholder = digits
+                .next()
+                .map_or(true, |first| digits.all(|digit| digit == first));
+            private && !placeholder
+        })
+        .map(|declaration| declaration.name)
+        .collect()
+}
+
+/// Generate random values for the `declared` witnesses, which are pairs of names and types.
+///
+/// The same `seed` always generates the same values, so random runs can be reproduced.
+///
+/// Return the names and value expressions of the witnesses, in the order of `declared`.
+pub fn random_values(
+    declared: &[(String, String)],
+    seed: u64,
+) -> Result<Vec<(String, String)>, String> {
+    // Unlike `StdRng`, the algorithm of `ChaCha8Rng` never changes, so seeds stay reproducible
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    declared
+        .iter()
+        .map(|(name, ty)| {
+            let ty = ResolvedType::parse_from_str(ty).map_err(|error| error.to_string())?;
+            let structural = StructuralType::from(&ty);
+            // Random bits never run out, so decoding cannot fail
+            let mut bits = BitIter::new(std::iter::repeat_with(|| rng.gen::<u8>()));
+            let value =
+                simplicityhl::simplicity::Value::from_compact_bits(&mut bits, structural.as_ref())
+                    .expect("random bits are endless");
+            let value = Value::reconstruct(&StructuralValue::from(value), &ty)
+                .expect("decoded value has the declared type");
+            Ok((name.clone(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Return the value of the type `ty` whose bits are all zero, which serves as a placeholder.
+fn zero_value(ty: &ResolvedType) -> String {
+    let structural = StructuralType::from(ty);
+    let mut bits = BitIter::new(std::iter::repeat(0_u8));
+    let value = simplicityhl::simplicity::Value::from_compact_bits(&mut bits, structural.as_ref())
+        .expect("zero bits are endless");
+    Value::reconstruct(&StructuralValue::from(value), ty)
+        .expect("decoded value has the declared type")
+        .to_string()
+}
+
+/// Return the program `text` with a witness module that declares each witness that the program
+/// uses, with a comment that shows its type.
+///
+/// Witnesses that are already declared with a value of their type keep that value;
+/// the others get a placeholder value.
+/// Return an error if the program is not analyzed far enough to know its witnesses,
+/// or if it uses no witnesses.
+pub fn stub_witness_module(text: &str) -> Result<String, String> {
+    let parsed = simplicityhl::parse::Program::parse_from_str(text).map_err(|error| {
+        format!("The program must parse before its witnesses are known.\n{error}")
+    })?;
+    let program = simplicityhl::ast::Program::analyze(&parsed).map_err(|error| {
+        format!("The program must type-check before its witnesses are known.\n{error}")
+    })?;
+    let mut types: Vec<_> = program.witness_types().iter().collect();
+    if types.is_empty() {
+        return Err("The program uses no witnesses.".to_string());
+    }
+    types.sort_by_key(|(name, _)| name.to_string());
+    let declared = source::declarations(text, ModuleKind::Witness);
+    let declarations: String = types
+        .into_iter()
+        .map(|(name, ty)| {
+            let ty_name = ty.to_string();
+            let value = declared
+                .iter()
+                .find(|declaration| declaration.name == name.as_inner())
+                .and_then(|declaration| declared_value(&text[declaration.span.clone()]))
+                .filter(|value| check_value(value, &ty_name).is_ok())
+                .map_or_else(|| zero_value(ty), str::to_string);
+            format!("    // {ty_name}\n    const {name}: {ty_name} = {value};\n")
+        })
+        .collect();
+    let module = format!("mod witness {{\n{declarations}}}");
+    Ok(source::replace_module(text, ModuleKind::Witness, &module))
+}
+
+/// Return the names of the witnesses of the program `text`
+/// in the order in which the compiler translates their uses.
+///
+/// This is the order of the witness data of the satisfied program.
+/// Return an error if the program is not analyzed far enough to know its witnesses.
+pub fn witness_order(text: &str) -> Result<Vec<String>, String> {
+    let parsed = simplicityhl::parse::Program::parse_from_str(text).map_err(|error| {
+        format!("The program must parse before the order of its witnesses is known.\n{error}")
+    })?;
+    let program = simplicityhl::ast::Program::analyze(&parsed).map_err(|error| {
+        format!("The program must type-check before the order of its witnesses is known.\n{error}")
+    })?;
+    // Each witness is used exactly once, in the main function
+    let mut order = Vec::new();
+    walk(program.main(), &mut |visit| {
+        if let Visit::Witness(name) = visit {
+            order.push(name.to_string());
+        }
+    });
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use simplicityhl::parse::ParseFromStr;
+    use simplicityhl::WitnessValues;
+
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn compiled_witness_order() {
+        let text = "fn main() {
+    let first: u8 = witness::FIRST;
+    let choice: Either<u8, u8> = witness::CHOICE;
+    let second: u8 = match choice {
+        Left(x: u8) => x,
+        Right(y: u8) => witness::SECOND,
+    };
+    assert!(jet::eq_8(first, second));
+}";
+        assert_eq!(
+            vec![
+                "FIRST".to_string(),
+                "CHOICE".to_string(),
+                "SECOND".to_string()
+            ],
+            witness_order(text).unwrap()
+        );
+        assert!(witness_order("fn main() {").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn explain_wrong_literal() {
+        let text = "mod witness {\n    const X: u8 = 0x0001;\n}\n\nfn main() {}";
+        let error = WitnessValues::parse_from_str(text).unwrap_err();
+        let message = explain_value_error(text, ModuleKind::Witness, &error);
+        assert!(message.starts_with(
+            "Witness `X` on line 2 is declared as `u8`, but its value is a 16-bit hexadecimal literal."
+        ));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn explain_wrong_structure() {
+        let text = "mod witness {\n    const SIG: Signature = (1, 2);\n}\n\nfn main() {}";
+        let error = WitnessValues::parse_from_str(text).unwrap_err();
+        let message = explain_value_error(text, ModuleKind::Witness, &error);
+        assert!(message.contains("Witness `SIG`"));
+        assert!(message.contains("`Signature`"));
+        assert!(message.contains("a tuple"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn private_looking_witnesses() {
+        let text = "mod witness {
+    const SIG: Signature = 0xf74b3ca574647f8595624b129324afa2f38b598a9c1c7cfc5f08a9c036ec5acd3c0fbb9ed3dae5ca23a0a65a34b5d6cccdd6ba248985d6041f7b21262b17af6f;
+    const EMPTY_SIG: Signature = 0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000;
+    const SECRET_PREIMAGE: u256 = 0x3f2a9b7c;
+    const INDEX: u32 = 7;
+}
+
+fn main() {}";
+        assert_eq!(vec!["SIG", "SECRET_PREIMAGE"], private_looking(text));
+        assert!(private_looking("fn main() {}").is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn explain_missing_witness() {
+        let message = explain_satisfaction_error("missing witness for SIG".to_string());
+        assert!(message.starts_with("Witness `SIG` is used by the program"));
+        let other = "some other error".to_string();
+        assert_eq!(other, explain_satisfaction_error(other.clone()));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn input_kinds() {
+        let text = "mod witness {}\n\nfn main() {\n    let a: bool = witness::A;\n    \
+            let b: u32 = witness::B;\n    let c: u256 = witness::C;\n    \
+            let d: Signature = witness::D;\n    let e: Option<u8> = witness::E;\n}";
+        let parsed = simplicityhl::parse::Program::parse_from_str(text).unwrap();
+        let program = simplicityhl::ast::Program::analyze(&parsed).unwrap();
+        let kind = |name: &str| {
+            let name = simplicityhl::str::WitnessName::from_str_unchecked(name);
+            InputKind::of(program.witness_types().get(&name).unwrap())
+        };
+        assert_eq!(InputKind::Boolean, kind("A"));
+        assert_eq!(InputKind::Integer, kind("B"));
+        assert_eq!(InputKind::Hex(32), kind("C"));
+        assert_eq!(InputKind::Hex(64), kind("D"));
+        assert_eq!(InputKind::Expression, kind("E"));
+        assert_eq!("0xab", InputKind::Hex(1).to_expression(" ab "));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn find_module_errors() {
+        let text = "mod witness {\n    const A: u8 = 256;\n}\n\nfn main() { oops }";
+        assert!(module_error(text, ModuleKind::Witness).is_some());
+        assert!(module_error(text, ModuleKind::Param).is_none());
+        let text = "mod param {\n    const A: u8 = 1;\n}\n\nfn main() { oops }";
+        assert!(module_error(text, ModuleKind::Param).is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn check_values() {
+        assert!(check_value("255", "u8").is_ok());
+        assert!(check_value("256", "u8").is_err());
+        assert!(check_value("(0x01, true)", "(u8, bool)").is_ok());
+        assert!(check_value("0x01", "bool").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn explain_duplicate_witness() {
+        let text = "mod witness {\n    const A: u8 = 1;\n    const A: u8 = 2;\n}\n\nfn main() {}";
+        let error = WitnessValues::parse_from_str(text).unwrap_err();
+        let message = explain_value_error(text, ModuleKind::Witness, &error);
+        assert_eq!(
+            "`A` is declared more than once in `mod witness`, on lines 2, 3. \
+            Remove all but one declaration.",
+            message
+        );
+        assert_eq!(vec![message], duplicate_warnings(text));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_pasted_witness() {
+        let declared = [
+            ("A".to_string(), "u8".to_string()),
+            ("B".to_string(), "bool".to_string()),
+        ];
+        let expected = vec![("A".to_string(), "1".to_string())];
+        assert_eq!(
+            parse_pasted("mod witness {\n    const A: u8 = 1;\n}", &declared).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_pasted("const A: u8 = 1;", &declared).unwrap(),
+            expected
+        );
+        let json =
+            r#"{ "A": { "value": "1", "type": "u8" }, "B": { "value": "true", "type": "bool" } }"#;
+        assert_eq!(
+            parse_pasted(json, &declared).unwrap(),
+            [
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "true".to_string())
+            ]
+        );
+
+        assert!(parse_pasted("const A: u16 = 1;", &declared).is_err());
+        assert!(parse_pasted("const C: u8 = 1;", &declared).is_err());
+        assert!(parse_pasted("const A: u8 = 256;", &declared).is_err());
+        assert!(parse_pasted("{ \"A\": 1 }", &declared).is_err());
+        assert!(parse_pasted("", &declared).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn seeded_random_values() {
+        let declared = [
+            ("A".to_string(), "u8".to_string()),
+            ("B".to_string(), "(bool, Option<u32>)".to_string()),
+            ("C".to_string(), "List<u16, 4>".to_string()),
+        ];
+        let values = random_values(&declared, 7).unwrap();
+        assert_eq!(values, random_values(&declared, 7).unwrap());
+        for ((name, ty), (value_name, value)) in declared.iter().zip(&values) {
+            assert_eq!(name, value_name);
+            assert!(check_value(value, ty).is_ok(), "{value}: {ty}");
+        }
+        let differs = (0..8).any(|seed| random_values(&declared, seed).unwrap() != values);
+        assert!(differs);
+        assert!(random_values(&[("A".to_string(), "oops".to_string())], 0).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn stub_witnesses() {
+        let text = "mod witness {\n    const B: u32 = 7;\n}\n\nfn main() {\n    \
+            let a: Signature = witness::A;\n    let b: u32 = witness::B;\n    \
+            let c: Either<bool, u8> = witness::C;\n}";
+        let stubbed = stub_witness_module(text).unwrap();
+        let expected_module = format!(
+            "mod witness {{\n    // [u8; 64]\n    const A: [u8; 64] = 0x{};\n    \
+            // u32\n    const B: u32 = 7;\n    \
+            // Either<bool,u8>\n    const C: Either<bool,u8> = Left(false);\n}}",
+            "00".repeat(64)
+        );
+        assert!(stubbed.starts_with(&expected_module), "{stubbed}");
+        assert!(WitnessValues::parse_from_str(&stubbed).is_ok());
+        assert_eq!(stub_witness_module(&stubbed).unwrap(), stubbed);
+
+        let appended = stub_witness_module("fn main() { assert!(witness::X); }").unwrap();
+        assert!(appended
+            .starts_with("mod witness {\n    // bool\n    const X: bool = false;\n}\n\nfn main()"));
+        assert!(stub_witness_module("fn main() { assert!(true); }").is_err());
+        assert!(
+            stub_witness_module("fn main() { let x: u8 = witness::X; let y: u16 = x; }").is_err()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Messages between the IDE and the Web Worker that runs batches of programs.
+//!
+//! The IDE sends a [`BatchRequest`] and the worker answers with a [`BatchResponse`],
+//! both as JSON text. Errors cross as the messages that the IDE shows,
+//! because the errors of the compiler cannot be serialized.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::pipeline::{self, ProgramError, RunOutcome};
+use crate::transaction::TxParams;
+
+/// Programs that the worker runs one after another.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRequest {
+    /// Number of the batch, which the response repeats.
+    pub id: usize,
+    /// Text of each program with the [`TxParams::to_strings`] of its transaction environment.
+    pub runs: Vec<(String, Vec<String>)>,
+    /// Number of steps after which each run is aborted, if there is a limit.
+    pub step_limit: Option<usize>,
+    /// Whether the programs are compiled with debug symbols, like the program of the IDE.
+    pub debug_symbols: bool,
+}
+
+impl BatchRequest {
+    pub fn new(
+        id: usize,
+        runs: &[(String, TxParams)],
+        step_limit: Option<usize>,
+        debug_symbols: bool,
+    ) -> Self {
+        Self {
+            id,
+            runs: runs
+                .iter()
+                .map(|(text, params)| (text.clone(), params.to_strings().to_vec()))
+                .collect(),
+            step_limit,
+            debug_symbols,
+        }
+    }
+}
+
+/// Reports of the runs of a batch, in the order of the request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub id: usize,
+    pub reports: Vec<RunReport>,
+}
+
+/// Outcome of a run, with the error as its message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunReport {
+    /// Whether the run succeeded, or why it failed.
+    pub result: Result<(), String>,
+    /// Output of the `dbg!` expressions.
+    pub debug_output: Vec<String>,
+    /// Number of executed steps.
+    pub steps: usize,
+}
+
+impl From<RunOutcome> for RunReport {
+    fn from(outcome: RunOutcome) -> Self {
+        Self {
+            result: outcome.result.map_err(|error| error.to_string()),
+            debug_output: outcome.debug_output,
+            steps: outcome.steps,
+        }
+    }
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self
+            .result
+            .as_ref()
+            .map_err(|error| error as &dyn fmt::Display);
+        pipeline::write_outcome(f, result, self.steps, &self.debug_output)
+    }
+}
+
+/// Run the batch of the JSON `request` and return the JSON of its response.
+///
+/// Return `None` if the request is not a batch.
+pub fn handle_request(request: &str) -> Option<String> {
+    let request: BatchRequest = serde_json::from_str(request).ok()?;
+    let reports = request
+        .runs
+        .iter()
+        .map(|(text, values)| {
+            let outcome = match TxParams::from_strings(values.iter().cloned()) {
+                Some(params) => pipeline::run_text_with_symbols(
+                    text,
+                    &params,
+                    request.step_limit,
+                    request.debug_symbols,
+                ),
+                None => RunOutcome::failed(ProgramError::Environment(
+                    "The transaction environment of the run is invalid".to_string(),
+                )),
+            };
+            RunReport::from(outcome)
+        })
+        .collect();
+    let response = BatchResponse {
+        id: request.id,
+        reports,
+    };
+    Some(serde_json::to_string(&response).expect("reports can be serialized"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn handle_batch() {
+        let passing = "fn main() { assert!(jet::eq_32(dbg!(7), 7)); }".to_string();
+        let failing = "fn main() { assert!(jet::eq_32(7, 8)); }".to_string();
+        let params = TxParams::default();
+        let runs = [(passing.clone(), params.clone()), (failing.clone(), params)];
+        let mut reports = vec![];
+        for debug_symbols in [false, true] {
+            let request = BatchRequest::new(3, &runs, None, debug_symbols);
+            let request = serde_json::to_string(&request).unwrap();
+            let response: BatchResponse =
+                serde_json::from_str(&handle_request(&request).unwrap()).unwrap();
+            assert_eq!(response.id, 3);
+            let expected: Vec<RunReport> = runs
+                .iter()
+                .map(|(text, params)| {
+                    pipeline::run_text_with_symbols(text, params, None, debug_symbols).into()
+                })
+                .collect();
+            assert_eq!(response.reports, expected);
+            assert_eq!(response.reports[0].result, Ok(()));
+            assert!(response.reports[1].result.is_err());
+            reports.push(response.reports);
+        }
+        // Only debug symbols tell which call failed
+        assert_ne!(reports[0][1].result, reports[1][1].result);
+
+        assert_eq!(handle_request("{}"), None);
+        let mut invalid = BatchRequest::new(4, &runs[..1], Some(10), false);
+        invalid.runs[0].1.clear();
+        let request = serde_json::to_string(&invalid).unwrap();
+        let response: BatchResponse =
+            serde_json::from_str(&handle_request(&request).unwrap()).unwrap();
+        assert!(response.reports[0].result.is_err());
+    }
+}